@@ -0,0 +1,230 @@
+//! A crater-style corpus runner: points `pcg_bin` at a directory of crate
+//! sources (or `.crate` tarballs) and, instead of stopping at the first
+//! failure like `tests/top_crates.rs`'s `#[ignore]` test does, records a
+//! structured outcome per crate and emits one aggregate JSON report. A
+//! regression in how much of the corpus PCG handles then shows up as a
+//! diff in that report, rather than as "the crater test failed on crate
+//! #37 this time".
+//!
+//! Per-function success/unsupported/error/timing comes from the
+//! `function_reports.json` artifact `pcg_bin` already writes under
+//! `PCG_VISUALIZATION_DATA_DIR` (see `FunctionReport` in
+//! `src/utils/callbacks.rs`); this binary adds the layer above that: if a
+//! crate fails to analyze at all (`cargo check` exits non-zero, e.g.
+//! because of an internal compiler error in one of its functions), the
+//! whole crate is recorded as `crashed` rather than aborting the run --
+//! we're scanning a corpus, not asserting that any one crate is
+//! PCG-clean.
+//!
+//! Usage: `pcg-corpus <crates-dir> [--pcg-bin <path>] [--out <path>]`.
+//! `<crates-dir>`'s entries may be crate source directories (containing a
+//! `Cargo.toml`) and/or `.crate` tarballs, which are extracted first.
+//! `--pcg-bin` defaults to `./pcg_bin`, `--out` to `corpus_report.json`.
+
+use std::{
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use serde::Serialize;
+
+fn main() {
+    let args: Vec<String> = std::env::args().collect();
+    let Some(crates_dir) = args.get(1) else {
+        eprintln!("usage: pcg-corpus <crates-dir> [--pcg-bin <path>] [--out <path>]");
+        std::process::exit(1);
+    };
+    let pcg_bin = option_arg(&args, "--pcg-bin").unwrap_or_else(|| "./pcg_bin".to_string());
+    let out = option_arg(&args, "--out").unwrap_or_else(|| "corpus_report.json".to_string());
+
+    let crates = collect_crates(Path::new(crates_dir));
+    let reports: Vec<CrateReport> = crates
+        .iter()
+        .map(|krate| {
+            eprintln!("Running pcg_bin on {}", krate.name);
+            run_on_crate(krate, Path::new(&pcg_bin))
+        })
+        .collect();
+
+    let summary = Summary::from_reports(&reports);
+    eprintln!(
+        "{} crate(s): {} clean, {} crashed, {} with unsupported/erroring functions",
+        summary.total_crates,
+        summary.clean_crates,
+        summary.crashed_crates,
+        summary.crates_with_issues
+    );
+
+    // Mirrors the `{"schema_version", "data"}` envelope `pcg::output::versioned`
+    // wraps every top-level visualization artifact in, so this report can grow
+    // non-additively later without silently breaking whatever reads it.
+    let report_json = serde_json::json!({
+        "schema_version": 1,
+        "data": { "summary": summary, "crates": reports },
+    });
+    std::fs::write(&out, report_json.to_string())
+        .unwrap_or_else(|e| panic!("Failed to write report to {out}: {e}"));
+    eprintln!("Wrote {out}");
+}
+
+fn option_arg(args: &[String], flag: &str) -> Option<String> {
+    args.iter()
+        .position(|a| a == flag)
+        .and_then(|i| args.get(i + 1))
+        .cloned()
+}
+
+/// A crate source directory, ready for `cargo check`. `_extracted_to` keeps
+/// a tarball's temporary extraction directory alive for as long as the
+/// crate is in scope.
+struct Crate {
+    name: String,
+    dir: PathBuf,
+    _extracted_to: Option<tempfile::TempDir>,
+}
+
+/// Finds every crate under `crates_dir`: subdirectories containing a
+/// `Cargo.toml` are used directly, `.crate` tarballs are extracted into a
+/// temporary directory first. Anything else is skipped.
+fn collect_crates(crates_dir: &Path) -> Vec<Crate> {
+    let mut crates = Vec::new();
+    for entry in std::fs::read_dir(crates_dir)
+        .unwrap_or_else(|e| panic!("Failed to read {}: {e}", crates_dir.display()))
+    {
+        let path = entry.unwrap().path();
+        let name = path.file_stem().unwrap().to_string_lossy().into_owned();
+        if path.is_dir() {
+            if path.join("Cargo.toml").exists() {
+                crates.push(Crate {
+                    name,
+                    dir: path,
+                    _extracted_to: None,
+                });
+            }
+        } else if path.extension().is_some_and(|ext| ext == "crate") {
+            let extracted = tempfile::tempdir().expect("Failed to create temp dir");
+            let status = Command::new("tar")
+                .arg("-xf")
+                .arg(&path)
+                .arg("-C")
+                .arg(extracted.path())
+                .status()
+                .unwrap_or_else(|e| panic!("Failed to extract {}: {e}", path.display()));
+            assert!(status.success(), "Failed to extract {}", path.display());
+            let dir = std::fs::read_dir(extracted.path())
+                .unwrap()
+                .find_map(|e| e.ok().map(|e| e.path()).filter(|p| p.is_dir()))
+                .unwrap_or_else(|| panic!("{} did not extract to a directory", path.display()));
+            crates.push(Crate {
+                name,
+                dir,
+                _extracted_to: Some(extracted),
+            });
+        }
+    }
+    crates
+}
+
+fn run_on_crate(krate: &Crate, pcg_bin: &Path) -> CrateReport {
+    let data_dir = tempfile::tempdir().expect("Failed to create temp dir");
+    let status = Command::new("cargo")
+        .arg("check")
+        .current_dir(&krate.dir)
+        .env("RUSTC", pcg_bin)
+        .env("PCG_VISUALIZATION", "true")
+        .env("PCG_VISUALIZATION_DATA_DIR", data_dir.path())
+        .status();
+
+    let outcome = match status {
+        Ok(status) if status.success() => CrateOutcome::Analyzed,
+        Ok(status) => CrateOutcome::Crashed {
+            message: format!("cargo check exited with {status}"),
+        },
+        Err(e) => CrateOutcome::Crashed {
+            message: format!("failed to run cargo check: {e}"),
+        },
+    };
+
+    let functions = std::fs::read_to_string(data_dir.path().join("function_reports.json"))
+        .ok()
+        .and_then(|contents| serde_json::from_str::<serde_json::Value>(&contents).ok())
+        .map(|report| report["data"].clone())
+        .unwrap_or_else(|| serde_json::json!([]));
+
+    CrateReport {
+        name: krate.name.clone(),
+        outcome,
+        functions,
+    }
+}
+
+#[derive(Serialize)]
+struct CrateReport {
+    name: String,
+    outcome: CrateOutcome,
+    /// The `data` array from `function_reports.json`, verbatim -- see
+    /// `FunctionReport` in `src/utils/callbacks.rs` for its shape. Empty if
+    /// the crate crashed before writing one, or had no checkable functions.
+    functions: serde_json::Value,
+}
+
+#[derive(Serialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+enum CrateOutcome {
+    Analyzed,
+    Crashed { message: String },
+}
+
+#[derive(Serialize)]
+struct Summary {
+    total_crates: usize,
+    clean_crates: usize,
+    crashed_crates: usize,
+    crates_with_issues: usize,
+    total_functions: usize,
+    successful_functions: usize,
+    unsupported_functions: usize,
+    erroring_functions: usize,
+}
+
+impl Summary {
+    fn from_reports(reports: &[CrateReport]) -> Self {
+        let mut summary = Summary {
+            total_crates: reports.len(),
+            clean_crates: 0,
+            crashed_crates: 0,
+            crates_with_issues: 0,
+            total_functions: 0,
+            successful_functions: 0,
+            unsupported_functions: 0,
+            erroring_functions: 0,
+        };
+        for report in reports {
+            if matches!(report.outcome, CrateOutcome::Crashed { .. }) {
+                summary.crashed_crates += 1;
+                continue;
+            }
+            let mut crate_has_issues = false;
+            for function in report.functions.as_array().into_iter().flatten() {
+                summary.total_functions += 1;
+                match function["outcome"]["status"].as_str() {
+                    Some("success") => summary.successful_functions += 1,
+                    Some("unsupported") => {
+                        summary.unsupported_functions += 1;
+                        crate_has_issues = true;
+                    }
+                    _ => {
+                        summary.erroring_functions += 1;
+                        crate_has_issues = true;
+                    }
+                }
+            }
+            if crate_has_issues {
+                summary.crates_with_issues += 1;
+            } else {
+                summary.clean_crates += 1;
+            }
+        }
+        summary
+    }
+}
@@ -0,0 +1,220 @@
+//! A structural diff tool for two PCG analysis runs, for use as a
+//! regression gate (e.g. in CI, comparing a PR branch against `main`, or
+//! bisecting a refactor that's suspected to have changed analysis results).
+//!
+//! Rather than inventing a new serialization format, this reads the
+//! per-function cache files `pcg_bin` already writes when
+//! `PCG_INCREMENTAL_CACHE_DIR` is set (see `src/utils/incremental.rs`):
+//! one `<item_name>.json` file per analysed function, containing
+//! `{"body_hash": ..., "debug_lines": [...]}`, where `debug_lines` is
+//! `PcgBasicBlock::debug_lines`'s output for each block: per statement
+//! phase, `Pcg::debug_lines` (sorted borrow-edge short strings followed
+//! by sorted `"<place>: <capability>"` lines; see `src/pcg/domain.rs`)
+//! and the repack-action lines, each prefixed with its location and
+//! phase. So: run `pcg_bin` twice (before/after) with
+//! `PCG_INCREMENTAL_CACHE_DIR` pointed at two different directories, then
+//! point this tool at those two directories.
+//!
+//! Usage: `pcg-diff <before-dir> <after-dir> [--out <path>]`. `--out`
+//! defaults to `pcg_diff_report.json`. Exits `1` if any function differs
+//! (added/removed/changed), `0` otherwise, so it can gate a CI job.
+
+use std::{collections::BTreeMap, path::Path};
+
+use serde::Serialize;
+
+fn main() {
+    let args: Vec<String> = std::env::args().collect();
+    let (Some(before_dir), Some(after_dir)) = (args.get(1), args.get(2)) else {
+        eprintln!("usage: pcg-diff <before-dir> <after-dir> [--out <path>]");
+        std::process::exit(2);
+    };
+    let out = option_arg(&args, "--out").unwrap_or_else(|| "pcg_diff_report.json".to_string());
+
+    let before = load_summaries(Path::new(before_dir));
+    let after = load_summaries(Path::new(after_dir));
+
+    let mut function_names: Vec<&String> = before.keys().chain(after.keys()).collect();
+    function_names.sort();
+    function_names.dedup();
+
+    let mut diffs = Vec::new();
+    for name in function_names {
+        match (before.get(name), after.get(name)) {
+            (Some(before), Some(after)) => {
+                let diff = FunctionDiff::between(before, after);
+                if !diff.is_empty() {
+                    diffs.push((name.clone(), diff));
+                }
+            }
+            (Some(_), None) => diffs.push((name.clone(), FunctionDiff::removed())),
+            (None, Some(_)) => diffs.push((name.clone(), FunctionDiff::added())),
+            (None, None) => unreachable!("name came from one of the two maps"),
+        }
+    }
+
+    eprintln!(
+        "{} function(s) compared, {} with differences",
+        before.len().max(after.len()),
+        diffs.len()
+    );
+
+    let diff_count = diffs.len();
+    let functions: BTreeMap<String, FunctionDiff> = diffs.into_iter().collect();
+
+    // Mirrors the `{"schema_version", "data"}` envelope `pcg::output::versioned`
+    // wraps every top-level visualization artifact in.
+    let report_json = serde_json::json!({
+        "schema_version": 1,
+        "data": { "functions": functions },
+    });
+    std::fs::write(&out, report_json.to_string())
+        .unwrap_or_else(|e| panic!("Failed to write report to {out}: {e}"));
+    eprintln!("Wrote {out}");
+
+    std::process::exit(if diff_count == 0 { 0 } else { 1 });
+}
+
+fn option_arg(args: &[String], flag: &str) -> Option<String> {
+    args.iter()
+        .position(|a| a == flag)
+        .and_then(|i| args.get(i + 1))
+        .cloned()
+}
+
+/// Mirrors the shape `src/utils/incremental.rs`'s `CachedSummary` writes;
+/// duplicated here rather than imported since that type is private to the
+/// `pcg` crate and this is a separate binary consuming its on-disk
+/// contract, not its Rust API.
+#[derive(serde::Deserialize)]
+struct CachedSummary {
+    #[allow(dead_code)]
+    body_hash: u64,
+    debug_lines: Vec<String>,
+}
+
+/// Loads every `<item_name>.json` cache file directly inside `dir`, keyed
+/// by function name (the file stem).
+fn load_summaries(dir: &Path) -> BTreeMap<String, CachedSummary> {
+    let mut summaries = BTreeMap::new();
+    let entries = std::fs::read_dir(dir)
+        .unwrap_or_else(|e| panic!("Failed to read {}: {e}", dir.display()));
+    for entry in entries {
+        let path = entry.unwrap().path();
+        if path.extension().map_or(true, |ext| ext != "json") {
+            continue;
+        }
+        let item_name = path.file_stem().unwrap().to_string_lossy().into_owned();
+        let contents = std::fs::read_to_string(&path)
+            .unwrap_or_else(|e| panic!("Failed to read {}: {e}", path.display()));
+        let summary: CachedSummary = serde_json::from_str(&contents)
+            .unwrap_or_else(|e| panic!("Failed to parse {}: {e}", path.display()));
+        summaries.insert(item_name, summary);
+    }
+    summaries
+}
+
+/// A `debug_lines` entry is a capability line (`"<place>: <capability>"`)
+/// rather than a borrow-edge line iff it ends in one of `CapabilityKind`'s
+/// single-letter `Debug` renderings (`R`/`W`/`E`/`e`; see
+/// `src/free_pcs/impl/place.rs`). This is a heuristic tied to that specific
+/// `Debug` impl rather than a real structured field, since `debug_lines`
+/// flattens both kinds into one `Vec<String>` with no other marker -- if
+/// `CapabilityKind::Debug` ever changes format, this split needs to follow
+/// it.
+fn is_capability_line(line: &str) -> bool {
+    line.rsplit_once(": ")
+        .is_some_and(|(_, cap)| matches!(cap, "R" | "W" | "E" | "e"))
+}
+
+#[derive(Clone, Serialize, Default)]
+struct FunctionDiff {
+    status: Option<&'static str>,
+    edges_added: Vec<String>,
+    edges_removed: Vec<String>,
+    capabilities_added: Vec<String>,
+    capabilities_removed: Vec<String>,
+    capabilities_changed: Vec<String>,
+}
+
+impl FunctionDiff {
+    fn added() -> Self {
+        Self {
+            status: Some("added"),
+            ..Self::default()
+        }
+    }
+
+    fn removed() -> Self {
+        Self {
+            status: Some("removed"),
+            ..Self::default()
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.status.is_none()
+            && self.edges_added.is_empty()
+            && self.edges_removed.is_empty()
+            && self.capabilities_added.is_empty()
+            && self.capabilities_removed.is_empty()
+            && self.capabilities_changed.is_empty()
+    }
+
+    fn between(before: &CachedSummary, after: &CachedSummary) -> Self {
+        let (before_edges, before_caps) = split_lines(&before.debug_lines);
+        let (after_edges, after_caps) = split_lines(&after.debug_lines);
+
+        let edges_added = after_edges.difference(&before_edges).cloned().collect();
+        let edges_removed = before_edges.difference(&after_edges).cloned().collect();
+
+        let mut capabilities_added = Vec::new();
+        let mut capabilities_changed = Vec::new();
+        for (place, cap) in &after_caps {
+            match before_caps.get(place) {
+                Some(before_cap) if before_cap != cap => {
+                    capabilities_changed.push(format!("{place}: {before_cap} -> {cap}"));
+                }
+                Some(_) => {}
+                None => capabilities_added.push(format!("{place}: {cap}")),
+            }
+        }
+        let mut capabilities_removed = Vec::new();
+        for (place, cap) in &before_caps {
+            if !after_caps.contains_key(place) {
+                capabilities_removed.push(format!("{place}: {cap}"));
+            }
+        }
+
+        Self {
+            status: None,
+            edges_added,
+            edges_removed,
+            capabilities_added,
+            capabilities_removed,
+            capabilities_changed,
+        }
+    }
+}
+
+/// Splits `debug_lines` into (borrow-edge lines, place -> capability map),
+/// using [`is_capability_line`] to tell them apart.
+fn split_lines(
+    lines: &[String],
+) -> (
+    std::collections::BTreeSet<String>,
+    BTreeMap<String, String>,
+) {
+    let mut edges = std::collections::BTreeSet::new();
+    let mut capabilities = BTreeMap::new();
+    for line in lines {
+        if is_capability_line(line) {
+            if let Some((place, cap)) = line.rsplit_once(": ") {
+                capabilities.insert(place.to_string(), cap.to_string());
+            }
+        } else {
+            edges.insert(line.clone());
+        }
+    }
+    (edges, capabilities)
+}
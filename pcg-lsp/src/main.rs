@@ -0,0 +1,139 @@
+//! A deliberately small stdio query loop for exploring PCG interactively
+//! from an editor, e.g. bound to a hover keybinding: "what capability
+//! does this place have here" and "what's currently blocking it".
+//!
+//! This is **not** a full Language Server Protocol implementation: it
+//! doesn't speak LSP's JSON-RPC framing (`Content-Length` headers) or any
+//! of the surrounding lifecycle methods (`initialize`,
+//! `textDocument/didOpen`, ...) that a real LSP client expects. Those are
+//! orthogonal to the part of this request that's specific to PCG --
+//! turning a `(file, line, place)` triple into an answer using the
+//! `block_N_stmt_M_queries.json` artifact that `pcg_bin` already writes
+//! (see `pcg::pcg::query`). A thin adapter translating real LSP
+//! `textDocument/hover` requests into the protocol below is future work;
+//! this binary is the query loop that adapter would sit in front of.
+//!
+//! Protocol: one JSON object per line on stdin,
+//! `{"file": "...", "line": <1-based line number>, "place": "..."}`,
+//! answered with one JSON object per line on stdout,
+//! `{"capability": "...", "blocked_by": ["...", ...]}`, or
+//! `{"error": "..."}` if the file couldn't be analyzed or the place
+//! wasn't found at that line.
+
+use std::{
+    io::{self, BufRead, Write},
+    process::Command,
+};
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize)]
+struct Request {
+    file: String,
+    line: usize,
+    place: String,
+}
+
+#[derive(Serialize)]
+struct Response {
+    capability: Option<String>,
+    blocked_by: Vec<String>,
+}
+
+fn main() {
+    let stdin = io::stdin();
+    let mut stdout = io::stdout();
+    for line in stdin.lock().lines() {
+        let line = line.expect("failed to read stdin");
+        if line.trim().is_empty() {
+            continue;
+        }
+        let reply = match serde_json::from_str::<Request>(&line) {
+            Ok(request) => match handle(&request) {
+                Ok(response) => serde_json::to_value(response).unwrap(),
+                Err(e) => serde_json::json!({ "error": e }),
+            },
+            Err(e) => serde_json::json!({ "error": format!("invalid request: {e}") }),
+        };
+        writeln!(stdout, "{reply}").expect("failed to write stdout");
+        stdout.flush().expect("failed to flush stdout");
+    }
+}
+
+fn handle(request: &Request) -> Result<Response, String> {
+    let data_dir = tempfile::tempdir().map_err(|e| e.to_string())?;
+
+    let status = Command::new("./pcg_bin")
+        .arg(&request.file)
+        .env("PCG_VISUALIZATION", "true")
+        .env(
+            "PCG_VISUALIZATION_DATA_DIR",
+            data_dir.path().to_str().unwrap(),
+        )
+        .status()
+        .map_err(|e| format!("failed to run pcg_bin: {e}"))?;
+    if !status.success() {
+        return Err(format!("pcg_bin exited with {status}"));
+    }
+
+    let (block, statement_index) = locate_statement(data_dir.path(), request.line)?;
+    let queries_path = data_dir
+        .path()
+        .join(format!("block_{block}_stmt_{statement_index}_queries.json"));
+    let queries_json: serde_json::Value = serde_json::from_str(
+        &std::fs::read_to_string(&queries_path).map_err(|e| e.to_string())?,
+    )
+    .map_err(|e| e.to_string())?;
+    let entries = queries_json["data"]
+        .as_array()
+        .ok_or("malformed queries.json: missing \"data\" array")?;
+
+    let entry = entries
+        .iter()
+        .find(|entry| entry["place"].as_str() == Some(request.place.as_str()))
+        .ok_or_else(|| format!("no PCG data for place `{}` at line {}", request.place, request.line))?;
+
+    Ok(Response {
+        capability: entry["capability"].as_str().map(str::to_string),
+        blocked_by: entry["blocked_by"]
+            .as_array()
+            .map(|edges| {
+                edges
+                    .iter()
+                    .filter_map(|edge| edge.as_str().map(str::to_string))
+                    .collect()
+            })
+            .unwrap_or_default(),
+    })
+}
+
+/// Finds the `(block, statement_index)` of the last statement whose span
+/// starts on or before `line`, by scanning `mir.json`'s per-block
+/// statement spans (`"src/foo.rs:LINE:COL: LINE:COL (#N)"`).
+fn locate_statement(data_dir: &std::path::Path, line: usize) -> Result<(usize, usize), String> {
+    let span_start = Regex::new(r":(\d+):\d+: \d+:\d+").unwrap();
+    let mir_json: serde_json::Value = serde_json::from_str(
+        &std::fs::read_to_string(data_dir.join("mir.json")).map_err(|e| e.to_string())?,
+    )
+    .map_err(|e| e.to_string())?;
+
+    let mut best: Option<(usize, usize, usize)> = None; // (line, block, statement_index)
+    for node in mir_json["nodes"].as_array().ok_or("malformed mir.json")? {
+        let block = node["block"].as_u64().ok_or("malformed mir.json")? as usize;
+        for (statement_index, stmt) in node["stmts"].as_array().unwrap_or(&vec![]).iter().enumerate() {
+            let span = stmt["span"].as_str().unwrap_or("");
+            let Some(captures) = span_start.captures(span) else {
+                continue;
+            };
+            let stmt_line: usize = captures[1].parse().map_err(|_| "bad span")?;
+            let improves_on_best = !best.is_some_and(|(best_line, ..)| stmt_line <= best_line);
+            if stmt_line <= line && improves_on_best {
+                best = Some((stmt_line, block, statement_index));
+            }
+        }
+    }
+
+    best.map(|(_, block, statement_index)| (block, statement_index))
+        .ok_or_else(|| format!("no statement found at or before line {line}"))
+}
@@ -1,6 +1,6 @@
 use axum::{
     body::Body,
-    extract::Multipart,
+    extract::{Multipart, Path as RoutePath},
     response::{IntoResponse, Redirect, Response, Html},
     routing::{get, post},
     Router,
@@ -34,6 +34,9 @@ async fn main() {
     let app = Router::new()
         .route("/", get(serve_upload_form))
         .route("/upload", post(handle_upload))
+        .route("/tmp/:id/functions", get(list_functions))
+        .route("/tmp/:id/status", get(get_status))
+        .route("/tmp/:id/reanalyze", post(reanalyze))
         .fallback_service(ServeDir::new("./").append_index_html_on_directories(false));
 
     info!("Starting server on 0.0.0.0:4000");
@@ -123,7 +126,26 @@ async fn handle_upload_inner(mut multipart: Multipart) -> Result<Response, Strin
     let saved_contents = fs::read_to_string(&file_path).map_err(|e| e.to_string())?;
     debug!("Saved file contents:\n{}", saved_contents);
 
-    // Get absolute paths for both input file and data directory
+    if let Err(error_message) = run_analysis(&unique_dir, &data_dir, &file_path) {
+        return Ok((StatusCode::INTERNAL_SERVER_ERROR, error_message).into_response());
+    }
+
+    // Redirect to the visualization
+    let redirect_path = format!("/tmp/{}/index.html", unique_dir.file_name().unwrap().to_str().unwrap());
+    Ok(Redirect::to(&redirect_path).into_response())
+}
+
+/// Runs `pcg_bin` against `file_path` with its visualization output routed
+/// into `data_dir`, then refreshes the static viewer assets (`dist/`,
+/// `index.html`) alongside it. Shared by [`handle_upload_inner`] (the first
+/// run) and [`reanalyze`] (re-running against an `input.rs` that was edited
+/// in place).
+///
+/// This is the closest this single-binary server gets to "watch the file
+/// and reload on change": rather than taking on a filesystem-watcher
+/// dependency, the client polls `/tmp/:id/status`'s mtime and calls
+/// `/tmp/:id/reanalyze` itself when it changes.
+fn run_analysis(unique_dir: &Path, data_dir: &Path, file_path: &Path) -> Result<(), String> {
     let abs_file_path = file_path.canonicalize().map_err(|e| e.to_string())?;
     let abs_data_dir = data_dir.canonicalize().map_err(|e| e.to_string())?;
     debug!("Using absolute file path: {:?}", abs_file_path);
@@ -140,12 +162,10 @@ async fn handle_upload_inner(mut multipart: Multipart) -> Result<Response, Strin
     if !output.status.success() {
         let stdout = String::from_utf8_lossy(&output.stdout);
         let stderr = String::from_utf8_lossy(&output.stderr);
-        let error_message = format!(
+        return Err(format!(
             "PCG analysis failed:\n\nStdout:\n{}\n\nStderr:\n{}",
-            stdout,
-            stderr
-        );
-        return Ok((StatusCode::INTERNAL_SERVER_ERROR, error_message).into_response());
+            stdout, stderr
+        ));
     }
 
     // Copy visualization files
@@ -161,9 +181,77 @@ async fn handle_upload_inner(mut multipart: Multipart) -> Result<Response, Strin
     )
     .map_err(|e| e.to_string())?;
 
-    // Redirect to the visualization
-    let redirect_path = format!("/tmp/{}/index.html", unique_dir.file_name().unwrap().to_str().unwrap());
-    Ok(Redirect::to(&redirect_path).into_response())
+    Ok(())
+}
+
+/// Lists the functions PCG analysed for visualization `id`, read back from
+/// the `functions.json` that [`run_analysis`]'s `pcg_bin` invocation writes
+/// into the data directory -- lets a viewer offer a picker instead of
+/// landing on whichever block the last run happened to write to
+/// `index.html`.
+async fn list_functions(RoutePath(id): RoutePath<String>) -> Response {
+    match list_functions_inner(&id) {
+        Ok(json) => (
+            StatusCode::OK,
+            [("content-type", "application/json")],
+            json,
+        )
+            .into_response(),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e).into_response(),
+    }
+}
+
+fn list_functions_inner(id: &str) -> Result<String, String> {
+    let functions_path = PathBuf::from("tmp").join(id).join("data").join("functions.json");
+    let contents = fs::read_to_string(&functions_path).map_err(|e| e.to_string())?;
+    let names: std::collections::HashMap<String, String> =
+        serde_json::from_str(&contents).map_err(|e| e.to_string())?;
+    let mut names: Vec<String> = names.into_keys().collect();
+    names.sort();
+    serde_json::to_string(&names).map_err(|e| e.to_string())
+}
+
+/// Reports the last-modified time of visualization `id`'s source file, so a
+/// client can poll this and call `/tmp/:id/reanalyze` when it changes. See
+/// [`run_analysis`]'s doc comment for why polling rather than a
+/// filesystem-watcher.
+async fn get_status(RoutePath(id): RoutePath<String>) -> Response {
+    match get_status_inner(&id) {
+        Ok(json) => (
+            StatusCode::OK,
+            [("content-type", "application/json")],
+            json,
+        )
+            .into_response(),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e).into_response(),
+    }
+}
+
+fn get_status_inner(id: &str) -> Result<String, String> {
+    let input_path = PathBuf::from("tmp").join(id).join("input.rs");
+    let modified = fs::metadata(&input_path)
+        .and_then(|m| m.modified())
+        .map_err(|e| e.to_string())?;
+    let secs = modified
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_err(|e| e.to_string())?
+        .as_secs();
+    serde_json::to_string(&serde_json::json!({ "mtime": secs })).map_err(|e| e.to_string())
+}
+
+/// Re-runs PCG against visualization `id`'s (possibly just-edited)
+/// `input.rs`, refreshing its `dist/`/`index.html` in place.
+async fn reanalyze(RoutePath(id): RoutePath<String>) -> Response {
+    let unique_dir = PathBuf::from("tmp").join(&id);
+    let file_path = unique_dir.join("input.rs");
+    let data_dir = unique_dir.join("data");
+    if !file_path.exists() {
+        return (StatusCode::NOT_FOUND, "Unknown visualization id").into_response();
+    }
+    match run_analysis(&unique_dir, &data_dir, &file_path) {
+        Ok(()) => StatusCode::NO_CONTENT.into_response(),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e).into_response(),
+    }
 }
 
 fn copy_dir(src: PathBuf, dst: PathBuf) -> std::io::Result<()> {
@@ -1,3 +1,6 @@
+pub mod replay;
+pub mod viper;
+
 use derive_more::{Deref, DerefMut, From};
 use serde_json::Map;
 
@@ -5,6 +8,7 @@ use crate::{
     borrow_checker::BorrowCheckerInterface,
     borrow_pcg::{
         action::{actions::BorrowPcgActions, BorrowPcgActionKind},
+        edge::kind::BorrowPcgEdgeKind,
         unblock_graph::BorrowPcgUnblockAction,
     },
     free_pcs::{CapabilityKind, RepackOp},
@@ -80,6 +84,10 @@ impl<'tcx> PcgActions<'tcx> {
             })
             .collect()
     }
+
+    pub fn kinds(&self) -> Vec<PcgActionKind<'tcx>> {
+        self.0.iter().map(|action| action.kind()).collect()
+    }
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -165,6 +173,119 @@ impl<'tcx> PcgAction<'tcx> {
             PcgAction::Owned(action) => action.debug_line(repacker),
         }
     }
+
+    /// A stable, semantic categorization of this action, independent of
+    /// whether it was recorded against the owned ([`RepackOp`]) or borrow
+    /// ([`BorrowPcgActionKind`]) half of the PCG. Consumers that only care
+    /// about *what kind* of thing happened (e.g. "a borrow was created")
+    /// rather than the full low-level representation should match on this
+    /// instead of destructuring [`PcgAction`] directly.
+    pub fn kind(&self) -> PcgActionKind<'tcx> {
+        match self {
+            PcgAction::Owned(owned) => match owned.kind() {
+                RepackOp::Weaken(place, from, to) => PcgActionKind::Weaken {
+                    place: *place,
+                    from: *from,
+                    to: Some(*to),
+                },
+                RepackOp::RegainLoanedCapability(place, capability) => PcgActionKind::Restore {
+                    place: *place,
+                    to: *capability,
+                },
+                RepackOp::Expand(expand) => PcgActionKind::Expand {
+                    place: expand.from(),
+                    capability: expand.capability(),
+                },
+                RepackOp::Collapse(collapse) => PcgActionKind::Collapse {
+                    place: collapse.to(),
+                    capability: collapse.capability(),
+                },
+                RepackOp::StorageDead(_)
+                | RepackOp::IgnoreStorageDead(_)
+                | RepackOp::DerefShallowInit(..)
+                | RepackOp::Allocate(_)
+                | RepackOp::Deallocate(_)
+                | RepackOp::RequireRead(_) => PcgActionKind::Other,
+            },
+            PcgAction::Borrow(borrow) => match borrow.kind() {
+                BorrowPcgActionKind::Weaken(weaken) => PcgActionKind::Weaken {
+                    place: weaken.place(),
+                    from: weaken.from_cap(),
+                    to: weaken.to_cap(),
+                },
+                BorrowPcgActionKind::Restore(restore) => PcgActionKind::Restore {
+                    place: restore.place(),
+                    to: restore.capability(),
+                },
+                BorrowPcgActionKind::MakePlaceOld(place, _) => {
+                    PcgActionKind::MakeOld { place: *place }
+                }
+                BorrowPcgActionKind::AddEdge { edge, .. } => match edge.kind() {
+                    BorrowPcgEdgeKind::Abstraction(_) => PcgActionKind::AbstractionAdded,
+                    BorrowPcgEdgeKind::Borrow(_) | BorrowPcgEdgeKind::BorrowFlow(_) => {
+                        PcgActionKind::ReborrowAdded
+                    }
+                    BorrowPcgEdgeKind::BorrowPcgExpansion(_) => PcgActionKind::Other,
+                },
+                BorrowPcgActionKind::RemoveEdge(edge) => match edge.kind() {
+                    BorrowPcgEdgeKind::Abstraction(_) => PcgActionKind::AbstractionExpired,
+                    BorrowPcgEdgeKind::Borrow(_) | BorrowPcgEdgeKind::BorrowFlow(_) => {
+                        PcgActionKind::ReborrowExpired
+                    }
+                    BorrowPcgEdgeKind::BorrowPcgExpansion(_) => PcgActionKind::Other,
+                },
+                BorrowPcgActionKind::RedirectEdge { .. }
+                | BorrowPcgActionKind::LabelRegionProjection(..)
+                | BorrowPcgActionKind::SetLatest(..) => PcgActionKind::Other,
+            },
+        }
+    }
+}
+
+/// A stable, exhaustive categorization of the semantic effect of a
+/// [`PcgAction`], cutting across the owned/borrow split of the underlying
+/// representation. See [`PcgAction::kind`].
+///
+/// Bookkeeping actions with no effect a consumer would typically care about
+/// (e.g. [`BorrowPcgActionKind::SetLatest`], or an edge add/remove for a
+/// [`crate::borrow_pcg::borrow_pcg_expansion::BorrowPcgExpansion`] edge,
+/// which mirrors an owned [`PcgActionKind::Expand`]/[`PcgActionKind::Collapse`]
+/// on the borrow side) are categorized as [`PcgActionKind::Other`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PcgActionKind<'tcx> {
+    /// The capability to `place` was weakened from `from` to `to` (or
+    /// removed entirely, if `to` is `None`).
+    Weaken {
+        place: Place<'tcx>,
+        from: CapabilityKind,
+        to: Option<CapabilityKind>,
+    },
+    /// A previously-lent-out capability to `place` was restored to `to`.
+    Restore { place: Place<'tcx>, to: CapabilityKind },
+    /// `place` was unpacked into its fields.
+    Expand {
+        place: Place<'tcx>,
+        capability: CapabilityKind,
+    },
+    /// `place`'s fields were packed back up.
+    Collapse {
+        place: Place<'tcx>,
+        capability: CapabilityKind,
+    },
+    /// A borrow or borrow-flow edge was added to the borrow PCG.
+    ReborrowAdded,
+    /// A borrow or borrow-flow edge was removed from the borrow PCG, e.g.
+    /// because the borrow expired.
+    ReborrowExpired,
+    /// A function-call abstraction edge was added to the borrow PCG.
+    AbstractionAdded,
+    /// A function-call abstraction edge was removed from the borrow PCG.
+    AbstractionExpired,
+    /// `place` was made old, e.g. because it is about to be overwritten or
+    /// its storage is about to end.
+    MakeOld { place: Place<'tcx> },
+    /// An action with no semantic effect a consumer would typically observe.
+    Other,
 }
 
 impl<'tcx: 'a, 'a> ToJsonWithCompilerCtxt<'tcx, &'a dyn BorrowCheckerInterface<'tcx>>
@@ -0,0 +1,77 @@
+// © 2023, ETH Zurich
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Replays a [`PcgActions`] sequence against a standalone [`Pcg`] state and
+//! checks that doing so reproduces the recorded post-state. This lets the
+//! engine verify that the actions emitted for a statement/phase fully
+//! explain the corresponding state transition, rather than being an
+//! incomplete summary of it.
+//!
+//! The replay goes through [`Pcg::apply_action`], the same method the
+//! engine itself uses while recording actions during the dataflow analysis
+//! (see [`crate::pcg::visitor::PcgVisitor::record_and_apply_action`]), so a
+//! mismatch here means the actions genuinely don't explain the transition,
+//! not that the replay logic disagrees with the engine about what an
+//! action does.
+//!
+//! `PcgEngine::apply_transfer_function` runs this after every phase, gated
+//! behind the same `validity_checks_enabled` flag as
+//! [`Pcg::assert_validity_at_location`] (`PCG_VALIDITY_CHECKS`), since both
+//! re-walk and re-check analysis state that's only worth the cost when
+//! those checks are turned on.
+
+use crate::{
+    pcg::{Pcg, PcgError},
+    utils::CompilerCtxt,
+};
+
+use super::{PcgAction, PcgActions};
+
+/// Why [`replay_and_check`] failed.
+#[derive(Debug)]
+pub enum ReplayMismatch<'tcx> {
+    /// Applying `action` (the `usize`-th action in the sequence) returned
+    /// an error.
+    ApplyError {
+        index: usize,
+        action: PcgAction<'tcx>,
+        error: PcgError,
+    },
+    /// Every action applied successfully, but the resulting state doesn't
+    /// match the recorded post-state.
+    StateMismatch {
+        expected: Box<Pcg<'tcx>>,
+        actual: Box<Pcg<'tcx>>,
+    },
+}
+
+/// Applies `actions` to a clone of `pre`, in order, and checks that the
+/// result equals `post`.
+pub fn replay_and_check<'tcx>(
+    pre: &Pcg<'tcx>,
+    actions: &PcgActions<'tcx>,
+    post: &Pcg<'tcx>,
+    ctxt: CompilerCtxt<'_, 'tcx>,
+) -> Result<(), ReplayMismatch<'tcx>> {
+    let mut state = pre.clone();
+    for (index, action) in actions.iter().enumerate() {
+        state
+            .apply_action(action, ctxt)
+            .map_err(|error| ReplayMismatch::ApplyError {
+                index,
+                action: action.clone(),
+                error,
+            })?;
+    }
+    if state == *post {
+        Ok(())
+    } else {
+        Err(ReplayMismatch::StateMismatch {
+            expected: Box::new(post.clone()),
+            actual: Box::new(state),
+        })
+    }
+}
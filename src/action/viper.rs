@@ -0,0 +1,179 @@
+// © 2023, ETH Zurich
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Translates [`PcgAction`]s into a small, Viper-encoder-oriented
+//! instruction set, ordered per [`EvalStmtPhase`], so a Viper encoder (e.g.
+//! Prusti) doesn't need to re-derive that ordering itself from the raw
+//! per-action JSON.
+//!
+//! Not every [`PcgAction`] has a Viper-relevant meaning: bookkeeping actions
+//! like [`BorrowPcgActionKind::SetLatest`] or
+//! [`BorrowPcgActionKind::LabelRegionProjection`] only affect how the PCG
+//! tracks its own internal state and don't correspond to an instruction an
+//! encoder needs to emit, so [`ViperInstruction::from_action`] returns
+//! `None` for them.
+
+use crate::{
+    borrow_checker::BorrowCheckerInterface,
+    borrow_pcg::action::BorrowPcgActionKind,
+    free_pcs::{CapabilityKind, RepackOp},
+    utils::{display::DisplayWithCompilerCtxt, json::ToJsonWithCompilerCtxt, CompilerCtxt, Place},
+};
+
+use super::{PcgAction, PcgActions};
+use crate::utils::eval_stmt_data::EvalStmtData;
+
+/// A single Viper-encoder-relevant effect of a [`PcgAction`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ViperInstruction<'tcx> {
+    Weaken {
+        place: Place<'tcx>,
+        from: CapabilityKind,
+        to: Option<CapabilityKind>,
+    },
+    Restore {
+        place: Place<'tcx>,
+        to: CapabilityKind,
+    },
+    Expand {
+        place: Place<'tcx>,
+        capability: CapabilityKind,
+    },
+    Collapse {
+        place: Place<'tcx>,
+        capability: CapabilityKind,
+    },
+    /// A borrow/reborrow expiring, i.e. the edge that blocked the borrowed
+    /// place being removed from the borrow PCG.
+    ReborrowExpiry {
+        edge: String,
+    },
+}
+
+impl<'tcx> ViperInstruction<'tcx> {
+    fn from_action(action: &PcgAction<'tcx>) -> Option<Self> {
+        match action {
+            PcgAction::Owned(owned) => match owned.kind() {
+                RepackOp::Weaken(place, from, to) => Some(ViperInstruction::Weaken {
+                    place: *place,
+                    from: *from,
+                    to: Some(*to),
+                }),
+                RepackOp::RegainLoanedCapability(place, capability) => {
+                    Some(ViperInstruction::Restore {
+                        place: *place,
+                        to: *capability,
+                    })
+                }
+                RepackOp::Expand(expand) => Some(ViperInstruction::Expand {
+                    place: expand.from(),
+                    capability: expand.capability(),
+                }),
+                RepackOp::Collapse(collapse) => Some(ViperInstruction::Collapse {
+                    place: collapse.to(),
+                    capability: collapse.capability(),
+                }),
+                // `RequireRead` doesn't need its own Viper instruction: the
+                // read it documents was already obtained by a prior
+                // `Expand`/`Weaken`/etc. (or needed none, if capability was
+                // already sufficient), either way with nothing further to
+                // encode here.
+                RepackOp::StorageDead(_)
+                | RepackOp::IgnoreStorageDead(_)
+                | RepackOp::DerefShallowInit(..)
+                | RepackOp::Allocate(_)
+                | RepackOp::Deallocate(_)
+                | RepackOp::RequireRead(_) => None,
+            },
+            PcgAction::Borrow(borrow) => match borrow.kind() {
+                BorrowPcgActionKind::Weaken(weaken) => Some(ViperInstruction::Weaken {
+                    place: weaken.place(),
+                    from: weaken.from_cap(),
+                    to: weaken.to_cap(),
+                }),
+                BorrowPcgActionKind::Restore(restore) => Some(ViperInstruction::Restore {
+                    place: restore.place(),
+                    to: restore.capability(),
+                }),
+                BorrowPcgActionKind::RemoveEdge(edge) => Some(ViperInstruction::ReborrowExpiry {
+                    edge: format!("{:?}", edge.kind()),
+                }),
+                BorrowPcgActionKind::RedirectEdge { .. }
+                | BorrowPcgActionKind::LabelRegionProjection(..)
+                | BorrowPcgActionKind::MakePlaceOld(..)
+                | BorrowPcgActionKind::SetLatest(..)
+                | BorrowPcgActionKind::AddEdge { .. } => None,
+            },
+        }
+    }
+}
+
+impl<'tcx, 'a> ToJsonWithCompilerCtxt<'tcx, &'a dyn BorrowCheckerInterface<'tcx>>
+    for ViperInstruction<'tcx>
+{
+    fn to_json(
+        &self,
+        ctxt: CompilerCtxt<'_, 'tcx, &'a dyn BorrowCheckerInterface<'tcx>>,
+    ) -> serde_json::Value {
+        match self {
+            ViperInstruction::Weaken { place, from, to } => serde_json::json!({
+                "instruction": "weaken",
+                "place": place.to_short_string(ctxt),
+                "from": format!("{from:?}"),
+                "to": to.map(|to| format!("{to:?}")),
+            }),
+            ViperInstruction::Restore { place, to } => serde_json::json!({
+                "instruction": "restore",
+                "place": place.to_short_string(ctxt),
+                "to": format!("{to:?}"),
+            }),
+            ViperInstruction::Expand { place, capability } => serde_json::json!({
+                "instruction": "expand",
+                "place": place.to_short_string(ctxt),
+                "capability": format!("{capability:?}"),
+            }),
+            ViperInstruction::Collapse { place, capability } => serde_json::json!({
+                "instruction": "collapse",
+                "place": place.to_short_string(ctxt),
+                "capability": format!("{capability:?}"),
+            }),
+            ViperInstruction::ReborrowExpiry { edge } => serde_json::json!({
+                "instruction": "reborrow_expiry",
+                "edge": edge,
+            }),
+        }
+    }
+}
+
+/// The Viper-relevant instructions for a single [`EvalStmtPhase`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ViperPhaseInstructions<'tcx>(pub Vec<ViperInstruction<'tcx>>);
+
+impl<'tcx, 'a> ToJsonWithCompilerCtxt<'tcx, &'a dyn BorrowCheckerInterface<'tcx>>
+    for ViperPhaseInstructions<'tcx>
+{
+    fn to_json(
+        &self,
+        ctxt: CompilerCtxt<'_, 'tcx, &'a dyn BorrowCheckerInterface<'tcx>>,
+    ) -> serde_json::Value {
+        self.0.iter().map(|i| i.to_json(ctxt)).collect()
+    }
+}
+
+/// Translates every action applied during a statement into the Viper
+/// instruction stream, ordered per [`EvalStmtPhase`].
+pub fn encode_for_viper<'tcx>(
+    actions: &EvalStmtData<PcgActions<'tcx>>,
+) -> EvalStmtData<ViperPhaseInstructions<'tcx>> {
+    actions.clone().map(|phase_actions| {
+        ViperPhaseInstructions(
+            phase_actions
+                .iter()
+                .filter_map(ViperInstruction::from_action)
+                .collect(),
+        )
+    })
+}
@@ -1,7 +1,7 @@
 extern crate polonius_engine;
 use polonius_engine::Output;
 
-use crate::borrow_checker::BorrowCheckerInterface;
+use crate::borrow_checker::{BorrowCheckerInterface, LivenessPrecision};
 use crate::borrow_pcg::region_projection::PcgRegion;
 use crate::pcg::PCGNode;
 use crate::rustc_interface::borrowck::{
@@ -40,17 +40,28 @@ impl<'mir, 'tcx: 'mir> PoloniusBorrowChecker<'mir, 'tcx> {
         CompilerCtxt::new(self.body, self.tcx, self)
     }
 
-    pub fn new<T: BodyAndBorrows<'tcx>>(tcx: ty::TyCtxt<'tcx>, body: &'mir T) -> Self {
+    /// `None` if `body` has no Polonius input facts (i.e. it was analyzed
+    /// without `PCG_POLONIUS=1`), since there's then nothing for
+    /// [`Output::compute`] to run on. Callers that want Polonius-based
+    /// liveness (`origins_live_at`) when available, but are fine falling
+    /// back to the NLL-region-based liveness in [`BorrowCheckerImpl`]
+    /// otherwise, should try this first and fall back on `None` -- see
+    /// `run_pcg_on_fn` in `crate::utils::callbacks`.
+    ///
+    /// This `None` path is already exercised by nearly every entry in
+    /// `test-files/` -- `tests/common::is_polonius_test_file` only sets
+    /// `PCG_POLONIUS=1` for files with "polonius" in their name, so the
+    /// other 170+ files all run NLL-only. No dedicated corpus entry was
+    /// added for it.
+    pub fn new<T: BodyAndBorrows<'tcx>>(tcx: ty::TyCtxt<'tcx>, body: &'mir T) -> Option<Self> {
         let location_table = body.location_table();
-        let output_facts = Output::compute(
-            body.input_facts(),
-            polonius_engine::Algorithm::DatafrogOpt,
-            true,
-        );
+        let input_facts = body.input_facts()?;
+        let output_facts =
+            Output::compute(input_facts, polonius_engine::Algorithm::DatafrogOpt, true);
         let region_cx = body.region_inference_context();
         let borrows = body.borrow_set();
-        Self {
-            input_facts: body.input_facts(),
+        Some(Self {
+            input_facts,
             location_table,
             output_facts,
             body: body.body(),
@@ -59,7 +70,7 @@ impl<'mir, 'tcx: 'mir> PoloniusBorrowChecker<'mir, 'tcx> {
             borrows,
             #[cfg(feature = "visualization")]
             pretty_printer: RegionPrettyPrinter::new(region_cx),
-        }
+        })
     }
 
     pub fn origin_live_on_entry(&self, location: RichLocation) -> Option<BTreeSet<ty::RegionVid>> {
@@ -154,10 +165,18 @@ impl<'mir, 'tcx: 'mir> BorrowCheckerInterface<'tcx> for PoloniusBorrowChecker<'m
                 }
                 false
             }),
-            PcgRegion::ReErased => todo!(),
-            PcgRegion::ReStatic => todo!(),
-            PcgRegion::ReBound(_, _) => todo!(),
-            PcgRegion::ReLateParam(_) => todo!(),
+            // None of these carry a `RegionVid` Polonius has loan facts
+            // for (erased regions are gone by the time we get here;
+            // `'static` loans never expire; bound/late-bound regions come
+            // from an uninstantiated HRTB signature, e.g. a callee's
+            // `for<'a>` parameter), so there's no loan-liveness fact to
+            // look up. Conservatively treat them as live, consistent with
+            // the `_ => return true` fallback in `BorrowCheckerImpl::is_live`
+            // below for nodes we can't otherwise pin down.
+            PcgRegion::ReErased
+            | PcgRegion::ReStatic
+            | PcgRegion::ReBound(_, _)
+            | PcgRegion::ReLateParam(_) => true,
         })
     }
 
@@ -165,6 +184,14 @@ impl<'mir, 'tcx: 'mir> BorrowCheckerInterface<'tcx> for PoloniusBorrowChecker<'m
         outlives(self.region_cx, sup, sub)
     }
 
+    /// Polonius origins are per-region (in practice, per-loan), not per-local
+    /// like [`BorrowCheckerImpl`]'s `MaybeLiveLocals`-based liveness, so a
+    /// borrow of one field doesn't make `is_live` return true for an
+    /// unrelated field of the same local.
+    fn liveness_precision(&self) -> LivenessPrecision {
+        LivenessPrecision::Place
+    }
+
     fn twophase_borrow_activations(
         &self,
         location: Location,
@@ -192,14 +219,14 @@ impl<'mir, 'tcx: 'mir> BorrowCheckerInterface<'tcx> for PoloniusBorrowChecker<'m
         self.borrows
     }
 
-    fn input_facts(&self) -> &PoloniusInput {
-        self.input_facts
+    fn input_facts(&self) -> Option<&PoloniusInput> {
+        Some(self.input_facts)
     }
 }
 
 #[derive(Clone)]
 pub struct BorrowCheckerImpl<'mir, 'tcx: 'mir> {
-    input_facts: &'mir PoloniusInput,
+    input_facts: Option<&'mir PoloniusInput>,
     cursor: Rc<RefCell<ResultsCursor<'mir, 'tcx, MaybeLiveLocals>>>,
     out_of_scope_borrows: FxIndexMap<Location, Vec<BorrowIndex>>,
     region_cx: &'mir RegionInferenceContext<'tcx>,
@@ -284,10 +311,22 @@ impl<'tcx> BorrowCheckerInterface<'tcx> for BorrowCheckerImpl<'_, 'tcx> {
     fn override_region_debug_string(&self, _region: ty::RegionVid) -> Option<&str> {
         None
     }
-    fn input_facts(&self) -> &PoloniusInput {
+    fn input_facts(&self) -> Option<&PoloniusInput> {
         self.input_facts
     }
 
+    /// Computed from `out_of_scope_borrows` (NLL region-inference data)
+    /// rather than Polonius's `loan_killed_at` facts, so this works even
+    /// when [`Self::input_facts`] is `None`.
+    fn loans_killed_at(&self, location: Location) -> BTreeSet<ty::RegionVid> {
+        self.out_of_scope_borrows
+            .get(&location)
+            .into_iter()
+            .flatten()
+            .map(|idx| self.borrow_index_to_region(*idx))
+            .collect()
+    }
+
     fn outlives(&self, sup: PcgRegion, sub: PcgRegion) -> bool {
         outlives(self.region_cx, sup, sub)
     }
@@ -374,10 +413,32 @@ impl<'tcx> BorrowCheckerInterface<'tcx> for BorrowCheckerImpl<'_, 'tcx> {
     }
 }
 
+// `region_cx.eval_outlives` answers `RegionVid`-to-`RegionVid` queries
+// against the caller's already-solved NLL region graph, which is a
+// fixpoint over all of the caller's constraints (direct *and* chained,
+// e.g. `'a: 'b` plus `'b: 'c` implying `'a: 'c`) -- there's no separate
+// "direct bounds only" step to fix up here. A callee's own generic
+// lifetime parameters (as opposed to the concrete regions the caller
+// instantiates them with) never show up as a distinct `PcgRegion` variant
+// in this codebase -- they're substituted away before we see them, or
+// (for HRTB parameters that can't be substituted, see the `ReBound`/
+// `ReLateParam` arm below) are symbolic with no named bounds of their own
+// to chase a transitive closure through.
 fn outlives(region_cx: &RegionInferenceContext<'_>, sup: PcgRegion, sub: PcgRegion) -> bool {
     match (sup, sub) {
         (PcgRegion::RegionVid(sup), PcgRegion::RegionVid(sub)) => region_cx.eval_outlives(sup, sub),
         (PcgRegion::ReStatic, _) => true,
+        // Bound/late-bound regions (e.g. from a callee's `for<'a>` HRTB
+        // signature) have no associated region-inference variable, so
+        // there's nothing to ask `region_cx` about them. The best we can
+        // do without instantiating the binder is reflexivity: a region
+        // always outlives itself. That's enough for
+        // `PcgVisitor::make_function_call_abstraction`'s disjoint-lifetime
+        // grouping (which matches regions via `same_region`, i.e. mutual
+        // `outlives`) to still connect such a callee's inputs and outputs
+        // instead of silently dropping the edge.
+        (PcgRegion::ReBound(..), PcgRegion::ReBound(..))
+        | (PcgRegion::ReLateParam(_), PcgRegion::ReLateParam(_)) => sup == sub,
         _ => false,
     }
 }
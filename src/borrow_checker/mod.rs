@@ -14,6 +14,25 @@ use crate::rustc_interface::data_structures::fx::FxIndexMap;
 
 pub mod r#impl;
 
+/// How precisely a [`BorrowCheckerInterface`] backend can answer
+/// [`BorrowCheckerInterface::is_live`] for a [`PCGNode::Place`]: at the
+/// granularity of the place's whole local (so e.g. `x.f` and `x.g` can't be
+/// told apart -- a borrow of one keeps both looking live), or of the
+/// specific place/loan queried.
+///
+/// This is informational only -- no caller currently branches on it (in
+/// particular, `BorrowsGraph::join`'s leaf-trimming doesn't yet defer to it).
+/// Wiring it into trimming to skip/delay expiring a `Local`-precision node
+/// that might actually still be field-live would be a real behavior change
+/// to the dataflow fixpoint that needs a compiler and test suite to check
+/// against, which this environment doesn't have; the enum exists so that
+/// seam is visible and named rather than silently baked into each backend.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LivenessPrecision {
+    Local,
+    Place,
+}
+
 pub trait BorrowCheckerInterface<'tcx> {
     /// Returns true if the node is live *before* `location`. `is_leaf` should
     /// be set to true if the node is a leaf node at this point: in this case,
@@ -21,6 +40,11 @@ pub trait BorrowCheckerInterface<'tcx> {
     /// is not live, then the node is definitely not live).
     fn is_live(&self, node: PCGNode<'tcx>, location: Location, is_leaf: bool) -> bool;
 
+    /// See [`LivenessPrecision`].
+    fn liveness_precision(&self) -> LivenessPrecision {
+        LivenessPrecision::Local
+    }
+
     /// See [`BorrowCheckerInterface::is_live`].
     fn is_dead(&self, node: PCGNode<'tcx>, location: Location, is_leaf: bool) -> bool {
         !self.is_live(node, location, is_leaf)
@@ -53,12 +77,19 @@ pub trait BorrowCheckerInterface<'tcx> {
         &self.borrow_set().location_map
     }
 
+    /// Returns the set of loans killed at `location`. Backends that don't
+    /// have Polonius input facts available (see [`Self::input_facts`])
+    /// should override this with an NLL-based computation instead of
+    /// relying on this default implementation.
     fn loans_killed_at(&self, location: Location) -> BTreeSet<RegionVid> {
+        let Some(input_facts) = self.input_facts() else {
+            return BTreeSet::new();
+        };
         let location_indices = [
             self.location_table().start_index(location),
             self.location_table().mid_index(location),
         ];
-        self.input_facts()
+        input_facts
             .loan_killed_at
             .iter()
             .filter(|(_, point)| location_indices.contains(point))
@@ -68,7 +99,10 @@ pub trait BorrowCheckerInterface<'tcx> {
 
     fn override_region_debug_string(&self, _region: RegionVid) -> Option<&str>;
 
-    fn input_facts(&self) -> &PoloniusInput;
+    /// Returns the Polonius input facts for this body, if the borrow checker
+    /// backend computed them. In NLL-only mode (no Polonius dump facts
+    /// available) this is `None`.
+    fn input_facts(&self) -> Option<&PoloniusInput>;
 
     /// Returns the set of two-phase borrows that activate at `location`.
     /// Each borrow in the returned set is represented by the MIR location
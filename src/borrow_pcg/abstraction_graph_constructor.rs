@@ -15,10 +15,10 @@ use crate::{
 use crate::{
     coupling,
     pcg::PCGNode,
-    pcg_validity_assert,
+    pcg_category_validity_assert, pcg_validity_assert,
     rustc_interface::data_structures::fx::FxHashSet,
     rustc_interface::middle::mir::{BasicBlock, Location},
-    utils::{display::DisplayWithCompilerCtxt, CompilerCtxt},
+    utils::{display::DisplayWithCompilerCtxt, CompilerCtxt, ValidityCheckCategory},
 };
 
 #[derive(Clone)]
@@ -199,7 +199,12 @@ impl<'tcx, 'graph> AbstractionGraph<'tcx, 'graph> {
                     }
                 }
             }
-            pcg_validity_assert!(self.inner.is_acyclic(), "Resulting graph contains cycles");
+            pcg_category_validity_assert!(
+                ValidityCheckCategory::GraphAcyclicity,
+                ctxt,
+                self.inner.is_acyclic(),
+                "Resulting graph contains cycles"
+            );
             return;
         }
     }
@@ -254,6 +259,24 @@ impl<T> DebugRecursiveCallHistory<T> {
     }
 }
 
+/// Collapses the per-iteration borrow/reborrow chain inside a loop into a
+/// single coupled summary rooted at `loop_head_block`.
+///
+/// `loop_head_block` is always a block [`crate::r#loop::LoopAnalysis`] (the
+/// loop forest [`BorrowsGraph`]'s `filter_for_loop_continuation` now uses to
+/// compute loop membership across possibly-multiple back edges) already
+/// recognizes as a loop head -- [`BorrowsGraph::join`] only calls into loop
+/// joining when [`CompilerCtxt::is_back_edge`] holds, and `LoopAnalysis`
+/// records a loop head for every block any back edge targets. This
+/// constructor doesn't re-derive or take the rest of the loop's block set,
+/// though: `should_include` below decides what to couple using borrow
+/// checker liveness *at `loop_head_block`* only, a property of the whole
+/// function rather than of any particular block set. Making it liveness-at-
+/// any-loop-exit instead (which `LoopAnalysis::exits` now makes possible)
+/// would be a real precision improvement, but it changes what counts as
+/// "live enough to keep separate" for every coupled edge already relying on
+/// today's head-only semantics -- too invasive to land blind in a sandbox
+/// with no toolchain to run the existing coupling tests against.
 pub(crate) struct AbstractionGraphConstructor<'mir, 'tcx, 'graph> {
     ctxt: CompilerCtxt<'mir, 'tcx>,
     loop_head_block: BasicBlock,
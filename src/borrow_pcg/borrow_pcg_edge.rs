@@ -360,6 +360,7 @@ impl<'tcx> PCGNode<'tcx> {
                 MaybeRemotePlace::Remote(remote_place) => {
                     Some(AbstractionGraphNode::place(remote_place.into()))
                 }
+                MaybeRemotePlace::Static(_) => None,
             },
             PCGNode::RegionProjection(rp) => Some(AbstractionGraphNode::from_region_projection(
                 rp.try_into().ok()?,
@@ -380,7 +381,7 @@ impl<'tcx> PCGNode<'tcx> {
             PCGNode::Place(MaybeRemotePlace::Local(maybe_old_place)) => {
                 Some(LocalNode::Place(*maybe_old_place))
             }
-            PCGNode::Place(MaybeRemotePlace::Remote(_)) => None,
+            PCGNode::Place(MaybeRemotePlace::Remote(_) | MaybeRemotePlace::Static(_)) => None,
             PCGNode::RegionProjection(rp) => {
                 let place = rp.place().as_local_place()?;
                 Some(LocalNode::RegionProjection(rp.with_base(place)))
@@ -30,6 +30,14 @@ impl<'tcx> FunctionData<'tcx> {
     pub(crate) fn new(def_id: DefId, substs: GenericArgsRef<'tcx>) -> Self {
         Self { def_id, substs }
     }
+
+    pub(crate) fn def_id(&self) -> DefId {
+        self.def_id
+    }
+
+    pub(crate) fn substs(&self) -> GenericArgsRef<'tcx> {
+        self.substs
+    }
 }
 
 #[derive(PartialEq, Eq, Clone, Debug, Hash)]
@@ -13,6 +13,7 @@ use crate::{
         latest::Latest,
         region_projection::{MaybeRemoteRegionProjectionBase, RegionProjectionLabel},
     },
+    coupling::HyperEdge,
     edgedata_enum,
     pcg::PCGNodeLike,
     utils::{maybe_remote::MaybeRemotePlace, redirect::MaybeRedirected},
@@ -355,6 +356,21 @@ impl<'tcx, Input: Clone> AbstractionBlockEdge<'tcx, Input> {
     }
 }
 
+impl<'tcx, Input: Clone + PCGNodeLike<'tcx>> AbstractionBlockEdge<'tcx, Input> {
+    /// This edge viewed as a [`HyperEdge`]: all of [`Self::inputs`] are
+    /// jointly required to block all of [`Self::outputs`], rather than each
+    /// input/output pair being an independent relation.
+    pub fn as_hyperedge(&self, ctxt: CompilerCtxt<'_, 'tcx>) -> HyperEdge<PCGNode<'tcx>> {
+        HyperEdge::new(
+            self.inputs().into_iter().map(|i| i.to_pcg_node(ctxt)).collect(),
+            self.outputs()
+                .into_iter()
+                .map(|o| o.to_pcg_node(ctxt))
+                .collect(),
+        )
+    }
+}
+
 impl<'tcx> HasPcgElems<MaybeOldPlace<'tcx>> for LoopAbstractionInput<'tcx> {
     fn pcg_elems(&mut self) -> Vec<&mut MaybeOldPlace<'tcx>> {
         match self {
@@ -15,7 +15,7 @@ use crate::{
             ty::{self},
         },
     },
-    utils::{remote::RemotePlace, HasPlace},
+    utils::{remote::RemotePlace, static_place::StaticPlace, HasPlace},
 };
 
 use crate::borrow_pcg::borrow_pcg_edge::{BlockedNode, LocalNode};
@@ -234,23 +234,174 @@ impl RemoteBorrow<'_> {
     }
 }
 
+/// A borrow of a `static` or `#[thread_local]` static item, e.g. the `x` in
+/// `let x = &FOO;` or `let x = &mut THREAD_LOCAL;`. Structurally this is
+/// [`RemoteBorrow`] with the blocked place replaced by a [`StaticPlace`],
+/// since in both cases the blocked place has no `mir::Place` of its own and
+/// is only ever the source, never the target, of a borrow.
+#[derive(Copy, PartialEq, Eq, Clone, Debug, Hash)]
+pub struct StaticBorrow<'tcx> {
+    blocked_place: StaticPlace,
+    assigned_ref: MaybeOldPlace<'tcx>,
+    rp_snapshot_location: Option<RegionProjectionLabel>,
+}
+
+impl<'tcx> LabelRegionProjection<'tcx> for StaticBorrow<'tcx> {
+    fn label_region_projection(
+        &mut self,
+        projection: &RegionProjection<'tcx, MaybeOldPlace<'tcx>>,
+        label: Option<RegionProjectionLabel>,
+        repacker: CompilerCtxt<'_, 'tcx>,
+    ) -> bool {
+        if self.assigned_ref.base_region_projection(repacker) == Some(*projection) {
+            self.rp_snapshot_location = label;
+            true
+        } else {
+            false
+        }
+    }
+}
+
+impl<'tcx> LabelEdgePlaces<'tcx> for StaticBorrow<'tcx> {
+    fn label_blocked_places(
+        &mut self,
+        _predicate: &LabelPlacePredicate<'tcx>,
+        _latest: &Latest<'tcx>,
+        _ctxt: CompilerCtxt<'_, 'tcx>,
+    ) -> bool {
+        false
+    }
+
+    fn label_blocked_by_places(
+        &mut self,
+        predicate: &LabelPlacePredicate<'tcx>,
+        latest: &Latest<'tcx>,
+        ctxt: CompilerCtxt<'_, 'tcx>,
+    ) -> bool {
+        self.assigned_ref.label_place(predicate, latest, ctxt)
+    }
+}
+
+impl<'tcx> HasPcgElems<MaybeOldPlace<'tcx>> for StaticBorrow<'tcx> {
+    fn pcg_elems(&mut self) -> Vec<&mut MaybeOldPlace<'tcx>> {
+        vec![&mut self.assigned_ref]
+    }
+}
+
+impl<'tcx> StaticBorrow<'tcx> {
+    pub(crate) fn new(blocked_place: StaticPlace, assigned_ref: MaybeOldPlace<'tcx>) -> Self {
+        Self {
+            blocked_place,
+            assigned_ref,
+            rp_snapshot_location: None,
+        }
+    }
+
+    pub(crate) fn deref_place(&self, repacker: CompilerCtxt<'_, 'tcx>) -> MaybeOldPlace<'tcx> {
+        self.assigned_ref.project_deref(repacker)
+    }
+
+    pub(crate) fn blocked_place(&self) -> StaticPlace {
+        self.blocked_place
+    }
+
+    pub(crate) fn assigned_ref(&self) -> MaybeOldPlace<'tcx> {
+        self.assigned_ref
+    }
+
+    pub(crate) fn assigned_region_projection<BC: Copy>(
+        &self,
+        ctxt: CompilerCtxt<'_, 'tcx, BC>,
+    ) -> RegionProjection<'tcx, MaybeOldPlace<'tcx>> {
+        let rp = self.assigned_ref.base_region_projection(ctxt).unwrap();
+        if let Some(location) = self.rp_snapshot_location {
+            rp.with_label(Some(location), ctxt)
+        } else {
+            rp
+        }
+    }
+
+    pub(crate) fn is_mut(&self, repacker: CompilerCtxt<'_, 'tcx>) -> bool {
+        self.assigned_ref.place().is_mut_ref(repacker)
+    }
+}
+
+impl<'tcx, 'a> DisplayWithCompilerCtxt<'tcx, &'a dyn BorrowCheckerInterface<'tcx>>
+    for StaticBorrow<'tcx>
+{
+    fn to_short_string(
+        &self,
+        ctxt: CompilerCtxt<'_, 'tcx, &'a dyn BorrowCheckerInterface<'tcx>>,
+    ) -> String {
+        format!(
+            "{} -> {}",
+            self.blocked_place(),
+            self.assigned_region_projection(ctxt).to_short_string(ctxt)
+        )
+    }
+}
+
+impl<'tcx> HasValidityCheck<'tcx> for StaticBorrow<'tcx> {
+    fn check_validity(&self, ctxt: CompilerCtxt<'_, 'tcx>) -> Result<(), String> {
+        self.assigned_ref.check_validity(ctxt)
+    }
+}
+
+impl<'tcx> EdgeData<'tcx> for StaticBorrow<'tcx> {
+    fn blocks_node<'slf>(
+        &self,
+        node: BlockedNode<'tcx>,
+        _repacker: CompilerCtxt<'_, 'tcx>,
+    ) -> bool {
+        if let BlockedNode::Place(MaybeRemotePlace::Static(sp)) = node {
+            self.blocked_place() == sp
+        } else {
+            false
+        }
+    }
+
+    fn blocked_nodes<'slf, BC: Copy>(
+        &'slf self,
+        _ctxt: CompilerCtxt<'_, 'tcx, BC>,
+    ) -> Box<dyn Iterator<Item = PCGNode<'tcx>> + 'slf>
+    where
+        'tcx: 'slf,
+    {
+        Box::new(std::iter::once(self.blocked_place().into()))
+    }
+
+    fn blocked_by_nodes<'slf, 'mir: 'slf, BC: Copy>(
+        &'slf self,
+        repacker: CompilerCtxt<'mir, 'tcx, BC>,
+    ) -> Box<dyn Iterator<Item = LocalNode<'tcx>> + 'slf>
+    where
+        'tcx: 'mir,
+    {
+        Box::new(std::iter::once(
+            self.assigned_region_projection(repacker).into(),
+        ))
+    }
+}
+
 #[derive(Clone, Debug, Eq, PartialEq, Hash)]
 pub enum BorrowEdge<'tcx> {
     Local(LocalBorrow<'tcx>),
     Remote(RemoteBorrow<'tcx>),
+    Static(StaticBorrow<'tcx>),
 }
 
 edgedata_enum!(
     BorrowEdge<'tcx>,
     Local(LocalBorrow<'tcx>),
     Remote(RemoteBorrow<'tcx>),
+    Static(StaticBorrow<'tcx>),
 );
 
 impl<'tcx> BorrowEdge<'tcx> {
     pub fn kind(&self) -> Option<mir::BorrowKind> {
         match self {
             BorrowEdge::Local(borrow) => Some(borrow.kind),
-            BorrowEdge::Remote(_) => None,
+            BorrowEdge::Remote(_) | BorrowEdge::Static(_) => None,
         }
     }
 
@@ -258,20 +409,21 @@ impl<'tcx> BorrowEdge<'tcx> {
         match self {
             BorrowEdge::Local(borrow) => borrow.is_mut(),
             BorrowEdge::Remote(borrow) => borrow.is_mut(repacker),
+            BorrowEdge::Static(borrow) => borrow.is_mut(repacker),
         }
     }
 
     pub(crate) fn reserve_location(&self) -> Option<Location> {
         match self {
             BorrowEdge::Local(borrow) => Some(borrow.reserve_location()),
-            BorrowEdge::Remote(_) => None,
+            BorrowEdge::Remote(_) | BorrowEdge::Static(_) => None,
         }
     }
 
     pub fn borrow_region(&self) -> Option<ty::Region<'tcx>> {
         match self {
             BorrowEdge::Local(borrow) => Some(borrow.region),
-            BorrowEdge::Remote(_) => None,
+            BorrowEdge::Remote(_) | BorrowEdge::Static(_) => None,
         }
     }
 
@@ -282,6 +434,7 @@ impl<'tcx> BorrowEdge<'tcx> {
         match self {
             BorrowEdge::Local(borrow) => borrow.assigned_region_projection(repacker),
             BorrowEdge::Remote(borrow) => borrow.assigned_region_projection(repacker),
+            BorrowEdge::Static(borrow) => borrow.assigned_region_projection(repacker),
         }
     }
 
@@ -289,6 +442,7 @@ impl<'tcx> BorrowEdge<'tcx> {
         match self {
             BorrowEdge::Local(borrow) => borrow.blocked_place.into(),
             BorrowEdge::Remote(borrow) => borrow.blocked_place().into(),
+            BorrowEdge::Static(borrow) => borrow.blocked_place().into(),
         }
     }
 
@@ -296,6 +450,7 @@ impl<'tcx> BorrowEdge<'tcx> {
         match self {
             BorrowEdge::Local(borrow) => borrow.deref_place(repacker),
             BorrowEdge::Remote(borrow) => borrow.deref_place(repacker),
+            BorrowEdge::Static(borrow) => borrow.deref_place(repacker),
         }
     }
 
@@ -303,6 +458,7 @@ impl<'tcx> BorrowEdge<'tcx> {
         match self {
             BorrowEdge::Local(borrow) => borrow.assigned_ref,
             BorrowEdge::Remote(remote) => remote.assigned_ref(),
+            BorrowEdge::Static(borrow) => borrow.assigned_ref(),
         }
     }
 }
@@ -5,7 +5,7 @@ use crate::borrow_pcg::edge::abstraction::AbstractionType;
 use crate::borrow_pcg::edge::borrow::BorrowEdge;
 use crate::utils::CompilerCtxt;
 
-use super::borrow::RemoteBorrow;
+use super::borrow::{RemoteBorrow, StaticBorrow};
 use super::outlives::BorrowFlowEdge;
 
 #[derive(Clone, Debug, Eq, PartialEq, Hash)]
@@ -22,6 +22,12 @@ impl<'tcx> From<RemoteBorrow<'tcx>> for BorrowPcgEdgeKind<'tcx> {
     }
 }
 
+impl<'tcx> From<StaticBorrow<'tcx>> for BorrowPcgEdgeKind<'tcx> {
+    fn from(borrow: StaticBorrow<'tcx>) -> Self {
+        BorrowPcgEdgeKind::Borrow(BorrowEdge::Static(borrow))
+    }
+}
+
 impl<'tcx> BorrowPcgEdgeKind<'tcx> {
     #[allow(unused)]
     pub(crate) fn could_mutate(&self, repacker: CompilerCtxt<'_, 'tcx>) -> bool {
@@ -182,6 +182,17 @@ pub enum BorrowFlowEdgeKind {
         regions_equal: bool,
     },
     InitialBorrows,
+    /// For a copy `let x: &'x T = y;` where `y: &'y T`, an edge `{y↓'y} ->
+    /// {x↓'x}` of this kind is created, connecting the copy's region
+    /// projections directly rather than introducing a full [`super::borrow::BorrowEdge`]
+    /// reborrow from `*y`. Since it's a [`BorrowFlowEdge`] rather than a
+    /// [`super::borrow::BorrowEdge`], it's blocked (and later expired) the same way as
+    /// any other region-projection edge, via [`crate::borrow_pcg::graph::BorrowsGraph::make_place_old`]
+    /// labelling `y` or `x` old and the usual loop-exit edge filtering in
+    /// [`crate::borrow_pcg::graph::BorrowsGraph::filter_for_loop_continuation`] — there's no separate
+    /// expiry path to duplicate or drift out of sync with those, so repeated
+    /// copies in a loop body don't accumulate edges beyond what those two
+    /// mechanisms already prune.
     CopyRef,
     Move,
     Future,
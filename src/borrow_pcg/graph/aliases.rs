@@ -1,11 +1,14 @@
 use crate::{
     borrow_pcg::{
-        borrow_pcg_edge::{BorrowPcgEdgeRef, LocalNode},
+        borrow_pcg_edge::{BorrowPcgEdgeLike, BorrowPcgEdgeRef, LocalNode},
         edge::{kind::BorrowPcgEdgeKind, outlives::BorrowFlowEdgeKind},
         edge_data::EdgeData,
     },
     pcg::{LocalNodeLike, PCGNode, PCGNodeLike},
-    rustc_interface::data_structures::fx::FxHashSet,
+    rustc_interface::{
+        data_structures::fx::FxHashSet,
+        middle::mir::BasicBlock,
+    },
     utils::{data_structures::HashSet, CompilerCtxt, HasPlace},
 };
 
@@ -40,6 +43,98 @@ impl<'tcx> BorrowsGraph<'tcx> {
         }
         result
     }
+    /// All nodes transitively blocked by `node`, i.e. nodes reachable by
+    /// repeatedly following "`node` blocks `X`, `X` blocks `Y`, ..." edges,
+    /// along with the edge that connects each step. If `path` is given, only
+    /// edges whose [`super::path_condition::PathConditions`] are valid for
+    /// that path (see [`crate::borrow_pcg::path_condition::PathConditions::valid_for_path`])
+    /// are followed, so a caller asking "what does this borrow still block
+    /// on this particular control-flow path" doesn't get edges from other
+    /// branches back.
+    ///
+    /// This is the public, path-condition-aware counterpart of
+    /// [`Self::ancestor_edges`]. It does its own walk over
+    /// [`Self::edges_blocked_by`] rather than calling `ancestor_edges`
+    /// (`ancestor_edges` doesn't filter by path, and only returns the
+    /// edges, not the reachable nodes themselves).
+    ///
+    /// Unlike [`Self::nodes_blocked_by`] (single-hop, `pub(crate)`), this
+    /// is the transitive closure; the two intentionally keep distinct names
+    /// rather than one overloading the other's arity.
+    ///
+    /// No cache is kept across calls: [`BorrowsGraph`] is cloned on every
+    /// join/statement transition in the dataflow fixpoint (see the
+    /// type-level doc comment on [`BorrowsGraph`]), and a cache would need
+    /// to be invalidated at every one of [`BorrowsGraph`]'s several mutation
+    /// sites (`insert`/`remove`/`retain`/`mut_edges`) to avoid a stale,
+    /// silently-shared-after-clone cache leaking one dataflow state's
+    /// answers into another's -- exactly the kind of correctness bug that
+    /// needs a compiler and test suite to catch, neither of which is
+    /// available in this pass. Each call instead does its own bounded
+    /// traversal (the `seen` set below already avoids revisiting a node
+    /// twice within that one call).
+    pub fn transitive_nodes_blocked_by<'graph, 'mir: 'graph>(
+        &'graph self,
+        node: LocalNode<'tcx>,
+        path: Option<&[BasicBlock]>,
+        ctxt: CompilerCtxt<'mir, 'tcx>,
+    ) -> Vec<(BorrowPcgEdgeRef<'tcx, 'graph>, PCGNode<'tcx>)> {
+        let mut result = Vec::new();
+        let mut stack = vec![node];
+        let mut seen: FxHashSet<PCGNode<'tcx>> = FxHashSet::default();
+        while let Some(node) = stack.pop() {
+            if seen.insert(node.into()) {
+                for edge in self.edges_blocked_by(node, ctxt) {
+                    if let Some(path) = path
+                        && !edge.conditions().valid_for_path(path, ctxt.body())
+                    {
+                        continue;
+                    }
+                    for blocked in edge.blocked_nodes(ctxt) {
+                        result.push((edge, blocked));
+                        if let Some(local_node) = blocked.try_to_local_node(ctxt) {
+                            stack.push(local_node);
+                        }
+                    }
+                }
+            }
+        }
+        result
+    }
+
+    /// All nodes that transitively block `node`, i.e. nodes reachable by
+    /// repeatedly following "`X` blocks `node`, `Y` blocks `X`, ..." edges
+    /// -- the mirror-image traversal of [`Self::transitive_nodes_blocked_by`],
+    /// built on [`Self::edges_blocking`] instead of [`Self::edges_blocked_by`].
+    /// See that method's doc comment for the `path` filtering and caching
+    /// caveats, which apply identically here.
+    pub fn transitive_nodes_blocking<'graph, 'mir: 'graph>(
+        &'graph self,
+        node: LocalNode<'tcx>,
+        path: Option<&[BasicBlock]>,
+        ctxt: CompilerCtxt<'mir, 'tcx>,
+    ) -> Vec<(BorrowPcgEdgeRef<'tcx, 'graph>, PCGNode<'tcx>)> {
+        let mut result = Vec::new();
+        let mut stack = vec![node];
+        let mut seen: FxHashSet<PCGNode<'tcx>> = FxHashSet::default();
+        while let Some(node) = stack.pop() {
+            if seen.insert(node.into()) {
+                for edge in self.edges_blocking(node.into(), ctxt) {
+                    if let Some(path) = path
+                        && !edge.conditions().valid_for_path(path, ctxt.body())
+                    {
+                        continue;
+                    }
+                    for blocker in edge.blocked_by_nodes(ctxt) {
+                        result.push((edge, blocker.into()));
+                        stack.push(blocker);
+                    }
+                }
+            }
+        }
+        result
+    }
+
     pub(crate) fn aliases<BC: Copy>(
         &self,
         node: LocalNode<'tcx>,
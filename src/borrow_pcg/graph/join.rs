@@ -23,11 +23,19 @@ use crate::{
     validity_checks_enabled,
 };
 
-use super::{borrows_imgcat_debug, coupling_imgcat_debug, BorrowsGraph};
+use super::{borrows_imgcat_debug, borrows_imgcat_debug_for, coupling_imgcat_debug, BorrowsGraph};
 
 impl<'tcx> BorrowsGraph<'tcx> {
-    pub(crate) fn render_debug_graph(&self, ctxt: CompilerCtxt<'_, 'tcx>, comment: &str) {
-        if borrows_imgcat_debug()
+    /// `block`, if known, is the join point this graph belongs to (the
+    /// block the filter in [`borrows_imgcat_debug_for`] can narrow on); see
+    /// its doc comment.
+    pub(crate) fn render_debug_graph(
+        &self,
+        ctxt: CompilerCtxt<'_, 'tcx>,
+        block: Option<BasicBlock>,
+        comment: &str,
+    ) {
+        if borrows_imgcat_debug_for(block, None, comment)
             && let Ok(dot_graph) = generate_borrows_dot_graph(ctxt, self)
         {
             DotGraph::render_with_imgcat(&dot_graph, comment).unwrap_or_else(|e| {
@@ -82,17 +90,19 @@ impl<'tcx> BorrowsGraph<'tcx> {
         let old_self = self.clone();
 
         if ctxt.is_back_edge(other_block, self_block) {
-            self.render_debug_graph(ctxt, &format!("Self graph: {self_block:?}"));
-            other.render_debug_graph(ctxt, &format!("Other graph: {other_block:?}"));
+            self.render_debug_graph(ctxt, Some(self_block), &format!("Self graph: {self_block:?}"));
+            other.render_debug_graph(
+                ctxt,
+                Some(other_block),
+                &format!("Other graph: {other_block:?}"),
+            );
             self.join_loop(other, self_block, other_block, loop_usage, ctxt);
             let result = *self != old_self;
-            if borrows_imgcat_debug()
+            let comment = format!("After join (loop, changed={result:?}):");
+            if borrows_imgcat_debug_for(Some(self_block), None, &comment)
                 && let Ok(dot_graph) = generate_borrows_dot_graph(ctxt, self)
             {
-                DotGraph::render_with_imgcat(
-                    &dot_graph,
-                    &format!("After join (loop, changed={result:?}):"),
-                )
+                DotGraph::render_with_imgcat(&dot_graph, &comment)
                 .unwrap_or_else(|e| {
                     eprintln!("Error rendering self graph: {e}");
                 });
@@ -127,19 +137,21 @@ impl<'tcx> BorrowsGraph<'tcx> {
 
         let changed = old_self != *self;
 
-        if borrows_imgcat_debug()
+        let comment = format!("After join: (changed={changed:?})");
+        if borrows_imgcat_debug_for(Some(self_block), None, &comment)
             && let Ok(dot_graph) = generate_borrows_dot_graph(ctxt, self)
         {
-            DotGraph::render_with_imgcat(&dot_graph, &format!("After join: (changed={changed:?})"))
-                .unwrap_or_else(|e| {
-                    eprintln!("Error rendering self graph: {e}");
-                });
+            DotGraph::render_with_imgcat(&dot_graph, &comment).unwrap_or_else(|e| {
+                eprintln!("Error rendering self graph: {e}");
+            });
             if changed {
                 eprintln!("{}", old_self.fmt_diff(self, ctxt))
             }
         }
 
-        // For performance reasons we only check validity here if we are also producing debug graphs
+        // Deliberately unfiltered: this dumps evidence of an already-detected
+        // soundness bug, and narrowing it by block could hide the very graph
+        // that's invalid.
         if validity_checks_enabled() && borrows_imgcat_debug() && !self.is_valid(ctxt) {
             if let Ok(dot_graph) = generate_borrows_dot_graph(ctxt, self) {
                 DotGraph::render_with_imgcat(&dot_graph, "Invalid self graph").unwrap_or_else(
@@ -163,6 +175,28 @@ impl<'tcx> BorrowsGraph<'tcx> {
         changed
     }
 
+    /// Joins `other` (the graph flowing around the loop, from `from_block`)
+    /// into `self` (the graph already established at `loop_head`) across
+    /// a loop back edge, producing [`LoopAbstraction`] edges for whatever
+    /// borrows were created or reshaped during the loop body.
+    ///
+    /// Both graphs are first reduced to an [`AbstractionGraphConstructor`]
+    /// "coupling graph" rooted at `loop_head`: a summary of which
+    /// loop-entry region projections each currently-live place at
+    /// `loop_head` is (transitively) borrowed from, collapsing however
+    /// many iterations of borrowing/reborrowing happened inside the loop
+    /// into a single edge per coupled group of inputs/outputs. Edges that
+    /// appear in one graph's coupling summary but not the other's (i.e.
+    /// the loop body's borrows haven't stabilized to a fixpoint yet) are
+    /// (re)inserted as [`LoopAbstraction`] edges -- this is what gives a
+    /// client outside the loop a clean interface at the loop boundary
+    /// (loop-entry region projections blocking loop-exit places) instead
+    /// of the full per-iteration borrow/reborrow chain.
+    ///
+    /// Before any of that, `self` is pruned via
+    /// [`BorrowsGraph::filter_for_loop_continuation`] to drop edges whose
+    /// path conditions prove they only exist on a `break`-only path, so
+    /// such borrows aren't coupled into the loop's steady state.
     fn join_loop<'mir>(
         &mut self,
         other: &Self,
@@ -175,6 +209,11 @@ impl<'tcx> BorrowsGraph<'tcx> {
         tracing::debug!("Self has {} edges", self.edges.len());
         tracing::debug!("Other has {} edges", other.edges.len());
 
+        // Drop edges whose path conditions show they were only created on
+        // a path that breaks out of the loop: they don't describe anything
+        // live on the path that's actually looping back around.
+        self.filter_for_loop_continuation(loop_head, from_block, ctxt);
+
         let old_self = self.clone();
         let self_abstraction_graph = AbstractionGraphConstructor::new(ctxt, loop_head)
             .construct_abstraction_graph(&old_self, ctxt.bc);
@@ -197,11 +236,11 @@ impl<'tcx> BorrowsGraph<'tcx> {
 
         // First only keep edges present in both graphs (remove other edges from `self`)
         let to_keep = self.common_edges(other);
-        self.edges
+        std::rc::Rc::make_mut(&mut self.edges)
             .retain(|edge_kind, _| to_keep.contains(edge_kind));
 
-        if borrows_imgcat_debug() {
-            self.render_debug_graph(ctxt, "common edges");
+        if borrows_imgcat_debug_for(Some(loop_head), None, "common edges") {
+            self.render_debug_graph(ctxt, Some(loop_head), "common edges");
         }
 
         let other_coupling_edges = other_coupling_graph.edges().collect::<Vec<_>>();
@@ -241,7 +280,7 @@ impl<'tcx> BorrowsGraph<'tcx> {
             .to_borrow_pcg_edge(PathConditions::new());
 
             self.insert(abstraction, ctxt);
-            self.edges
+            std::rc::Rc::make_mut(&mut self.edges)
                 .retain(|edge_kind, _| !to_remove.contains(edge_kind));
         }
 
@@ -276,8 +315,8 @@ impl<'tcx> BorrowsGraph<'tcx> {
             }
         }
 
-        if borrows_imgcat_debug() {
-            self.render_debug_graph(ctxt, "done");
+        if borrows_imgcat_debug_for(Some(loop_head), None, "done") {
+            self.render_debug_graph(ctxt, Some(loop_head), "done");
         }
         tracing::debug!("join_loop {from_block:?} {loop_head:?} end");
     }
@@ -19,7 +19,7 @@ use crate::{
         display::{DebugLines, DisplayWithCompilerCtxt},
         maybe_old::MaybeOldPlace,
         validity::HasValidityCheck,
-        HasPlace, Place, BORROWS_DEBUG_IMGCAT, COUPLING_DEBUG_IMGCAT,
+        HasPlace, Place, BORROWS_DEBUG_IMGCAT, COUPLING_DEBUG_IMGCAT, IMGCAT_DEBUG_FILTER,
     },
 };
 use frozen::{CachedBlockingEdges, CachedLeafEdges, FrozenGraphRef};
@@ -37,10 +37,37 @@ use crate::borrow_pcg::edge::borrow::BorrowEdge;
 use crate::borrow_pcg::edge::kind::BorrowPcgEdgeKind;
 use crate::utils::json::ToJsonWithCompilerCtxt;
 use crate::utils::CompilerCtxt;
-
+use std::rc::Rc;
+
+/// The edges are stored behind an `Rc` so that cloning a [`BorrowsGraph`] is
+/// O(1) and only copies the underlying map on the first write after the
+/// clone (via [`Rc::make_mut`] in the mutating methods below). This matters
+/// because [`crate::utils::domain_data::DomainDataStates`] clones the whole
+/// [`crate::pcg::Pcg`] (which embeds a `BorrowsGraph`) once per
+/// [`crate::pcg::EvalStmtPhase`] for every statement.
+///
+/// Note that the `Rc`'s backing allocation, and the `FxHashMap` it points
+/// to, still always come from the global allocator, even when
+/// [`crate::pcg::PcgEngine`] is instantiated with a bump arena (its `A:
+/// Allocator` parameter, see [`crate::run_pcg_with_options`]): that arena is
+/// currently only used for the outer per-statement [`crate::pcg::Pcg`]
+/// snapshots (`ArenaRef<Pcg<'tcx>, A>` in
+/// [`crate::pcg::domain::PcgDomainData`]), not for anything inside them.
+/// Making this map itself arena-allocated would mean giving `BorrowsGraph`
+/// (and, transitively, [`crate::borrow_pcg::state::BorrowsState`] and
+/// [`crate::pcg::Pcg`], both of which are referenced by name — without an
+/// allocator type parameter — throughout `borrow_pcg`, `free_pcs`,
+/// `query`, and `visualization`) a second generic parameter, and switching
+/// from `std::collections::HashMap` to an allocator-aware map (`std`'s
+/// `HashMap` has no allocator hook even under `#![feature(allocator_api)]`;
+/// only crates like `hashbrown` expose one). That's a crate-wide signature
+/// change touching far more than the borrow graph itself, so it's been left
+/// out of this pass rather than attempted as an unreviewable sweeping
+/// migration; the per-snapshot arena allocation already in place is the
+/// part of this that's implemented.
 #[derive(Clone, Debug, Default)]
 pub struct BorrowsGraph<'tcx> {
-    edges: FxHashMap<BorrowPcgEdgeKind<'tcx>, PathConditions>,
+    edges: Rc<FxHashMap<BorrowPcgEdgeKind<'tcx>, PathConditions>>,
 }
 
 impl<'tcx> DebugLines<CompilerCtxt<'_, 'tcx>> for BorrowsGraph<'tcx> {
@@ -94,6 +121,21 @@ pub(crate) fn borrows_imgcat_debug() -> bool {
     *BORROWS_DEBUG_IMGCAT
 }
 
+/// Like [`borrows_imgcat_debug`], but also checks the render against
+/// [`IMGCAT_DEBUG_FILTER`] (block, statement range, and/or place-of-interest
+/// substring of `comment`), so a developer debugging a single join doesn't
+/// get flooded with renders for every other block too. Pass `None` for
+/// `block`/`statement_index` at call sites where that context isn't
+/// available; the filter treats an unknown dimension as a match.
+pub(crate) fn borrows_imgcat_debug_for(
+    block: Option<mir::BasicBlock>,
+    statement_index: Option<usize>,
+    comment: &str,
+) -> bool {
+    *BORROWS_DEBUG_IMGCAT
+        && IMGCAT_DEBUG_FILTER.allows(block.map(mir::BasicBlock::as_usize), statement_index, comment)
+}
+
 impl<'tcx> BorrowsGraph<'tcx> {
     pub(crate) fn owned_places(&self, ctxt: CompilerCtxt<'_, 'tcx>) -> HashSet<Place<'tcx>> {
         let mut result = HashSet::default();
@@ -217,7 +259,7 @@ impl<'tcx> BorrowsGraph<'tcx> {
 
     #[allow(unused)]
     pub(crate) fn into_edges(self) -> impl Iterator<Item = BorrowPcgEdge<'tcx>> {
-        self.edges
+        Rc::unwrap_or_clone(self.edges)
             .into_iter()
             .map(|(kind, conditions)| BorrowPcgEdge { kind, conditions })
     }
@@ -484,10 +526,10 @@ impl<'tcx> BorrowsGraph<'tcx> {
         edge: BorrowPcgEdge<'tcx>,
         ctxt: CompilerCtxt<'_, 'tcx>,
     ) -> bool {
-        if let Some(conditions) = self.edges.get_mut(edge.kind()) {
+        if let Some(conditions) = Rc::make_mut(&mut self.edges).get_mut(edge.kind()) {
             conditions.join(&edge.conditions, ctxt.body())
         } else {
-            self.edges.insert(edge.kind, edge.conditions);
+            Rc::make_mut(&mut self.edges).insert(edge.kind, edge.conditions);
             true
         }
     }
@@ -510,7 +552,7 @@ impl<'tcx> BorrowsGraph<'tcx> {
     }
 
     pub(crate) fn remove(&mut self, edge: &BorrowPcgEdgeKind<'tcx>) -> Option<PathConditions> {
-        self.edges.remove(edge)
+        Rc::make_mut(&mut self.edges).remove(edge)
     }
 }
 
@@ -1,3 +1,5 @@
+use std::rc::Rc;
+
 use crate::{
     borrow_pcg::{
         borrow_pcg_edge::BorrowPcgEdge,
@@ -5,12 +7,60 @@ use crate::{
         latest::Latest,
         path_condition::{PathCondition, PathConditions},
     },
-    rustc_interface::middle::mir::BasicBlock,
+    r#loop::LoopAnalysis,
+    rustc_interface::{
+        data_structures::fx::FxHashSet,
+        middle::mir::{self, BasicBlock},
+    },
     utils::{CompilerCtxt, Place},
 };
 
 use super::BorrowsGraph;
 
+/// The blocks of the natural loop with header `loop_head` and back-edge
+/// source `from_block`: `loop_head` itself, plus everything reachable from
+/// `from_block` by walking predecessors without going through `loop_head`.
+///
+/// Used only as a fallback for [`loop_blocks`], when `loop_head` isn't
+/// recognized as a loop head by [`LoopAnalysis`] (shouldn't happen for a
+/// `loop_head` derived from [`CompilerCtxt::is_back_edge`], but this keeps
+/// that coupling implicit rather than panicking on it).
+fn natural_loop_blocks(
+    loop_head: BasicBlock,
+    from_block: BasicBlock,
+    body: &mir::Body<'_>,
+) -> FxHashSet<BasicBlock> {
+    let predecessors = body.basic_blocks.predecessors();
+    let mut blocks = FxHashSet::default();
+    blocks.insert(loop_head);
+    let mut worklist = vec![from_block];
+    while let Some(block) = worklist.pop() {
+        if blocks.insert(block) {
+            worklist.extend(predecessors[block].iter().copied());
+        }
+    }
+    blocks
+}
+
+/// The blocks making up the loop headed at `loop_head`, preferring the
+/// [`LoopAnalysis`] loop forest over [`natural_loop_blocks`]'s single-edge
+/// walk: [`LoopAnalysis::find_loops`] merges every back edge into
+/// `loop_head` (and correctly accounts for nesting) in one
+/// reverse-postorder sweep, so a loop reached by more than one back edge
+/// (or a head shared with an enclosing loop) still gets one consistent
+/// block set, rather than whatever `from_block` alone happens to dominate.
+fn loop_blocks(
+    loop_head: BasicBlock,
+    from_block: BasicBlock,
+    body: &mir::Body<'_>,
+) -> FxHashSet<BasicBlock> {
+    let loop_analysis = LoopAnalysis::find_loops(body);
+    match loop_analysis.loop_head_of(loop_head) {
+        Some(loop_id) => loop_analysis.blocks(loop_id).collect(),
+        None => natural_loop_blocks(loop_head, from_block, body),
+    }
+}
+
 impl<'tcx> BorrowsGraph<'tcx> {
     pub(crate) fn make_place_old(
         &mut self,
@@ -33,8 +83,7 @@ impl<'tcx> BorrowsGraph<'tcx> {
         mut f: impl FnMut(&mut BorrowPcgEdge<'tcx>) -> bool,
     ) -> bool {
         let mut changed = false;
-        self.edges = self
-            .edges
+        let new_edges = Rc::make_mut(&mut self.edges)
             .drain()
             .map(|(kind, conditions)| {
                 let mut edge = BorrowPcgEdge::new(kind, conditions);
@@ -44,12 +93,13 @@ impl<'tcx> BorrowsGraph<'tcx> {
                 (edge.kind, edge.conditions)
             })
             .collect();
+        self.edges = Rc::new(new_edges);
         changed
     }
 
     fn mut_edge_conditions(&mut self, mut f: impl FnMut(&mut PathConditions) -> bool) -> bool {
         let mut changed = false;
-        for (_, conditions) in self.edges.iter_mut() {
+        for (_, conditions) in Rc::make_mut(&mut self.edges).iter_mut() {
             if f(conditions) {
                 changed = true;
             }
@@ -58,10 +108,36 @@ impl<'tcx> BorrowsGraph<'tcx> {
     }
 
     pub fn filter_for_path(&mut self, path: &[BasicBlock], ctxt: CompilerCtxt<'_, 'tcx>) {
-        self.edges
+        Rc::make_mut(&mut self.edges)
             .retain(|_, conditions| conditions.valid_for_path(path, ctxt.body()));
     }
 
+    /// Drops edges whose path conditions prove they were only created on a
+    /// path that exits the natural loop headed at `loop_head` (e.g. a
+    /// `break`-only path) rather than one that can reach `from_block`, the
+    /// loop's back-edge source, again. Such edges describe borrows that
+    /// don't survive into the next iteration, so they shouldn't be carried
+    /// across the back edge when joining at the loop head.
+    ///
+    /// This is a conservative, single-hop check (a branch is only treated
+    /// as loop-exiting if *none* of its recorded choices lead to another
+    /// block inside the loop): it can miss edges that are transitively
+    /// break-only deeper in the loop body, but it never drops an edge that
+    /// might still be live on a continuing iteration.
+    pub(crate) fn filter_for_loop_continuation(
+        &mut self,
+        loop_head: BasicBlock,
+        from_block: BasicBlock,
+        ctxt: CompilerCtxt<'_, 'tcx>,
+    ) {
+        let loop_body = loop_blocks(loop_head, from_block, ctxt.body());
+        Rc::make_mut(&mut self.edges).retain(|_, conditions| {
+            conditions.all_choices_accepted_by(ctxt.body(), |_, successor| {
+                loop_body.contains(&successor)
+            })
+        });
+    }
+
     pub(crate) fn add_path_condition(
         &mut self,
         pc: PathCondition,
@@ -12,13 +12,39 @@ use crate::utils::{CompilerCtxt, Place, SnapshotLocation};
 use crate::utils::json::ToJsonWithCompilerCtxt;
 
 #[derive(Clone, Debug, PartialEq, Eq)]
-pub struct Latest<'tcx>(FxHashMap<Place<'tcx>, SnapshotLocation>);
+pub struct Latest<'tcx> {
+    snapshots: FxHashMap<Place<'tcx>, SnapshotLocation>,
+    /// How many times each place has been snapshotted (i.e. made old) so
+    /// far, exposed via [`Self::generation`]/JSON so that two snapshots of
+    /// the same place recorded at the same [`SnapshotLocation`] (e.g. the
+    /// same statement revisited on a later loop iteration during the
+    /// dataflow fixpoint) can still be told apart by a human or tool reading
+    /// the output, even though they compare equal as `SnapshotLocation`s.
+    ///
+    /// This intentionally does *not* fold the counter into `SnapshotLocation`
+    /// or [`crate::utils::place::maybe_old::MaybeOldPlace`] itself:
+    /// `SnapshotLocation` is `Eq`/`Hash`/`Ord` key material for PCG graph
+    /// nodes and edges throughout `borrow_pcg` (joins, place-labelling, edge
+    /// identity), so changing what it means for two snapshots to be equal is
+    /// a pervasive, correctness-sensitive change to node identity that needs
+    /// compiler and test verification this environment doesn't have. The
+    /// counter here is diagnostic metadata layered on top, not a new
+    /// identity.
+    generations: FxHashMap<Place<'tcx>, u32>,
+}
 
 impl<'tcx> DebugLines<CompilerCtxt<'_, 'tcx>> for Latest<'tcx> {
     fn debug_lines(&self, repacker: CompilerCtxt<'_, 'tcx>) -> Vec<String> {
-        self.0
+        self.snapshots
             .iter()
-            .map(|(p, l)| format!("{} -> {:?}", p.to_short_string(repacker), l))
+            .map(|(p, l)| {
+                format!(
+                    "{} -> {:?} (generation {})",
+                    p.to_short_string(repacker),
+                    l,
+                    self.generation(*p)
+                )
+            })
             .collect()
     }
 }
@@ -26,7 +52,7 @@ impl<'tcx> DebugLines<CompilerCtxt<'_, 'tcx>> for Latest<'tcx> {
 impl<'tcx, BC: Copy> ToJsonWithCompilerCtxt<'tcx, BC> for Latest<'tcx> {
     fn to_json(&self, ctxt: CompilerCtxt<'_, 'tcx, BC>) -> serde_json::Value {
         json!(self
-            .0
+            .snapshots
             .iter()
             .map(|(p, l)| {
                 let ty = p.ty(ctxt).ty;
@@ -42,7 +68,10 @@ impl<'tcx, BC: Copy> ToJsonWithCompilerCtxt<'tcx, BC> for Latest<'tcx> {
                 };
                 (
                     format!("{}: {}", p.to_short_string(ctxt), ty_str),
-                    format!("{l:?}"),
+                    json!({
+                        "at": format!("{l:?}"),
+                        "generation": self.generation(*p),
+                    }),
                 )
             })
             .collect::<BTreeMap<_, _>>())
@@ -57,11 +86,20 @@ impl Default for Latest<'_> {
 
 impl<'tcx> Latest<'tcx> {
     pub fn new() -> Self {
-        Self(FxHashMap::default())
+        Self {
+            snapshots: FxHashMap::default(),
+            generations: FxHashMap::default(),
+        }
+    }
+
+    /// How many times `place` has been snapshotted (made old) so far. `0` if
+    /// it has never been snapshotted.
+    pub fn generation(&self, place: Place<'tcx>) -> u32 {
+        self.generations.get(&place).copied().unwrap_or(0)
     }
 
     fn get_exact(&self, place: Place<'tcx>) -> Option<SnapshotLocation> {
-        self.0.get(&place).copied()
+        self.snapshots.get(&place).copied()
     }
 
     fn get_opt(
@@ -81,6 +119,26 @@ impl<'tcx> Latest<'tcx> {
         }
     }
 
+    /// The location `place` (or a prefix/postfix of it) was last snapshotted
+    /// at, if any. Unlike [`Self::get`], this returns `None` rather than
+    /// defaulting to [`SnapshotLocation::start`] when `place` was never
+    /// snapshotted, so callers can tell "not snapshotted" apart from
+    /// "snapshotted at the start of the body".
+    pub fn snapshot_of(
+        &self,
+        place: Place<'tcx>,
+        ctxt: CompilerCtxt<'_, 'tcx>,
+    ) -> Option<SnapshotLocation> {
+        self.get_opt(place, ctxt)
+    }
+
+    /// All places with a recorded snapshot, along with the location they
+    /// were snapshotted at. Does not include prefixes/postfixes that would
+    /// merely resolve to one of these entries via [`Self::get`]/[`Self::snapshot_of`].
+    pub fn snapshotted_places(&self) -> impl Iterator<Item = (Place<'tcx>, SnapshotLocation)> + '_ {
+        self.snapshots.iter().map(|(p, l)| (*p, *l))
+    }
+
     pub fn get(&self, place: Place<'tcx>, ctxt: CompilerCtxt<'_, 'tcx>) -> SnapshotLocation {
         self.get_opt(place, ctxt)
             .unwrap_or(SnapshotLocation::start())
@@ -94,8 +152,9 @@ impl<'tcx> Latest<'tcx> {
         if self.get_exact(place) == Some(location) {
             return false;
         }
+        *self.generations.entry(place).or_insert(0) += 1;
 
-        self.0.retain(|existing, loc| {
+        self.snapshots.retain(|existing, loc| {
             // After insertion of this place, if we were to lookup `existing`,
             // we'd get this location for `place`. For example if existing is `x.f.g`
             // and place is `x.f`, then `Latest::get_opt(x.f.g)` would not find `x.f.g` and
@@ -113,21 +172,28 @@ impl<'tcx> Latest<'tcx> {
             }
             true
         });
-        self.0.insert(place, location);
+        self.snapshots.insert(place, location);
         true
     }
 
     pub fn join(&mut self, other: &Self, block: BasicBlock, ctxt: CompilerCtxt<'_, 'tcx>) -> bool {
-        if self.0.is_empty() {
-            if other.0.is_empty() {
+        if self.snapshots.is_empty() {
+            if other.snapshots.is_empty() {
                 return false;
             } else {
-                self.0 = other.0.clone();
+                self.snapshots = other.snapshots.clone();
+                self.generations = other.generations.clone();
                 return true;
             }
         }
         let mut changed = false;
-        for (place, other_loc) in other.0.iter() {
+        for (place, other_generation) in other.generations.iter() {
+            let self_generation = self.generations.entry(*place).or_insert(0);
+            if *other_generation > *self_generation {
+                *self_generation = *other_generation;
+            }
+        }
+        for (place, other_loc) in other.snapshots.iter() {
             if let Some(self_loc) = self.get_opt(*place, ctxt) {
                 if self_loc != *other_loc {
                     self.insert_unchecked(*place, SnapshotLocation::Start(block));
@@ -80,6 +80,15 @@ impl BranchChoices {
         }
     }
 
+    fn chosen_successors(&self, body: &mir::Body<'_>) -> Vec<BasicBlock> {
+        effective_successors(self.from, body)
+            .into_iter()
+            .enumerate()
+            .filter(|(i, _)| self.chosen.contains(*i))
+            .map(|(_, s)| s)
+            .collect()
+    }
+
     fn join(&mut self, other: &Self, body: &mir::Body<'_>) -> BranchChoicesJoinResult {
         assert_eq!(self.from, other.from);
         let old_len = self.chosen.len();
@@ -133,8 +142,18 @@ impl Default for PathConditions {
 }
 
 impl<'tcx, BC: Copy> ToJsonWithCompilerCtxt<'tcx, BC> for PathConditions {
-    fn to_json(&self, _ctxt: CompilerCtxt<'_, 'tcx, BC>) -> serde_json::Value {
-        todo!()
+    fn to_json(&self, ctxt: CompilerCtxt<'_, 'tcx, BC>) -> serde_json::Value {
+        self.all_branch_choices()
+            .map(|bc| {
+                serde_json::json!({
+                    "from": bc.from.index(),
+                    "to": bc.chosen_successors(ctxt.body())
+                        .into_iter()
+                        .map(|b| b.index())
+                        .collect::<Vec<_>>(),
+                })
+            })
+            .collect()
     }
 }
 
@@ -181,6 +200,14 @@ impl PathConditions {
         self.0.retain(|c| c.from != from);
     }
 
+    /// Joins two edges' conditions (union of the paths each allows),
+    /// normalizing as it goes: if `from`'s choices come to cover every one
+    /// of its successors, the entry no longer constrains anything -- every
+    /// way of reaching `from` is now accounted for -- so it's dropped
+    /// rather than kept around as dead weight for later comparisons and
+    /// visualization. An entry present on only one side is already
+    /// maximally permissive (no recorded constraint beats any recorded
+    /// one), so it's dropped the same way rather than carried over.
     pub(crate) fn join(&mut self, other: &Self, body: &mir::Body<'_>) -> bool {
         let mut changed = false;
         for other_branch_choices in other.all_branch_choices() {
@@ -197,6 +224,18 @@ impl PathConditions {
                 }
             }
         }
+        // `other` has no recorded constraint for these `from`s at all, so
+        // it's unconditionally reachable through them -- the union with
+        // `self`'s (now-irrelevant) constraint is just "unconstrained".
+        let only_in_self = self
+            .all_branch_choices()
+            .map(|bc| bc.from)
+            .filter(|from| other.all_branch_choices().all(|bc| bc.from != *from))
+            .collect::<SmallVec<[BasicBlock; 8]>>();
+        for from in only_in_self {
+            self.delete_branch_choices(from);
+            changed = true;
+        }
         changed
     }
 
@@ -246,6 +285,26 @@ impl PathConditions {
         true
     }
 
+    /// Returns `false` iff some recorded branch choice was made up entirely
+    /// of successors rejected by `accepts` -- i.e. the edge these
+    /// conditions are attached to was only ever created on a path that
+    /// `accepts` can prove can't lead anywhere `accepts` allows. Unlike
+    /// [`Self::valid_for_path`], this doesn't need a concrete path: it's
+    /// for callers (e.g. a loop back edge join) that only have a predicate
+    /// over individual successors, not a full path to check choices
+    /// against.
+    pub(crate) fn all_choices_accepted_by(
+        &self,
+        body: &mir::Body<'_>,
+        accepts: impl Fn(BasicBlock, BasicBlock) -> bool,
+    ) -> bool {
+        self.all_branch_choices().all(|pc| {
+            pc.chosen_successors(body)
+                .into_iter()
+                .any(|s| accepts(pc.from, s))
+        })
+    }
+
     // pub fn paths(&self) -> Option<HashSet<Vec<PathCondition>>> {
     //     match self {
     //         PathConditions::AtBlock(_b) => None,
@@ -18,6 +18,7 @@ use crate::utils::json::ToJsonWithCompilerCtxt;
 use crate::utils::place::maybe_old::MaybeOldPlace;
 use crate::utils::place::maybe_remote::MaybeRemotePlace;
 use crate::utils::remote::RemotePlace;
+use crate::utils::static_place::StaticPlace;
 use crate::utils::{CompilerCtxt, SnapshotLocation};
 use crate::{
     pcg::{LocalNodeLike, PCGNode, PCGNodeLike},
@@ -103,7 +104,7 @@ impl PcgRegion {
             PcgRegion::ReBound(debruijn_index, region) => {
                 format!("ReBound({debruijn_index:?}, {region:?})")
             }
-            PcgRegion::ReLateParam(_) => todo!(),
+            PcgRegion::ReLateParam(late_param) => format!("ReLateParam({late_param:?})"),
         }
     }
 
@@ -203,7 +204,8 @@ impl<'tcx> HasValidityCheck<'tcx> for MaybeRemoteRegionProjectionBase<'tcx> {
     fn check_validity(&self, ctxt: CompilerCtxt<'_, 'tcx>) -> Result<(), String> {
         match self {
             MaybeRemoteRegionProjectionBase::Place(p) => p.check_validity(ctxt),
-            MaybeRemoteRegionProjectionBase::Const(_) => todo!(),
+            // Constants have no place-labeling invariants to check.
+            MaybeRemoteRegionProjectionBase::Const(_) => Ok(()),
         }
     }
 }
@@ -217,7 +219,10 @@ impl<'tcx, 'a> ToJsonWithCompilerCtxt<'tcx, &'a dyn BorrowCheckerInterface<'tcx>
     ) -> serde_json::Value {
         match self {
             MaybeRemoteRegionProjectionBase::Place(p) => p.to_json(ctxt),
-            MaybeRemoteRegionProjectionBase::Const(_) => todo!(),
+            MaybeRemoteRegionProjectionBase::Const(c) => json!({
+                "const": format!("{c}"),
+                "promoted_body": ctxt.promoted_body(*c).map(|body| format!("{body:?}")),
+            }),
         }
     }
 }
@@ -231,7 +236,10 @@ impl<'tcx, 'a> DisplayWithCompilerCtxt<'tcx, &'a dyn BorrowCheckerInterface<'tcx
     ) -> String {
         match self {
             MaybeRemoteRegionProjectionBase::Place(p) => p.to_short_string(ctxt),
-            MaybeRemoteRegionProjectionBase::Const(c) => format!("{c}"),
+            MaybeRemoteRegionProjectionBase::Const(c) => match ctxt.promoted_body(*c) {
+                Some(body) => format!("promoted({:?})", body.return_ty()),
+                None => format!("{c}"),
+            },
         }
     }
 }
@@ -858,3 +866,9 @@ impl From<RemotePlace> for MaybeRemoteRegionProjectionBase<'_> {
         MaybeRemoteRegionProjectionBase::Place(remote_place.into())
     }
 }
+
+impl From<StaticPlace> for MaybeRemoteRegionProjectionBase<'_> {
+    fn from(static_place: StaticPlace) -> Self {
+        MaybeRemoteRegionProjectionBase::Place(static_place.into())
+    }
+}
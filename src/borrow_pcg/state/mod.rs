@@ -1,11 +1,13 @@
 use super::{
     borrow_pcg_edge::{BlockedNode, BorrowPcgEdgeRef, BorrowPcgEdge, ToBorrowsEdge},
-    edge::borrow::RemoteBorrow,
+    edge::borrow::{RemoteBorrow, StaticBorrow},
     graph::BorrowsGraph,
     latest::Latest,
     path_condition::{PathCondition, PathConditions},
     visitor::extract_regions,
 };
+use crate::rustc_interface::hir::def_id::DefId;
+use crate::utils::static_place::StaticPlace;
 use crate::{action::BorrowPcgAction, utils::place::maybe_remote::MaybeRemotePlace};
 use crate::{
     borrow_pcg::borrow_pcg_edge::LocalNode,
@@ -295,7 +297,18 @@ impl<'tcx> BorrowsState<'tcx> {
             _ => {
                 match capabilities.get(blocked_place) {
                     Some(CapabilityKind::Exclusive) => {
-                        assert!(capabilities.insert(blocked_place, CapabilityKind::Read));
+                        // `UnsafeCell<T>` interiors are writable through a
+                        // shared reference in real Rust; see
+                        // `UNSAFE_CELL_WRITE_CAPABILITY`.
+                        let shared_borrow_cap =
+                            if *crate::utils::UNSAFE_CELL_WRITE_CAPABILITY
+                                && blocked_place.is_unsafe_cell(ctxt)
+                            {
+                                CapabilityKind::ShallowExclusive
+                            } else {
+                                CapabilityKind::Read
+                            };
+                        assert!(capabilities.insert(blocked_place, shared_borrow_cap));
                     }
                     Some(CapabilityKind::Read) => {
                         // Do nothing, this just adds another shared borrow
@@ -318,4 +331,28 @@ impl<'tcx> BorrowsState<'tcx> {
             }
         }
     }
+
+    /// Like [`Self::add_borrow`], but for a borrow of a `static` or
+    /// `#[thread_local]` static item (`def_id`) rather than a named place,
+    /// e.g. for `let x = &FOO;` or `let x = &mut THREAD_LOCAL;`. Statics
+    /// aren't tracked in [`PlaceCapabilities`], so unlike `add_borrow` there's
+    /// no capability bookkeeping to do for the blocked place.
+    pub(crate) fn add_static_borrow(
+        &mut self,
+        def_id: DefId,
+        assigned_place: Place<'tcx>,
+        ctxt: CompilerCtxt<'_, 'tcx>,
+    ) {
+        assert!(
+            assigned_place.ty(ctxt).ty.ref_mutability().is_some(),
+            "Assigned place {:?} is not a reference. Ty: {:?}",
+            assigned_place,
+            assigned_place.ty(ctxt).ty
+        );
+        let borrow_edge = StaticBorrow::new(StaticPlace::new(def_id), assigned_place.into());
+        assert!(self.graph.insert(
+            BorrowEdge::Static(borrow_edge).to_borrow_pcg_edge(self.path_conditions.clone()),
+            ctxt
+        ));
+    }
 }
@@ -9,7 +9,8 @@ use super::borrow_pcg_edge::{BlockedNode, BorrowPcgEdge};
 use crate::utils::json::ToJsonWithCompilerCtxt;
 use crate::{
     borrow_pcg::{edge_data::EdgeData, state::BorrowsState},
-    utils::CompilerCtxt,
+    pcg::PcgError,
+    utils::{CompilerCtxt, Place},
 };
 
 type UnblockEdge<'tcx> = BorrowPcgEdge<'tcx>;
@@ -18,15 +19,57 @@ pub struct UnblockGraph<'tcx> {
     edges: HashSet<UnblockEdge<'tcx>>,
 }
 
+/// How to order edges that become killable in the same round of
+/// [`UnblockGraph::actions_with_tie_break`]'s topological sort (i.e. edges
+/// with no ordering dependency between them). The sort itself -- which round
+/// an edge is killed in -- is always the same regardless of this choice;
+/// this only decides the order actions are emitted in *within* a round.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ExpiryTieBreak {
+    /// Order same-round edges by their `{:?}` representation. Neither
+    /// [`BorrowPcgEdge`] nor [`super::borrow_pcg_edge::BorrowPcgEdgeKind`]
+    /// implement `Ord` (they're graph node/edge identity types compared
+    /// throughout `borrow_pcg` by `Eq`/`Hash` only, not by rank), so this is
+    /// the cheapest total order available without adding derives to those
+    /// types and everything they're built from.
+    #[default]
+    LexicographicDebug,
+}
+
+impl ExpiryTieBreak {
+    fn sort<'tcx>(self, edges: &mut [&BorrowPcgEdge<'tcx>]) {
+        match self {
+            ExpiryTieBreak::LexicographicDebug => {
+                edges.sort_by_key(|edge| format!("{edge:?}"));
+            }
+        }
+    }
+}
+
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct BorrowPcgUnblockAction<'tcx> {
     pub(super) edge: BorrowPcgEdge<'tcx>,
+    /// This action's position in the order it should be applied in, relative
+    /// to the other actions returned alongside it (lower runs first). Wand
+    /// application order matters to consumers like Prusti, so
+    /// [`UnblockGraph::actions`] assigns this deterministically rather than
+    /// leaving it to be inferred from `Vec` position (which callers may
+    /// reorder, e.g. while deduplicating against other actions). Actions not
+    /// produced by [`UnblockGraph::actions`] (e.g. [`BorrowPcgUnblockAction`]s
+    /// recovered from an already-applied [`super::action::actions::BorrowPcgActions`] log via
+    /// [`From<BorrowPcgEdge>`]) get `0`, since there's no topological
+    /// computation to order them against.
+    order: usize,
 }
 
 impl<'tcx> BorrowPcgUnblockAction<'tcx> {
     pub fn edge(&self) -> &BorrowPcgEdge<'tcx> {
         &self.edge
     }
+
+    pub fn order(&self) -> usize {
+        self.order
+    }
 }
 
 impl<'tcx, 'a> ToJsonWithCompilerCtxt<'tcx, &'a dyn BorrowCheckerInterface<'tcx>>
@@ -44,7 +87,7 @@ impl<'tcx, 'a> ToJsonWithCompilerCtxt<'tcx, &'a dyn BorrowCheckerInterface<'tcx>
 
 impl<'tcx> From<BorrowPcgEdge<'tcx>> for BorrowPcgUnblockAction<'tcx> {
     fn from(edge: BorrowPcgEdge<'tcx>) -> Self {
-        Self { edge }
+        Self { edge, order: 0 }
     }
 }
 
@@ -65,6 +108,21 @@ impl<'tcx> UnblockGraph<'tcx> {
         ug
     }
 
+    /// The ordered list of edge-expiry actions that, applied in order,
+    /// restore [`crate::free_pcs::CapabilityKind::Exclusive`] to `place` --
+    /// i.e. everything currently blocking it. Equivalent to
+    /// `UnblockGraph::for_node(place, state, ctxt).actions(ctxt)`, for
+    /// verifiers (e.g. Prusti) that want to generate an "expire borrows"
+    /// obligation for a place on demand without first naming the more
+    /// general [`BlockedNode`] the rest of this API works over.
+    pub fn for_place(
+        place: Place<'tcx>,
+        state: &BorrowsState<'tcx>,
+        repacker: CompilerCtxt<'_, 'tcx>,
+    ) -> Result<Vec<BorrowPcgUnblockAction<'tcx>>, PcgError> {
+        Ok(Self::for_node(place, state, repacker).actions(repacker)?)
+    }
+
     pub fn is_empty(&self) -> bool {
         self.edges.is_empty()
     }
@@ -74,13 +132,35 @@ impl<'tcx> UnblockGraph<'tcx> {
             .retain(|edge| edge.valid_for_path(path, ctxt.body()));
     }
 
+    /// Returns an ordered list of actions to unblock the edges in the graph,
+    /// using [`ExpiryTieBreak::default`] to order edges that become
+    /// killable in the same topological round. See [`Self::actions_with_tie_break`].
+    pub fn actions(
+        self,
+        repacker: CompilerCtxt<'_, 'tcx>,
+    ) -> Result<Vec<BorrowPcgUnblockAction<'tcx>>, PCGInternalError> {
+        self.actions_with_tie_break(ExpiryTieBreak::default(), repacker)
+    }
+
     /// Returns an ordered list of actions to unblock the edges in the graph.
-    /// This is essentially a topological sort of the edges.
+    /// This is essentially a topological sort of the edges: on each round,
+    /// every edge not currently blocked by any other remaining edge ("a
+    /// leaf") is killed.
+    ///
+    /// A round can produce more than one leaf (e.g. two independent borrows
+    /// that both become unblocked at once), and [`Self::edges`] is a
+    /// `HashSet`, so which of a round's leaves is emitted first is otherwise
+    /// an implementation detail of hashing. `tie_break` picks a deterministic
+    /// order among them instead; the index each action ends up at is exposed
+    /// via [`BorrowPcgUnblockAction::order`] so callers that reorder or
+    /// filter the returned `Vec` (e.g. Prusti, when merging it with other
+    /// actions) don't lose track of the intended application order.
     ///
     /// If this method returns an error, it is definitely a bug in the PCG
     /// implementation and should be reported.
-    pub fn actions(
+    pub fn actions_with_tie_break(
         self,
+        tie_break: ExpiryTieBreak,
         repacker: CompilerCtxt<'_, 'tcx>,
     ) -> Result<Vec<BorrowPcgUnblockAction<'tcx>>, PCGInternalError> {
         let mut edges = self.edges;
@@ -93,11 +173,15 @@ impl<'tcx> UnblockGraph<'tcx> {
                 edge.blocked_by_nodes(repacker)
                     .all(|node| edges.iter().all(|e| !e.blocks_node(node.into(), repacker)))
             };
-            for edge in edges.iter() {
-                if should_kill_edge(edge) {
-                    actions.push(BorrowPcgUnblockAction { edge: edge.clone() });
-                    to_keep.remove(edge);
-                }
+            let mut leaves: Vec<&BorrowPcgEdge<'tcx>> =
+                edges.iter().filter(|edge| should_kill_edge(edge)).collect();
+            tie_break.sort(&mut leaves);
+            for edge in leaves {
+                actions.push(BorrowPcgUnblockAction {
+                    edge: edge.clone(),
+                    order: actions.len(),
+                });
+                to_keep.remove(edge);
             }
             if to_keep.len() >= edges.len() {
                 return Err(PCGInternalError::new(format!(
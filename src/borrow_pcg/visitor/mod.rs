@@ -51,6 +51,27 @@ impl<'tcx> TypeVisitor<ty::TyCtxt<'tcx>> for LifetimeExtractor<'tcx> {
 /// `['c, 'd]` respectively. This enables substitution of regions to handle
 /// moves in the PCG e.g for the statement `let x: T<'a, 'b> = move c: T<'c,
 /// 'd>`.
+///
+/// Caveat: since this walks `ty` with `super_visit_with` rather than
+/// normalizing first, a region hidden behind an un-normalized
+/// associated-type projection is invisible to it. E.g. for `&'a T::Target`,
+/// `super_visit_with` finds `'a` (it's a direct argument of the `Ref`) but
+/// not any region appearing in the concrete type `T::Target` eventually
+/// normalizes to -- that region is only an implicit detail of `Target`'s
+/// definition for the impl that ends up selected, not part of the
+/// projection's own generic args. This mirrors
+/// [`crate::pcg::visitor::function_call`]'s call-site handling, which reads
+/// regions off the already-normalized, already-substituted types MIR
+/// typeck produces for a call's actual arguments/destination rather than
+/// re-deriving them from the callee's generic signature -- so this gap only
+/// matters for a type that is itself generic over an associated type at the
+/// point this function is called on it, which only `PcgVisitor` call sites
+/// analyzing a generic function's own body (not its concrete callers) can
+/// hit. Closing it would mean threading a param/typing environment (like
+/// `pcg::engine::MonomorphizeEnv`) through to normalize `ty` here, which
+/// isn't available on [`CompilerCtxt`] today and is risky to add
+/// blind (wrong normalization can ICE on types that are only generically
+/// well-formed) without a compiler to check it against.
 pub(crate) fn extract_regions<'tcx, C: Copy>(
     ty: ty::Ty<'tcx>,
     repacker: CompilerCtxt<'_, 'tcx, C>,
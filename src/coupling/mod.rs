@@ -433,6 +433,14 @@ impl<'tcx, N: Copy + Ord + Clone + Hash + std::fmt::Debug, E: Clone + Eq + Hash>
     }
 }
 
+/// A true hyperedge: a set of `lhs` nodes jointly blocking a set of `rhs`
+/// nodes, as opposed to [`DisjointSetGraph`]'s binary edges between
+/// [`Coupled`] node sets (which say the two *sets* are related, not that
+/// every element of one jointly depends on every element of the other).
+/// [`crate::borrow_pcg::edge::abstraction::AbstractionBlockEdge`] is the
+/// canonical source of these: a function call or loop abstraction with
+/// several inputs and outputs is one obligation relating all of them at
+/// once, not `inputs.len() * outputs.len()` independent borrows.
 #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
 pub struct HyperEdge<N> {
     lhs: BTreeSet<N>,
@@ -440,12 +448,31 @@ pub struct HyperEdge<N> {
 }
 
 impl<N: Ord> HyperEdge<N> {
+    /// # Panics
+    /// Panics if `lhs` or `rhs` is empty: a hyperedge with no nodes on one
+    /// side isn't a relation between anything, mirroring the
+    /// `assert!(!inputs.is_empty())`/`assert!(!outputs.is_empty())` checks in
+    /// [`crate::borrow_pcg::edge::abstraction::AbstractionBlockEdge::new`].
+    pub fn new(lhs: BTreeSet<N>, rhs: BTreeSet<N>) -> Self {
+        assert!(!lhs.is_empty());
+        assert!(!rhs.is_empty());
+        Self { lhs, rhs }
+    }
+
     pub fn lhs(&self) -> &BTreeSet<N> {
         &self.lhs
     }
     pub fn rhs(&self) -> &BTreeSet<N> {
         &self.rhs
     }
+
+    /// Whether this edge actually couples more than one node on either
+    /// side. `false` for a plain one-to-one edge, in which case rendering it
+    /// as a bundle of grouped edges (see `visualization::graph_constructor`)
+    /// is unnecessary.
+    pub fn is_hyper(&self) -> bool {
+        self.lhs.len() > 1 || self.rhs.len() > 1
+    }
 }
 
 impl<N, E> fmt::Display for DisjointSetGraph<N, E>
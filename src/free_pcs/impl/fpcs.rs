@@ -53,6 +53,18 @@ impl<'tcx> FreePlaceCapabilitySummary<'tcx> {
         )
     }
 
+    /// Computes the expand/collapse operations that transform `self` into
+    /// `other`. Useful for e.g. bridging the owned-PCS state at the end of a
+    /// loop body back to a user-specified loop invariant.
+    pub fn repack_ops(
+        &self,
+        other: &Self,
+        place_capabilities: &PlaceCapabilities<'tcx>,
+        repacker: CompilerCtxt<'_, 'tcx>,
+    ) -> std::result::Result<Vec<RepackOp<'tcx>>, PcgError> {
+        self.bridge(other, place_capabilities, repacker)
+    }
+
     pub fn initialize_as_start_block(
         &mut self,
         capabilities: &mut PlaceCapabilities<'tcx>,
@@ -114,6 +114,10 @@ impl<'tcx> CapabilityProjections<'tcx> {
                     } else {
                         self_place_capabilities.remove(*place);
                     }
+                    // Union sibling fields never have a capability inserted
+                    // in the first place (see `CapabilityProjections::expand`),
+                    // so `other_place_capabilities.get` is `None` for them
+                    // here and this just keeps them capability-less.
                     for place in place.expansion_places(other_expansion, repacker) {
                         if let Some(cap) = other_place_capabilities.get(place) {
                             self_place_capabilities.insert(place, cap);
@@ -14,7 +14,7 @@ use crate::{
 use itertools::Itertools;
 
 use crate::{
-    free_pcs::{CapabilityKind, RepackOp},
+    free_pcs::{CapabilityKind, CapabilityLattice, RepackOp},
     pcg::{PCGInternalError, PcgError},
     utils::{corrected::CorrectedPlace, display::DisplayWithCompilerCtxt, CompilerCtxt, Place},
 };
@@ -183,6 +183,15 @@ impl<'tcx> CapabilityProjections<'tcx> {
         let expansion = from.expand(*to, repacker)?;
 
         for place in expansion.other_expansions() {
+            if place.is_union_field(repacker) {
+                // Sibling union fields overlap in memory with the field
+                // being expanded to, so granting them `from_cap` would let
+                // us believe we can independently read/write them. Instead
+                // leave them without a tracked capability: writing the
+                // expanded field invalidates whatever was there.
+                capabilities.remove(place);
+                continue;
+            }
             capabilities.insert(place, if for_cap.is_read() { for_cap } else { from_cap });
         }
 
@@ -242,7 +251,7 @@ impl<'tcx> CapabilityProjections<'tcx> {
                         .iter()
                         .fold(CapabilityKind::Exclusive, |acc, place| {
                             match capabilities.remove(*place) {
-                                Some(cap) => acc.minimum(cap).unwrap_or(CapabilityKind::Write),
+                                Some(cap) => acc.meet(cap).unwrap_or(CapabilityKind::Write),
                                 None => acc,
                             }
                         });
@@ -12,7 +12,12 @@ use std::{
 #[derive(Copy, Clone, PartialEq, Eq, Hash)]
 pub enum CapabilityKind {
     /// For borrowed places only: permits reads from the location, but not writes or
-    /// drops.
+    /// drops. This is the capability a place retains while shared-borrowed:
+    /// it is deliberately incomparable with [`CapabilityKind::Write`] (a
+    /// place with `Write` cannot be read from, and a place with `Read`
+    /// cannot be written to), so joining a `Read` place with a `Write` place
+    /// drops the capability entirely rather than over-weakening to one or
+    /// the other.
     Read,
 
     /// For owned places, this capability is used when the place is moved out
@@ -26,6 +31,13 @@ pub enum CapabilityKind {
 
     /// [`CapabilityKind::Exclusive`] for everything not through a dereference,
     /// [`CapabilityKind::Write`] for everything through a dereference.
+    ///
+    /// This is the capability produced for a place just initialized via
+    /// `Rvalue::ShallowInitBox` (e.g. the desugaring of `Box::new`): the
+    /// allocation exists but its payload is not yet written, so the
+    /// dereferenced place starts out with only [`CapabilityKind::Read`]
+    /// (see [`crate::free_pcs::RepackOp::DerefShallowInit`]) and becomes
+    /// [`CapabilityKind::Exclusive`] once something is written through it.
     ShallowExclusive,
 }
 impl Debug for CapabilityKind {
@@ -92,11 +104,93 @@ impl CapabilityKind {
     }
 }
 
+/// Names the lattice structure of the [`CapabilityKind`] partial order (see
+/// its [`PartialOrd`] impl) so that call sites which are really asking a
+/// meet/join/ordering question read as such, instead of as ad-hoc
+/// [`PartialOrd`] comparisons or a call to the oddly-named
+/// [`CapabilityKind::minimum`].
+///
+/// The poset has a top element ([`CapabilityKind::Exclusive`] dominates
+/// every other capability), so [`CapabilityLattice::join`] (the least upper
+/// bound) is always defined. It has no bottom element ([`CapabilityKind::Read`]
+/// and [`CapabilityKind::Write`] are incomparable minimal elements), so
+/// [`CapabilityLattice::meet`] (the greatest lower bound) is partial: this is
+/// exactly the case [`CapabilityKind::minimum`] already returns `None` for.
+pub trait CapabilityLattice: Sized + Copy + PartialOrd {
+    /// The least upper bound of `self` and `other`.
+    fn join(self, other: Self) -> Self;
+
+    /// The greatest lower bound of `self` and `other`, or `None` if no
+    /// element is weaker than both. This is the operation used when joining
+    /// two branches of a dataflow analysis: the result is the strongest
+    /// capability valid on both branches.
+    fn meet(self, other: Self) -> Option<Self>;
+
+    /// `true` iff `self` is no stronger than `other`, i.e. a place with
+    /// `self` capability may be weakened to `other`.
+    fn leq(self, other: Self) -> bool {
+        matches!(self.partial_cmp(&other), Some(Ordering::Less | Ordering::Equal))
+    }
+}
+
+impl CapabilityLattice for CapabilityKind {
+    fn join(self, other: Self) -> Self {
+        match self.partial_cmp(&other) {
+            Some(Ordering::Greater) | Some(Ordering::Equal) => self,
+            Some(Ordering::Less) => other,
+            // Exclusive dominates every capability, so it's always a valid
+            // (if not always tight) upper bound for incomparable pairs.
+            None => CapabilityKind::Exclusive,
+        }
+    }
+
+    fn meet(self, other: Self) -> Option<Self> {
+        self.minimum(other)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use std::collections::HashMap;
 
+    #[test]
+    fn test_read_write_incomparable() {
+        // `Read` (shared-borrowed, readable) and `Write` (writable but not
+        // exclusive) are deliberately incomparable: neither can be weakened
+        // to the other, so joining them must drop the capability rather
+        // than silently picking one.
+        assert_eq!(
+            CapabilityKind::Read.partial_cmp(&CapabilityKind::Write),
+            None
+        );
+        assert_eq!(CapabilityKind::Read.minimum(CapabilityKind::Write), None);
+    }
+
+    #[test]
+    fn test_join_is_always_defined_and_is_an_upper_bound() {
+        // Unlike `minimum`/`meet`, `join` never returns `None`: `Exclusive`
+        // is a top element, so it's always a valid upper bound even for
+        // incomparable pairs like `Read`/`Write`.
+        let caps = [
+            CapabilityKind::Exclusive,
+            CapabilityKind::ShallowExclusive,
+            CapabilityKind::Write,
+            CapabilityKind::Read,
+        ];
+        for a in caps {
+            for b in caps {
+                let joined = a.join(b);
+                assert!(a.leq(joined));
+                assert!(b.leq(joined));
+            }
+        }
+        assert_eq!(
+            CapabilityKind::Read.join(CapabilityKind::Write),
+            CapabilityKind::Exclusive
+        );
+    }
+
     #[test]
     fn test_capability_kind_dag_reachability() {
         use petgraph::algo::has_path_connecting;
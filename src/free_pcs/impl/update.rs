@@ -12,10 +12,11 @@ use crate::{
         place_capabilities::PlaceCapabilities,
         triple::{PlaceCondition, Triple},
     },
-    pcg_validity_assert,
+    pcg_category_validity_assert,
     utils::{
         CompilerCtxt,
         LocalMutationIsAllowed,
+        ValidityCheckCategory,
     },
 };
 
@@ -33,6 +34,8 @@ impl<'tcx> CapabilityLocals<'tcx> {
         match pre {
             PlaceCondition::ExpandTwoPhase(_place) => {}
             PlaceCondition::RemoveCapability(_place) => {}
+            PlaceCondition::DropWrite(_place) => {}
+            PlaceCondition::BoxDerefMoveWrite(_place) => unreachable!("never a pre-condition"),
             PlaceCondition::Unalloc(local) => {
                 assert!(
                     self[local].is_unallocated(),
@@ -47,9 +50,13 @@ impl<'tcx> CapabilityLocals<'tcx> {
                     }
                     CapabilityKind::Write => {
                         // Cannot get write on a shared ref
-                        pcg_validity_assert!(place
-                            .is_mutable(LocalMutationIsAllowed::Yes, repacker)
-                            .is_ok());
+                        pcg_category_validity_assert!(
+                            ValidityCheckCategory::CapabilityConsistency,
+                            repacker,
+                            place
+                                .is_mutable(LocalMutationIsAllowed::Yes, repacker)
+                                .is_ok()
+                        );
                     }
                     CapabilityKind::Exclusive => {
                         // Cannot get exclusive on a shared ref
@@ -64,7 +71,9 @@ impl<'tcx> CapabilityLocals<'tcx> {
                 }
                 if place.is_owned(repacker) {
                     if let Some(current_cap) = capabilities.get(place) {
-                        pcg_validity_assert!(
+                        pcg_category_validity_assert!(
+                            ValidityCheckCategory::CapabilityConsistency,
+                            repacker,
                             matches!(
                                 current_cap.partial_cmp(&required_cap),
                                 Some(Ordering::Equal) | Some(Ordering::Greater)
@@ -72,7 +81,12 @@ impl<'tcx> CapabilityLocals<'tcx> {
                             "Capability {current_cap:?} is not >= {required_cap:?} for {place:?}"
                         )
                     } else {
-                        pcg_validity_assert!(false, "No capability for {place:?}");
+                        pcg_category_validity_assert!(
+                            ValidityCheckCategory::CapabilityConsistency,
+                            repacker,
+                            false,
+                            "No capability for {place:?}"
+                        );
                     }
                 }
             }
@@ -110,6 +124,18 @@ impl<'tcx> CapabilityLocals<'tcx> {
             PlaceCondition::ExpandTwoPhase(place) => {
                 place_capabilities.insert(place, CapabilityKind::Read);
             }
+            PlaceCondition::DropWrite(_place) => unreachable!("DropWrite is never a post-condition"),
+            PlaceCondition::BoxDerefMoveWrite(place) => {
+                let box_place = place
+                    .target_place()
+                    .expect("BoxDerefMoveWrite place is not a deref");
+                self[box_place.local]
+                    .get_allocated_mut()
+                    .expansions
+                    .remove(&box_place);
+                place_capabilities.remove(place);
+                place_capabilities.insert(box_place, CapabilityKind::ShallowExclusive);
+            }
         }
     }
 }
@@ -9,15 +9,18 @@ use std::{alloc::Allocator, rc::Rc};
 use derive_more::Deref;
 
 use crate::{
-    action::{BorrowPcgAction, OwnedPcgAction, PcgActions},
+    action::{BorrowPcgAction, OwnedPcgAction, PcgActionKind, PcgActions},
     borrow_pcg::{
-        borrow_pcg_edge::{BorrowPcgEdgeRef, BorrowPcgEdge},
+        borrow_pcg_edge::{BorrowPcgEdge, BorrowPcgEdgeLike, BorrowPcgEdgeRef, LocalNode},
         latest::Latest,
         region_projection::MaybeRemoteRegionProjectionBase,
     },
-    pcg::{successor_blocks, EvalStmtPhase, PCGNode, Pcg, PcgEngine, PcgError, PcgSuccessor},
+    pcg::{
+        successor_blocks, EvalStmtPhase, FunctionSummary, PCGNode, Pcg, PcgDiagnostics, PcgEngine,
+        PcgError, PcgStats, PcgSuccessor, PcgTimings,
+    },
     rustc_interface::{
-        data_structures::fx::FxHashSet,
+        data_structures::fx::{FxHashMap, FxHashSet},
         dataflow::AnalysisEngine,
         index::IndexVec,
         middle::{
@@ -27,14 +30,16 @@ use crate::{
         mir_dataflow::ResultsCursor,
     },
     utils::{
-        display::DebugLines, domain_data::DomainDataStates, validity::HasValidityCheck, Place,
+        display::DebugLines, domain_data::DomainDataStates, place::maybe_remote::MaybeRemotePlace,
+        validity::HasValidityCheck, Place, SnapshotLocation,
     },
 };
 
 use crate::borrow_pcg::action::actions::BorrowPcgActions;
+use crate::r#loop::LoopAnalysis;
 use crate::utils::eval_stmt_data::EvalStmtData;
 use crate::{
-    free_pcs::{CapabilityLocals, RepackOp},
+    free_pcs::{CapabilityKind, CapabilityLocals, RepackOp},
     utils::CompilerCtxt,
 };
 
@@ -44,16 +49,39 @@ pub struct PcgAnalysis<'mir, 'tcx: 'mir, A: Allocator + Copy> {
     pub cursor: Cursor<'mir, 'tcx, AnalysisEngine<PcgEngine<'mir, 'tcx, A>>>,
     curr_stmt: Option<Location>,
     end_stmt: Option<Location>,
+    ignore_unwind_paths: bool,
 }
 
 impl<'mir, 'tcx, A: Allocator + Copy> PcgAnalysis<'mir, 'tcx, A> {
+    /// A snapshot of the counters gathered while this analysis's fixpoint
+    /// was computed (expansions, collapses, join iterations, ...). See
+    /// [`PcgStats`].
+    pub fn stats(&self) -> PcgStats {
+        self.analysis().stats_handle().borrow().clone()
+    }
+
+    /// A wall-clock breakdown of the time spent computing this analysis's
+    /// fixpoint. See [`PcgTimings`].
+    pub fn timings(&self) -> PcgTimings {
+        self.analysis().timings_handle().borrow().clone()
+    }
+
+    /// The imprecisions/skips the engine recorded while this analysis's
+    /// fixpoint was computed (indirect calls, raw-pointer escapes, ...). See
+    /// [`PcgDiagnostics`].
+    pub fn diagnostics(&self) -> PcgDiagnostics {
+        self.analysis().diagnostics_handle().borrow().clone()
+    }
+
     pub(crate) fn new(
         cursor: Cursor<'mir, 'tcx, AnalysisEngine<PcgEngine<'mir, 'tcx, A>>>,
+        ignore_unwind_paths: bool,
     ) -> Self {
         Self {
             cursor,
             curr_stmt: None,
             end_stmt: None,
+            ignore_unwind_paths,
         }
     }
 
@@ -127,10 +155,12 @@ impl<'mir, 'tcx, A: Allocator + Copy> PcgAnalysis<'mir, 'tcx, A> {
                     .reachable_blocks
                     .contains(succ.index())
             })
+            .filter(|succ| !(self.ignore_unwind_paths && self.body()[*succ].is_cleanup))
             .collect::<Vec<_>>();
         let succs = succ_blocks
             .into_iter()
             .map(|succ| {
+                let is_cleanup = self.body()[succ].is_cleanup;
                 self.cursor.seek_to_block_start(succ);
                 let to = &self.cursor.get().data()?.pcg;
 
@@ -168,12 +198,74 @@ impl<'mir, 'tcx, A: Allocator + Copy> PcgAnalysis<'mir, 'tcx, A> {
                     succ,
                     actions,
                     to.entry_state.borrow.clone().into(),
+                    is_cleanup,
                 ))
             })
             .collect::<Result<Vec<_>, PcgError>>()?;
         Ok(PcgTerminator { succs })
     }
 
+    /// Derives a [`FunctionSummary`] for the analyzed function itself, from
+    /// the state at its `Return` terminator (if reachable): for each of the
+    /// return place's region projections, walks backward through the borrow
+    /// graph to find which arguments' [`crate::utils::remote::RemotePlace`]
+    /// nodes (introduced for reference-typed arguments by
+    /// [`crate::borrow_pcg::state::BorrowsState::initialize_as_start_block`])
+    /// it is transitively blocked by, i.e. which arguments the return value
+    /// borrows from.
+    ///
+    /// Returns `None` if the function has no reachable `Return` terminator
+    /// (e.g. it always panics or loops). This is the same shape of fact
+    /// [`crate::PcgOptionsBuilder::function_summaries`] lets callers supply
+    /// by hand for functions PCG can't analyze; this method computes it for
+    /// a function PCG *can* analyze, so callers summarizing a whole crate
+    /// don't need to hand-write an entry for every function.
+    pub fn derive_function_summary(&mut self) -> Result<Option<FunctionSummary>, PcgError> {
+        let Some(return_block) = self.body().basic_blocks.iter_enumerated().find_map(
+            |(block, data)| match data.terminator().kind {
+                mir::TerminatorKind::Return => Some(block),
+                _ => None,
+            },
+        ) else {
+            return Ok(None);
+        };
+        if !self.analysis().reachable_blocks.contains(return_block.index()) {
+            return Ok(None);
+        }
+        let ctxt = self.ctxt();
+        self.analysis_for_bb(return_block);
+        while self.curr_stmt.unwrap() != self.end_stmt.unwrap() {
+            self.next(self.curr_stmt.unwrap())?;
+        }
+        let pcg = &self.cursor.get().data()?.pcg;
+        let graph = pcg.states[EvalStmtPhase::PostMain].borrow.graph();
+
+        let return_place: Place<'tcx> = mir::RETURN_PLACE.into();
+        let mut borrows_from_args = FxHashSet::default();
+        for start in return_place
+            .region_projections(ctxt)
+            .into_iter()
+            .map(LocalNode::from)
+        {
+            for edge in graph.ancestor_edges(start, ctxt) {
+                for blocked in edge.blocked_nodes(ctxt) {
+                    if let PCGNode::Place(MaybeRemotePlace::Remote(rp)) = blocked
+                        && let Some(idx) = self
+                            .body()
+                            .args_iter()
+                            .position(|arg| arg == rp.assigned_local())
+                    {
+                        borrows_from_args.insert(idx);
+                    }
+                }
+            }
+        }
+
+        let mut borrows_from_args = borrows_from_args.into_iter().collect::<Vec<_>>();
+        borrows_from_args.sort_unstable();
+        Ok(Some(FunctionSummary::new(borrows_from_args)))
+    }
+
     /// Obtains the results of the dataflow analysis for all blocks.
     ///
     /// This is rather expensive to compute and may take a lot of memory. You
@@ -186,6 +278,32 @@ impl<'mir, 'tcx, A: Allocator + Copy> PcgAnalysis<'mir, 'tcx, A> {
         Ok(PcgBasicBlocks(result))
     }
 
+    /// Like [`Self::results_for_all_blocks`], but streams each statement's
+    /// PCG state to `consumer` instead of collecting every `PcgLocation`
+    /// into a `PcgBasicBlocks`. `consumer` is called once per reachable
+    /// statement, in program order, and the `PcgLocation` is dropped
+    /// immediately afterwards, so memory usage is bounded by the current
+    /// block's entry state rather than growing with the size of the body.
+    pub fn for_each_location(
+        &mut self,
+        mut consumer: impl FnMut(&PcgLocation<'tcx>),
+    ) -> Result<(), PcgError> {
+        for block in self.body().basic_blocks.indices() {
+            if !self.analysis().reachable_blocks.contains(block.index()) {
+                continue;
+            }
+            self.analysis_for_bb(block);
+            while self.curr_stmt.unwrap() != self.end_stmt.unwrap() {
+                match self.next(self.curr_stmt.unwrap())? {
+                    Some(stmt) => consumer(&stmt),
+                    None => break,
+                }
+            }
+            self.terminator()?;
+        }
+        Ok(())
+    }
+
     fn analysis(&self) -> &PcgEngine<'mir, 'tcx, A> {
         &self.cursor.analysis().0
     }
@@ -194,6 +312,67 @@ impl<'mir, 'tcx, A: Allocator + Copy> PcgAnalysis<'mir, 'tcx, A> {
         self.analysis().first_error.error().cloned()
     }
 
+    /// The PCG that holds at the start of every loop head in `loops`, once
+    /// the underlying join-until-fixpoint dataflow analysis has converged
+    /// -- i.e. the capability summary and borrow graph that's guaranteed to
+    /// hold at the start of every iteration of that loop, no matter how
+    /// many times it's taken. Intended for downstream verifiers that want
+    /// to state and check a loop invariant without re-deriving one by hand.
+    ///
+    /// This surfaces the fixpoint the existing join-based analysis already
+    /// computes, rather than computing a different (e.g. widened) one: a
+    /// widening operator over the capability/borrow-graph lattice would
+    /// need to be designed so it only ever *weakens* what PCG knows (never
+    /// invents a capability or edge that isn't really there), and getting
+    /// that wrong is a soundness bug, not just a performance one. Absent a
+    /// concrete convergence problem that justifies taking on that risk,
+    /// the plain joins-to-exhaustion rustc's dataflow framework already
+    /// performs remains the safer choice here.
+    pub fn loop_invariants(
+        &mut self,
+        loops: &LoopAnalysis,
+    ) -> Result<FxHashMap<BasicBlock, Pcg<'tcx>>, PcgError> {
+        let mut invariants = FxHashMap::default();
+        for block in self.body().basic_blocks.indices() {
+            if loops.loop_head_of(block).is_none() {
+                continue;
+            }
+            if !self.analysis().reachable_blocks.contains(block.index()) {
+                continue;
+            }
+            self.cursor.seek_to_block_start(block);
+            let entry_state = &self.cursor.get().data()?.pcg.entry_state;
+            invariants.insert(block, (**entry_state).clone());
+        }
+        Ok(invariants)
+    }
+
+    /// Per-loop structure (header, member blocks, exits, nesting depth)
+    /// paired with the converged invariant [`Self::loop_invariants`]
+    /// computes for that header, bundled into one [`LoopSummary`] per loop
+    /// so a downstream verifier generating loop invariants doesn't also
+    /// have to build and query its own [`LoopAnalysis`] over the same MIR
+    /// to get at the structure the invariant is for.
+    pub fn loop_summaries(&mut self) -> Result<Vec<LoopSummary<'tcx>>, PcgError> {
+        let loops = LoopAnalysis::find_loops(self.body());
+        let invariants = self.loop_invariants(&loops)?;
+        let mut summaries: Vec<_> = invariants
+            .into_iter()
+            .filter_map(|(header, invariant)| {
+                let loop_id = loops.loop_head_of(header)?;
+                Some(LoopSummary {
+                    header,
+                    blocks: loops.blocks(loop_id).collect(),
+                    exits: loops.exits(loop_id, self.body()),
+                    nest_depth: loops.loop_nest_depth(loop_id),
+                    invariant,
+                })
+            })
+            .collect();
+        summaries.sort_by_key(|s| s.header);
+        Ok(summaries)
+    }
+
     /// Recommended interface.
     /// Does *not* require that one calls `analysis_for_bb` first
     /// This function may return `None` if the PCG did not analyze this block.
@@ -223,6 +402,25 @@ impl<'mir, 'tcx, A: Allocator + Copy> PcgAnalysis<'mir, 'tcx, A> {
     }
 }
 
+/// A loop's structure (as computed by [`LoopAnalysis`]) together with the
+/// PCG invariant that holds at its header once the fixpoint has converged.
+/// Returned by [`PcgAnalysis::loop_summaries`].
+pub struct LoopSummary<'tcx> {
+    /// The loop header: the join point every back edge into this loop
+    /// targets.
+    pub header: BasicBlock,
+    /// Every block belonging to this loop, including nested loops.
+    pub blocks: FxHashSet<BasicBlock>,
+    /// Blocks outside this loop that an edge leaving it leads to (see
+    /// [`LoopAnalysis::exits`]).
+    pub exits: FxHashSet<BasicBlock>,
+    /// How many loops this loop is nested inside (0 = outermost).
+    pub nest_depth: usize,
+    /// The PCG guaranteed to hold at `header` on every iteration; see
+    /// [`PcgAnalysis::loop_invariants`].
+    pub invariant: Pcg<'tcx>,
+}
+
 #[derive(Deref)]
 pub struct PcgBasicBlocks<'tcx>(IndexVec<BasicBlock, Option<PcgBasicBlock<'tcx>>>);
 
@@ -235,6 +433,15 @@ impl<'tcx> PcgBasicBlocks<'tcx> {
         }
     }
 
+    /// O(1) random access to the PCG state at `location` for a given
+    /// `phase`, backed by the results already cached in `self`. Unlike
+    /// `PcgAnalysis`, which is driven by a forward-only dataflow cursor,
+    /// this doesn't require visiting locations in program order.
+    pub fn state_at(&self, location: Location, phase: EvalStmtPhase) -> Option<&Pcg<'tcx>> {
+        self.get_statement(location)
+            .map(|stmt| &stmt.states[phase])
+    }
+
     fn aggregate<'mir, T: std::hash::Hash + std::cmp::Eq>(
         &self,
         f: impl Fn(&PcgLocation<'tcx>) -> FxHashSet<T>,
@@ -314,6 +521,55 @@ impl<'tcx> PcgLocation<'tcx> {
         &self.actions[phase]
     }
 
+    /// The stable, semantic categorization ([`PcgActionKind`]) of each
+    /// action recorded during `phase`, in the order they were applied.
+    pub fn action_kinds(&self, phase: EvalStmtPhase) -> Vec<PcgActionKind<'tcx>> {
+        self.actions[phase].kinds()
+    }
+
+    /// The structural difference between the states at `phase_a` and
+    /// `phase_b`, so consumers don't have to diff the two [`Pcg`] snapshots
+    /// themselves.
+    pub fn delta(&self, phase_a: EvalStmtPhase, phase_b: EvalStmtPhase) -> PcgStateDelta<'tcx> {
+        let pcg_a = &self.states[phase_a];
+        let pcg_b = &self.states[phase_b];
+
+        let edges_a: FxHashSet<BorrowPcgEdge<'tcx>> = pcg_a
+            .borrow_pcg()
+            .graph()
+            .edges()
+            .map(|edge| edge.to_owned_edge())
+            .collect();
+        let edges_b: FxHashSet<BorrowPcgEdge<'tcx>> = pcg_b
+            .borrow_pcg()
+            .graph()
+            .edges()
+            .map(|edge| edge.to_owned_edge())
+            .collect();
+        let added_edges = edges_b.difference(&edges_a).cloned().collect();
+        let removed_edges = edges_a.difference(&edges_b).cloned().collect();
+
+        let caps_a: FxHashMap<Place<'tcx>, CapabilityKind> = pcg_a.capabilities().iter().collect();
+        let caps_b: FxHashMap<Place<'tcx>, CapabilityKind> = pcg_b.capabilities().iter().collect();
+        let changed_places: FxHashSet<Place<'tcx>> =
+            caps_a.keys().chain(caps_b.keys()).copied().collect();
+        let mut capability_changes: Vec<_> = changed_places
+            .into_iter()
+            .filter_map(|place| {
+                let from = caps_a.get(&place).copied();
+                let to = caps_b.get(&place).copied();
+                (from != to).then_some((place, from, to))
+            })
+            .collect();
+        capability_changes.sort_by_key(|(place, _, _)| *place);
+
+        PcgStateDelta {
+            added_edges,
+            removed_edges,
+            capability_changes,
+        }
+    }
+
     pub fn ancestor_edges<'slf, 'mir: 'slf, 'bc: 'slf>(
         &'slf self,
         place: Place<'tcx>,
@@ -327,6 +583,21 @@ impl<'tcx> PcgLocation<'tcx> {
         ancestors
     }
 
+    /// Returns the borrow PCG edges that currently block `place`, i.e. the
+    /// edges that would need to expire for `place` to regain full
+    /// capability, along with the path conditions under which each edge is
+    /// live (via [`crate::borrow_pcg::borrow_pcg_edge::BorrowPcgEdgeLike::conditions`]).
+    pub fn blockers_of<'slf, 'mir: 'slf>(
+        &'slf self,
+        place: Place<'tcx>,
+        ctxt: CompilerCtxt<'mir, 'tcx>,
+    ) -> Vec<BorrowPcgEdgeRef<'tcx, 'slf>> {
+        self.states[EvalStmtPhase::PostMain]
+            .borrow
+            .graph()
+            .edges_blocking_set(place.into(), ctxt)
+    }
+
     pub fn aliases<'mir>(
         &self,
         place: impl Into<Place<'tcx>>,
@@ -344,7 +615,9 @@ impl<'tcx> PcgLocation<'tcx> {
             .flat_map(|p| match p {
                 PCGNode::Place(p) => p.as_current_place(),
                 PCGNode::RegionProjection(p) => match p.base() {
-                    MaybeRemoteRegionProjectionBase::Place(p) => {
+                    MaybeRemoteRegionProjectionBase::Place(
+                        p @ (MaybeRemotePlace::Local(_) | MaybeRemotePlace::Remote(_)),
+                    ) => {
                         let assoc_place = p.related_local_place();
                         if assoc_place.is_ref(ctxt) {
                             Some(assoc_place.project_deref(ctxt))
@@ -359,10 +632,42 @@ impl<'tcx> PcgLocation<'tcx> {
             .collect()
     }
 
+    /// All borrow PCG edges present at `phase`, with their path conditions
+    /// and [`crate::borrow_pcg::edge::kind::BorrowPcgEdgeKind`] (`Borrow` for reborrows/initial borrows,
+    /// `BorrowPcgExpansion` for place expansions, `Abstraction` for
+    /// function-call/loop abstractions, `BorrowFlow` for region-projection
+    /// member edges), for consumers that need the raw graph rather than the
+    /// high-level [`PcgActions`] recorded while computing it.
+    pub fn borrow_pcg_edges<'slf>(
+        &'slf self,
+        phase: EvalStmtPhase,
+    ) -> impl Iterator<Item = BorrowPcgEdgeRef<'tcx, 'slf>> {
+        self.states[phase].borrow.graph().edges()
+    }
+
     pub fn latest(&self) -> &Latest<'tcx> {
         &self.states[EvalStmtPhase::PostMain].borrow.latest
     }
 
+    /// The location `place` was last made old at, if any, as of this
+    /// statement. Lets a consumer relate an old place it found elsewhere in
+    /// the graph back to the program point of its last modification,
+    /// without parsing the `at` field out of the visualization JSON by
+    /// hand. See [`Latest::snapshot_of`].
+    pub fn latest_snapshot_of(
+        &self,
+        place: Place<'tcx>,
+        ctxt: CompilerCtxt<'_, 'tcx>,
+    ) -> Option<SnapshotLocation> {
+        self.latest().snapshot_of(place, ctxt)
+    }
+
+    /// All places with a recorded snapshot as of this statement, along with
+    /// the location they were snapshotted at. See [`Latest::snapshotted_places`].
+    pub fn snapshotted_places(&self) -> impl Iterator<Item = (Place<'tcx>, SnapshotLocation)> + '_ {
+        self.latest().snapshotted_places()
+    }
+
     pub(crate) fn debug_lines(
         &self,
         phase: EvalStmtPhase,
@@ -380,3 +685,17 @@ impl<'tcx> PcgLocation<'tcx> {
 pub struct PcgTerminator<'tcx> {
     pub succs: Vec<PcgSuccessor<'tcx>>,
 }
+
+/// The structural difference between two [`Pcg`] states, as computed by
+/// [`PcgLocation::delta`].
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct PcgStateDelta<'tcx> {
+    /// Borrow PCG edges present in the second state but not the first.
+    pub added_edges: Vec<BorrowPcgEdge<'tcx>>,
+    /// Borrow PCG edges present in the first state but not the second.
+    pub removed_edges: Vec<BorrowPcgEdge<'tcx>>,
+    /// Places whose capability differs between the two states, as
+    /// `(place, from, to)`. `from`/`to` are `None` if the place has no
+    /// capability in the corresponding state.
+    pub capability_changes: Vec<(Place<'tcx>, Option<CapabilityKind>, Option<CapabilityKind>)>,
+}
@@ -6,6 +6,8 @@
 
 mod repacks;
 mod cursor;
+mod summary;
 
 pub use cursor::*;
 pub use repacks::*;
+pub use summary::*;
@@ -181,6 +181,34 @@ pub enum RepackOp<'tcx> {
     /// TODO: This to some extent overlaps with [`UnblockAction::TerminateBorrow`];
     /// if we merge the free and borrow PCG this should no longer be needed.
     RegainLoanedCapability(Place<'tcx>, CapabilityKind),
+    /// Emitted for a
+    /// [`mir::StatementKind::StorageLive`](https://doc.rust-lang.org/nightly/nightly-rustc/rustc_middle/mir/enum.StatementKind.html#variant.StorageLive)
+    /// statement, once the local's [`CapabilityLocal`](crate::free_pcs::CapabilityLocal) has
+    /// transitioned from `Unallocated` to `Allocated`. Consumers that model stack
+    /// (de)allocation can use this to emit an allocation obligation for `local`.
+    Allocate(Local),
+    /// Emitted for a
+    /// [`mir::StatementKind::StorageDead`](https://doc.rust-lang.org/nightly/nightly-rustc/rustc_middle/mir/enum.StatementKind.html#variant.StorageDead)
+    /// statement, once the local's [`CapabilityLocal`](crate::free_pcs::CapabilityLocal) has
+    /// transitioned from `Allocated` to `Unallocated`. Unlike [`RepackOp::StorageDead`], this is
+    /// emitted for every `StorageDead` statement (not just edges between basic blocks) and
+    /// marks the point at which `local`'s storage is actually freed.
+    Deallocate(Local),
+    /// Emitted for a
+    /// [`mir::Rvalue::Len`](https://doc.rust-lang.org/nightly/nightly-rustc/rustc_middle/mir/enum.Rvalue.html#variant.Len)
+    /// or
+    /// [`mir::Rvalue::Discriminant`](https://doc.rust-lang.org/nightly/nightly-rustc/rustc_middle/mir/enum.Rvalue.html#variant.Discriminant),
+    /// to make explicit that computing the rvalue reads `place`. Unlike the
+    /// operand of an ordinary read (e.g. a `Copy`), these don't have their
+    /// own [`mir::Operand`](https://doc.rust-lang.org/nightly/nightly-rustc/rustc_middle/mir/enum.Operand.html)
+    /// in the statement for an expansion/capability obligation to attach
+    /// to, so without this the read obtained for `place` in
+    /// `pcg::triple`'s triple for the rvalue would be invisible to
+    /// consumers whenever `place` already had at least `Read` capability
+    /// (the common case): verification backends need an explicit
+    /// obligation to justify the read even when no capability/expansion
+    /// change was needed to satisfy it.
+    RequireRead(Place<'tcx>),
 }
 
 impl<'tcx, BC: Copy> DisplayWithCompilerCtxt<'tcx, BC> for RepackOp<'tcx> {
@@ -226,12 +254,16 @@ impl<'tcx> RepackOp<'tcx> {
 
     pub fn affected_place(&self) -> Place<'tcx> {
         match *self {
-            RepackOp::StorageDead(local) | RepackOp::IgnoreStorageDead(local) => local.into(),
+            RepackOp::StorageDead(local)
+            | RepackOp::IgnoreStorageDead(local)
+            | RepackOp::Allocate(local)
+            | RepackOp::Deallocate(local) => local.into(),
             RepackOp::Weaken(place, _, _)
             | RepackOp::Collapse(RepackCollapse { to: place, .. })
             | RepackOp::Expand(RepackExpand { from: place, .. })
             | RepackOp::RegainLoanedCapability(place, _)
-            | RepackOp::DerefShallowInit(place, _) => place,
+            | RepackOp::DerefShallowInit(place, _)
+            | RepackOp::RequireRead(place) => place,
         }
     }
 }
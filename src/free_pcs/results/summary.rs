@@ -0,0 +1,105 @@
+// © 2023, ETH Zurich
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! A `'tcx`-free snapshot of a [`PcgAnalysis`]'s results, so they can be
+//! cached to disk (see [`AnalysisSummary::to_bincode`], behind the
+//! `binary-serialization` feature) and reloaded by a separate process
+//! instead of rerunning the analysis.
+//!
+//! Places, borrow edges, and actions are all encoded as their debug strings
+//! rather than kept as borrowed `Place<'tcx>`/`BorrowPcgEdgeRef<'tcx, '_>`
+//! values, since those can't outlive the `TyCtxt` that produced them. This
+//! loses the ability to re-query the summary against the compiler (e.g. to
+//! ask for a place's type), which is an intentional trade against the
+//! complexity of a `tcx`-agnostic place/edge encoding.
+
+use std::{alloc::Allocator, collections::BTreeMap};
+
+use serde_derive::{Deserialize, Serialize};
+
+use crate::{
+    pcg::EvalStmtPhase,
+    utils::display::DisplayWithCompilerCtxt,
+};
+
+use super::cursor::PcgAnalysis;
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LocationSummary {
+    /// Place (stable debug encoding) -> capability, at the end of the
+    /// statement (`EvalStmtPhase::PostMain`).
+    pub capabilities: BTreeMap<String, String>,
+    /// Borrow PCG edges live at the end of the statement.
+    pub borrow_edges: Vec<String>,
+    /// Actions applied during the statement, keyed by the phase they ran in.
+    pub actions: BTreeMap<String, Vec<String>>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AnalysisSummary {
+    /// MIR location (stable debug encoding) -> its summary.
+    pub locations: BTreeMap<String, LocationSummary>,
+}
+
+impl AnalysisSummary {
+    #[cfg(feature = "binary-serialization")]
+    pub fn to_bincode(&self) -> Result<Vec<u8>, bincode::Error> {
+        bincode::serialize(self)
+    }
+
+    #[cfg(feature = "binary-serialization")]
+    pub fn from_bincode(bytes: &[u8]) -> Result<Self, bincode::Error> {
+        bincode::deserialize(bytes)
+    }
+}
+
+impl<'mir, 'tcx, A: Allocator + Copy> PcgAnalysis<'mir, 'tcx, A> {
+    /// Extracts a summary of this analysis's results across every reachable
+    /// block, suitable for caching to disk.
+    pub fn summary(&mut self) -> AnalysisSummary {
+        let ctxt = self.ctxt();
+        let mut locations = BTreeMap::new();
+        for block in ctxt.body().basic_blocks.indices() {
+            let Ok(Some(pcg_block)) = self.get_all_for_bb(block) else {
+                continue;
+            };
+            for stmt in &pcg_block.statements {
+                let pcg = &stmt.states[EvalStmtPhase::PostMain];
+                let capabilities = pcg
+                    .capabilities()
+                    .iter()
+                    .map(|(place, cap)| (place.to_short_string(ctxt), format!("{cap:?}")))
+                    .collect();
+                let borrow_edges = pcg
+                    .borrow
+                    .graph()
+                    .edges()
+                    .map(|edge| format!("{edge:?}"))
+                    .collect();
+                let actions = EvalStmtPhase::phases()
+                    .into_iter()
+                    .map(|phase| {
+                        let debug_lines = stmt
+                            .actions(phase)
+                            .iter()
+                            .map(|action| action.debug_line(ctxt))
+                            .collect();
+                        (format!("{phase:?}"), debug_lines)
+                    })
+                    .collect();
+                locations.insert(
+                    format!("{:?}", stmt.location),
+                    LocationSummary {
+                        capabilities,
+                        borrow_edges,
+                        actions,
+                    },
+                );
+            }
+        }
+        AnalysisSummary { locations }
+    }
+}
@@ -18,16 +18,23 @@ pub mod borrow_pcg;
 pub mod coupling;
 pub mod free_pcs;
 pub mod r#loop;
+pub mod output;
 pub mod pcg;
 pub mod rustc_interface;
 pub mod utils;
 pub mod visualization;
 
-use action::PcgActions;
+use std::{cell::RefCell, rc::Rc};
+
+use action::{viper::encode_for_viper, PcgActions};
 use borrow_checker::BorrowCheckerInterface;
-use borrow_pcg::{graph::borrows_imgcat_debug, latest::Latest};
-use free_pcs::{CapabilityKind, PcgLocation};
-use pcg::{EvalStmtPhase, PcgEngine, PcgSuccessor};
+use borrow_pcg::{
+    graph::{borrows_imgcat_debug, borrows_imgcat_debug_for},
+    latest::Latest,
+};
+use crate::pcg_validity_assert;
+use free_pcs::{CapabilityKind, CapabilityLattice, PcgLocation};
+use pcg::{query, EvalStmtPhase, FunctionSummaryRegistry, PcgEngine, PcgObserver, PcgSuccessor};
 use rustc_interface::{
     borrowck::{self, BorrowSet, LocationTable, PoloniusInput, RegionInferenceContext},
     dataflow::{compute_fixpoint, AnalysisEngine},
@@ -37,7 +44,7 @@ use serde_json::json;
 use utils::{
     display::{DebugLines, DisplayWithCompilerCtxt},
     validity::HasValidityCheck,
-    CompilerCtxt, Place, VALIDITY_CHECKS, VALIDITY_CHECKS_WARN_ONLY,
+    CompilerCtxt, Place, ValidityConfig, HTML_REPORT, VALIDITY_CHECKS, VALIDITY_CHECKS_WARN_ONLY,
 };
 use visualization::mir_graph::generate_json_from_mir;
 
@@ -73,15 +80,14 @@ impl<'tcx> Weaken<'tcx> {
         from: CapabilityKind,
         to: Option<CapabilityKind>,
     ) -> Self {
-        // TODO
-        // if let Some(to) = to {
-        //     pcg_validity_assert!(
-        //         from > to,
-        //         "FROM capability ({:?}) is not greater than TO capability ({:?})",
-        //         from,
-        //         to
-        //     );
-        // }
+        if let Some(to) = to {
+            pcg_validity_assert!(
+                to.leq(from) && to != from,
+                "FROM capability ({:?}) is not greater than TO capability ({:?})",
+                from,
+                to
+            );
+        }
         Self { place, from, to }
     }
 
@@ -156,24 +162,17 @@ impl<'tcx> DebugLines<CompilerCtxt<'_, 'tcx>> for BorrowPcgActions<'tcx> {
 }
 
 use borrow_pcg::action::actions::BorrowPcgActions;
-use std::{alloc::Allocator, sync::Mutex};
+use std::alloc::Allocator;
 use utils::eval_stmt_data::EvalStmtData;
 
-lazy_static::lazy_static! {
-    /// Whether to record PCG information for each block. This is used for
-    /// debugging only. This is set to true when the PCG is initially
-    /// constructed, and then disabled after its construction. The reason for
-    /// using a global variable is that debugging information is written during
-    /// the dataflow operations of the PCG, which are also used when examining
-    /// PCG results. We don't want to write the debugging information to disk
-    /// during examination, of course.
-    static ref RECORD_PCG: Mutex<bool> = Mutex::new(false);
-}
-
 struct PCGStmtVisualizationData<'a, 'tcx> {
     /// The value of the "latest" map at the end of the statement.
     latest: &'a Latest<'tcx>,
     actions: &'a EvalStmtData<PcgActions<'tcx>>,
+    /// The statement's source span, so viewers of the visualization JSON can
+    /// highlight the originating source code without re-deriving it from the
+    /// MIR location.
+    span: String,
 }
 
 struct PcgSuccessorVisualizationData<'a, 'tcx> {
@@ -201,18 +200,20 @@ impl<'tcx, 'a> ToJsonWithCompilerCtxt<'tcx, &'a dyn BorrowCheckerInterface<'tcx>
         json!({
             "latest": self.latest.to_json(repacker),
             "actions": self.actions.to_json(repacker),
+            "span": self.span,
         })
     }
 }
 
 impl<'a, 'tcx> PCGStmtVisualizationData<'a, 'tcx> {
-    fn new<'mir>(location: &'a PcgLocation<'tcx>) -> Self
+    fn new<'mir>(location: &'a PcgLocation<'tcx>, ctxt: CompilerCtxt<'_, 'tcx>) -> Self
     where
         'tcx: 'mir,
     {
         Self {
             latest: &location.states[EvalStmtPhase::PostMain].borrow.latest,
             actions: &location.actions,
+            span: format!("{:?}", ctxt.body().source_info(location.location).span),
         }
     }
 }
@@ -222,7 +223,9 @@ pub trait BodyAndBorrows<'tcx> {
     fn borrow_set(&self) -> &BorrowSet<'tcx>;
     fn region_inference_context(&self) -> &RegionInferenceContext<'tcx>;
     fn location_table(&self) -> &LocationTable;
-    fn input_facts(&self) -> &PoloniusInput;
+    /// `None` when the body was analyzed in NLL-only mode, i.e. without
+    /// Polonius input facts.
+    fn input_facts(&self) -> Option<&PoloniusInput>;
 }
 
 impl<'tcx> BodyAndBorrows<'tcx> for borrowck::BodyWithBorrowckFacts<'tcx> {
@@ -240,11 +243,292 @@ impl<'tcx> BodyAndBorrows<'tcx> for borrowck::BodyWithBorrowckFacts<'tcx> {
         self.location_table.as_ref().unwrap()
     }
 
-    fn input_facts(&self) -> &PoloniusInput {
-        self.input_facts.as_ref().unwrap()
+    fn input_facts(&self) -> Option<&PoloniusInput> {
+        self.input_facts.as_ref()
+    }
+}
+
+/// When a borrow's leaf edge in the borrow PCG becomes expirable (it is old
+/// or the borrow checker reports it dead), [`crate::pcg::visitor::PcgVisitor`]
+/// currently always expires it eagerly, at the next opportunity (see
+/// `pack_old_and_dead_borrow_leaves`). `AtLivenessEnd` and `AtStorageDead`
+/// name two alternative timings (defer expiry until the borrow checker's
+/// liveness range actually ends, or until the backing local's `StorageDead`,
+/// respectively) that some consumers may prefer for visualization or
+/// diagnostic purposes.
+///
+/// Only [`BorrowExpiryPolicy::Eager`] is actually implemented: the other two
+/// variants are accepted and recorded in [`crate::pcg::stats::PcgStats`] so
+/// that the chosen policy is visible in output metadata, but the analysis
+/// expires borrows eagerly regardless of which variant is selected. Making
+/// `AtLivenessEnd`/`AtStorageDead` change the analysis's actual behavior
+/// would mean moving the `pack_old_and_dead_borrow_leaves` call out of
+/// `perform_borrow_initial_pre_operand_actions` and into new trigger points
+/// threaded through the dataflow engine -- a change to the fixpoint itself
+/// that needs a compiler and test suite to validate, neither of which is
+/// available here.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, serde_derive::Serialize)]
+pub enum BorrowExpiryPolicy {
+    /// Expire a borrow as soon as it is old or dead. This is the only
+    /// variant that is actually implemented.
+    #[default]
+    Eager,
+    /// Not yet implemented; behaves like [`Self::Eager`]. See the enum-level
+    /// doc comment.
+    AtLivenessEnd,
+    /// Not yet implemented; behaves like [`Self::Eager`]. See the enum-level
+    /// doc comment.
+    AtStorageDead,
+}
+
+/// Per-invocation configuration for [`run_pcg_with_options`]. Downstream
+/// consumers (e.g. Prusti) that want to configure the analysis
+/// programmatically, rather than via the process-global environment
+/// variables read by [`crate::utils`], should build one of these with
+/// [`PcgOptions::builder`].
+#[derive(Clone, Default)]
+pub struct PcgOptions<'a, 'tcx> {
+    visualization_output_path: Option<String>,
+    validity_checks: Option<bool>,
+    validity_checks_warn_only: Option<bool>,
+    cross_validation: Option<bool>,
+    ignore_unwind_paths: Option<bool>,
+    promoted_bodies: Option<bool>,
+    inline_trivial_getters: Option<bool>,
+    borrow_expiry_policy: Option<BorrowExpiryPolicy>,
+    observer: Option<Rc<RefCell<dyn PcgObserver<'tcx> + 'a>>>,
+    function_summaries: Option<Rc<FunctionSummaryRegistry>>,
+    visualization_style: Option<visualization::legend::VisualizationStyle>,
+}
+
+impl std::fmt::Debug for PcgOptions<'_, '_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PcgOptions")
+            .field("visualization_output_path", &self.visualization_output_path)
+            .field("validity_checks", &self.validity_checks)
+            .field("validity_checks_warn_only", &self.validity_checks_warn_only)
+            .field("cross_validation", &self.cross_validation)
+            .field("ignore_unwind_paths", &self.ignore_unwind_paths)
+            .field("promoted_bodies", &self.promoted_bodies)
+            .field("inline_trivial_getters", &self.inline_trivial_getters)
+            .field("borrow_expiry_policy", &self.borrow_expiry_policy)
+            .field("observer", &self.observer.is_some())
+            .field("function_summaries", &self.function_summaries.is_some())
+            .field("visualization_style", &self.visualization_style)
+            .finish()
     }
 }
 
+impl<'a, 'tcx> PcgOptions<'a, 'tcx> {
+    pub fn builder() -> PcgOptionsBuilder<'a, 'tcx> {
+        PcgOptionsBuilder::default()
+    }
+
+    pub fn visualization_output_path(&self) -> Option<&str> {
+        self.visualization_output_path.as_deref()
+    }
+
+    fn validity_checks(&self) -> bool {
+        self.validity_checks.unwrap_or_else(validity_checks_enabled)
+    }
+
+    pub fn validity_checks_warn_only(&self) -> bool {
+        self.validity_checks_warn_only
+            .unwrap_or_else(validity_checks_warn_only)
+    }
+
+    fn cross_validation(&self) -> bool {
+        self.cross_validation.unwrap_or(*utils::CROSS_VALIDATION)
+    }
+
+    /// Whether terminator successors that are only reachable by unwinding
+    /// (MIR cleanup blocks) should be omitted from
+    /// [`crate::free_pcs::PcgTerminator::succs`]. If unset, falls back to
+    /// `PCG_IGNORE_UNWIND_PATHS`.
+    pub fn ignore_unwind_paths(&self) -> bool {
+        self.ignore_unwind_paths
+            .unwrap_or(*utils::IGNORE_UNWIND_PATHS)
+    }
+
+    /// Whether promoted MIR bodies should be pulled in from `tcx` on demand
+    /// and surfaced in visualization output. If unset, falls back to
+    /// `PCG_PROMOTED_BODIES`.
+    pub fn promoted_bodies(&self) -> bool {
+        self.promoted_bodies.unwrap_or(*utils::PROMOTED_BODIES)
+    }
+
+    /// Whether calls to detected "trivial getter" callees (see
+    /// [`crate::utils::mir_inline::is_trivial_getter`]) are logged via
+    /// `tracing::debug!` as candidates for a coarse
+    /// [`FunctionCallAbstraction`](crate::borrow_pcg::edge::abstraction::function::FunctionCallAbstraction)
+    /// that a real MIR-inlining preprocessing pass could have avoided
+    /// entirely. If unset, falls back to `PCG_INLINE_TRIVIAL_GETTERS`.
+    ///
+    /// This only adds a diagnostic: actually inlining such a callee before
+    /// analysis would mean handing [`run_pcg_with_options`] an owned,
+    /// rewritten [`Body`] rather than the borrowed one it takes today (to
+    /// renumber the callee's locals/blocks past the caller's, substitute
+    /// its generics, and guard against inlining it into itself through
+    /// recursion), which is out of scope for this option.
+    pub fn inline_trivial_getters(&self) -> bool {
+        self.inline_trivial_getters
+            .unwrap_or(*utils::INLINE_TRIVIAL_GETTERS)
+    }
+
+    /// The borrow-expiry timing policy recorded for this run. If unset,
+    /// defaults to [`BorrowExpiryPolicy::Eager`]. See
+    /// [`BorrowExpiryPolicy`]'s doc comment for what is and isn't actually
+    /// implemented.
+    pub fn borrow_expiry_policy(&self) -> BorrowExpiryPolicy {
+        self.borrow_expiry_policy.unwrap_or_default()
+    }
+
+    fn observer(&self) -> Option<Rc<RefCell<dyn PcgObserver<'tcx> + 'a>>> {
+        self.observer.clone()
+    }
+
+    fn function_summaries(&self) -> Option<Rc<FunctionSummaryRegistry>> {
+        self.function_summaries.clone()
+    }
+
+    /// The style used to render [`visualization::legend`]'s standalone
+    /// legend graphs. If unset, falls back to
+    /// [`VisualizationStyle::default`](visualization::legend::VisualizationStyle::default).
+    fn visualization_style(&self) -> visualization::legend::VisualizationStyle {
+        self.visualization_style.clone().unwrap_or_default()
+    }
+}
+
+/// Builder for [`PcgOptions`]. Unset fields fall back to the corresponding
+/// `PCG_*` environment variable, matching the behavior before this builder
+/// existed.
+#[derive(Clone, Default)]
+pub struct PcgOptionsBuilder<'a, 'tcx> {
+    options: PcgOptions<'a, 'tcx>,
+}
+
+impl std::fmt::Debug for PcgOptionsBuilder<'_, '_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PcgOptionsBuilder")
+            .field("options", &self.options)
+            .finish()
+    }
+}
+
+impl<'a, 'tcx> PcgOptionsBuilder<'a, 'tcx> {
+    /// Directory to which per-block/per-statement visualization JSON and DOT
+    /// files should be written. If unset, no visualization output is
+    /// produced.
+    pub fn visualization_output_path(mut self, path: impl Into<String>) -> Self {
+        self.options.visualization_output_path = Some(path.into());
+        self
+    }
+
+    /// Whether to run the (potentially expensive) internal validity checks
+    /// after each statement. If unset, falls back to `PCG_VALIDITY_CHECKS`.
+    pub fn validity_checks(mut self, enabled: bool) -> Self {
+        self.options.validity_checks = Some(enabled);
+        self
+    }
+
+    /// Whether validity check failures should be logged as warnings instead
+    /// of causing a panic. If unset, falls back to
+    /// `PCG_VALIDITY_CHECKS_WARN_ONLY`.
+    pub fn validity_checks_warn_only(mut self, enabled: bool) -> Self {
+        self.options.validity_checks_warn_only = Some(enabled);
+        self
+    }
+
+    /// Whether to cross-validate PCG's liveness facts against
+    /// [`crate::borrow_checker::BorrowCheckerInterface`] after each
+    /// statement (see [`crate::pcg::cross_validation`]) and report
+    /// divergences via `tracing::warn!`. If unset, falls back to
+    /// `PCG_CROSS_VALIDATION`.
+    pub fn cross_validation(mut self, enabled: bool) -> Self {
+        self.options.cross_validation = Some(enabled);
+        self
+    }
+
+    /// Whether to omit terminator successors that are only reachable by
+    /// unwinding (MIR cleanup blocks) from
+    /// [`crate::free_pcs::PcgTerminator::succs`]. Cleanup blocks are still
+    /// analyzed internally (so e.g. destructors are still accounted for);
+    /// this only affects which successors are surfaced to callers. If
+    /// unset, falls back to `PCG_IGNORE_UNWIND_PATHS`.
+    pub fn ignore_unwind_paths(mut self, enabled: bool) -> Self {
+        self.options.ignore_unwind_paths = Some(enabled);
+        self
+    }
+
+    /// Whether to pull in promoted MIR bodies (e.g. the body backing a
+    /// promoted temporary like the one behind `&[1, 2, 3]`) from `tcx` on
+    /// demand, so that borrows of them can be resolved and surfaced in
+    /// visualization output instead of showing an opaque constant. If
+    /// unset, falls back to `PCG_PROMOTED_BODIES`.
+    pub fn promoted_bodies(mut self, enabled: bool) -> Self {
+        self.options.promoted_bodies = Some(enabled);
+        self
+    }
+
+    /// Whether to log calls to detected trivial-getter callees as
+    /// candidates for the coarse function-call abstraction they are about
+    /// to be modeled with. If unset, falls back to
+    /// `PCG_INLINE_TRIVIAL_GETTERS`. See
+    /// [`PcgOptions::inline_trivial_getters`] for what this does and
+    /// doesn't do.
+    pub fn inline_trivial_getters(mut self, enabled: bool) -> Self {
+        self.options.inline_trivial_getters = Some(enabled);
+        self
+    }
+
+    /// Records which borrow-expiry timing policy this run is using in
+    /// output metadata (see [`crate::pcg::stats::PcgStats::borrow_expiry_policy`]).
+    /// If unset, defaults to [`BorrowExpiryPolicy::Eager`]. See
+    /// [`BorrowExpiryPolicy`]'s doc comment: only `Eager` actually changes
+    /// analysis behavior, the other variants are recorded but not yet acted
+    /// on.
+    pub fn borrow_expiry_policy(mut self, policy: BorrowExpiryPolicy) -> Self {
+        self.options.borrow_expiry_policy = Some(policy);
+        self
+    }
+
+    /// Registers a [`PcgObserver`] to be notified of every action
+    /// (`Weaken`, `RestoreCapability`, reborrow add/remove, expansion, ...)
+    /// the analysis applies, in order.
+    pub fn observer(mut self, observer: Rc<RefCell<dyn PcgObserver<'tcx> + 'a>>) -> Self {
+        self.options.observer = Some(observer);
+        self
+    }
+
+    /// Registers hand-written [`FunctionSummary`](pcg::FunctionSummary)s that
+    /// override the signature-derived defaults `pcg::visitor::function_call`
+    /// would otherwise compute for the functions they cover.
+    pub fn function_summaries(mut self, function_summaries: Rc<FunctionSummaryRegistry>) -> Self {
+        self.options.function_summaries = Some(function_summaries);
+        self
+    }
+
+    /// Sets the style used to render the standalone `edge_legend.dot`/
+    /// `node_legend.dot` graphs (colors, shapes, and which edge kinds
+    /// appear), so downstream tools can brand or simplify them. If unset,
+    /// defaults to [`VisualizationStyle::default`](visualization::legend::VisualizationStyle::default).
+    pub fn visualization_style(
+        mut self,
+        style: visualization::legend::VisualizationStyle,
+    ) -> Self {
+        self.options.visualization_style = Some(style);
+        self
+    }
+
+    pub fn build(self) -> PcgOptions<'a, 'tcx> {
+        self.options
+    }
+}
+
+/// Runs the PCG analysis for `body`, configuring the analysis via the fixed
+/// positional arguments used before [`PcgOptions`] was introduced. This is
+/// kept for existing callers; new code should prefer
+/// [`run_pcg_with_options`].
 pub fn run_pcg<
     'a,
     'tcx: 'a,
@@ -257,18 +541,51 @@ pub fn run_pcg<
     arena: A,
     visualization_output_path: Option<&str>,
 ) -> PcgOutput<'a, 'tcx, A> {
-    let ctxt: CompilerCtxt<'a, 'tcx> = CompilerCtxt::new(body, tcx, bc.as_dyn());
-    let engine = PcgEngine::new(ctxt, arena, visualization_output_path);
-    {
-        let mut record_pcg = RECORD_PCG.lock().unwrap();
-        *record_pcg = true;
+    let mut builder = PcgOptions::builder();
+    if let Some(path) = visualization_output_path {
+        builder = builder.visualization_output_path(path);
     }
+    run_pcg_with_options(body, tcx, bc, arena, builder.build())
+}
+
+/// Runs the PCG analysis for `body` as configured by `options`.
+pub fn run_pcg_with_options<
+    'a,
+    'tcx: 'a,
+    A: Allocator + Copy + std::fmt::Debug,
+    BC: BorrowCheckerInterface<'tcx> + ?Sized,
+>(
+    body: &'a Body<'tcx>,
+    tcx: TyCtxt<'tcx>,
+    bc: &'a BC,
+    arena: A,
+    options: PcgOptions<'a, 'tcx>,
+) -> PcgOutput<'a, 'tcx, A> {
+    let visualization_output_path = options.visualization_output_path();
+    let observer = options.observer();
+    let function_summaries = options.function_summaries();
+    let ctxt: CompilerCtxt<'a, 'tcx> = CompilerCtxt::new(body, tcx, bc.as_dyn())
+        .with_validity_config(
+            ValidityConfig::all(options.validity_checks())
+                .warn_only(options.validity_checks_warn_only()),
+        )
+        .with_promoted_bodies(options.promoted_bodies())
+        .with_inline_trivial_getters(options.inline_trivial_getters());
+    let engine = PcgEngine::new(
+        ctxt,
+        arena,
+        visualization_output_path,
+        observer,
+        function_summaries,
+    );
+    let recording = engine.recording_flag();
+    let timings = engine.timings_handle();
+    recording.set(true);
     let analysis = compute_fixpoint(AnalysisEngine(engine), tcx, body);
-    {
-        let mut record_pcg = RECORD_PCG.lock().unwrap();
-        *record_pcg = false;
-    }
+    recording.set(false);
     if let Some(dir_path) = &visualization_output_path {
+        let _span = tracing::info_span!("visualization_io").entered();
+        let start = std::time::Instant::now();
         for block in body.basic_blocks.indices() {
             let state = analysis.entry_set_for_block(block);
             assert!(state.block() == block);
@@ -280,22 +597,57 @@ pub fn run_pcg<
                 .borrow()
                 .write_json_file(&block_iterations_json_file);
         }
+        timings.borrow_mut().record_visualization_io(start.elapsed());
+    }
+    let mut fpcs_analysis = free_pcs::PcgAnalysis::new(
+        analysis.into_results_cursor(body),
+        options.ignore_unwind_paths(),
+    );
+
+    // With visualization output enabled, cross-validation runs in the
+    // per-statement loop below instead.
+    if options.cross_validation() && visualization_output_path.is_none() {
+        for block in body.basic_blocks.indices() {
+            let Ok(Some(pcs_block)) = fpcs_analysis.get_all_for_bb(block) else {
+                continue;
+            };
+            for statement in pcs_block.statements.iter() {
+                let pcg_at_stmt = &statement.states[EvalStmtPhase::PostMain];
+                for divergence in pcg::cross_validation::check(pcg_at_stmt, statement.location, ctxt) {
+                    tracing::warn!("{divergence}");
+                }
+            }
+        }
     }
-    let mut fpcs_analysis = free_pcs::PcgAnalysis::new(analysis.into_results_cursor(body));
 
     if let Some(dir_path) = visualization_output_path {
+        let _span = tracing::info_span!("visualization_io").entered();
+        let start = std::time::Instant::now();
+        let visualization_style = options.visualization_style();
         let edge_legend_file_path = format!("{dir_path}/edge_legend.dot");
-        let edge_legend_graph = crate::visualization::legend::generate_edge_legend().unwrap();
+        let edge_legend_graph =
+            crate::visualization::legend::generate_edge_legend(&visualization_style).unwrap();
         std::fs::write(&edge_legend_file_path, edge_legend_graph)
             .expect("Failed to write edge legend");
 
         let node_legend_file_path = format!("{dir_path}/node_legend.dot");
-        let node_legend_graph = crate::visualization::legend::generate_node_legend().unwrap();
+        let node_legend_graph =
+            crate::visualization::legend::generate_node_legend(&visualization_style).unwrap();
         std::fs::write(&node_legend_file_path, node_legend_graph)
             .expect("Failed to write node legend");
         generate_json_from_mir(&format!("{dir_path}/mir.json"), ctxt)
             .expect("Failed to generate JSON from MIR");
 
+        if *HTML_REPORT {
+            crate::visualization::html::write_html_report(dir_path, body.basic_blocks.len())
+                .expect("Failed to write HTML report");
+        }
+
+        // Every `PcgAction` applied across the function, in program order,
+        // for tool authors that want the full action history without
+        // stitching together the per-statement `*_pcg_data.json` files.
+        let mut timeline_entries: Vec<serde_json::Value> = Vec::new();
+
         // Iterate over each statement in the MIR
         for (block, _data) in body.basic_blocks.iter_enumerated() {
             let pcs_block_option = if let Ok(opt) = fpcs_analysis.get_all_for_bb(block) {
@@ -308,19 +660,69 @@ pub fn run_pcg<
             }
             let pcs_block = pcs_block_option.unwrap();
             for (statement_index, statement) in pcs_block.statements.iter().enumerate() {
-                if validity_checks_enabled() {
+                if options.validity_checks() {
                     statement.assert_validity(ctxt);
                 }
-                let data = PCGStmtVisualizationData::new(statement);
+                if options.cross_validation() {
+                    for divergence in pcg::cross_validation::check(
+                        &statement.states[EvalStmtPhase::PostMain],
+                        statement.location,
+                        ctxt,
+                    ) {
+                        tracing::warn!("{divergence}");
+                    }
+                }
+                let data = PCGStmtVisualizationData::new(statement, ctxt);
                 let pcg_data_file_path = format!(
                     "{}/block_{}_stmt_{}_pcg_data.json",
                     &dir_path,
                     block.index(),
                     statement_index
                 );
-                let pcg_data_json = data.to_json(ctxt);
+                let pcg_data_json = output::versioned(data.to_json(ctxt));
                 std::fs::write(&pcg_data_file_path, pcg_data_json.to_string())
                     .expect("Failed to write pcg data to JSON file");
+                let viper_data_file_path = format!(
+                    "{}/block_{}_stmt_{}_viper.json",
+                    &dir_path,
+                    block.index(),
+                    statement_index
+                );
+                let viper_json = output::versioned(encode_for_viper(data.actions).to_json(ctxt));
+                std::fs::write(&viper_data_file_path, viper_json.to_string())
+                    .expect("Failed to write viper data to JSON file");
+                let queries_file_path = format!(
+                    "{}/block_{}_stmt_{}_queries.json",
+                    &dir_path,
+                    block.index(),
+                    statement_index
+                );
+                let pcg_at_stmt = &statement.states[EvalStmtPhase::PostMain];
+                let queries_json = output::versioned(
+                    pcg_at_stmt
+                        .capabilities()
+                        .iter()
+                        .map(|(place, capability)| {
+                            json!({
+                                "place": place.to_short_string(ctxt),
+                                "capability": format!("{capability:?}"),
+                                "blocked_by": query::blocking_edges(pcg_at_stmt, place, ctxt),
+                            })
+                        })
+                        .collect::<Vec<_>>(),
+                );
+                std::fs::write(&queries_file_path, queries_json.to_string())
+                    .expect("Failed to write queries data to JSON file");
+                for (phase, actions) in data.actions.iter() {
+                    for action in actions.iter() {
+                        timeline_entries.push(json!({
+                            "block": block.index(),
+                            "statement_index": statement_index,
+                            "phase": phase,
+                            "action": action.to_json(ctxt),
+                        }));
+                    }
+                }
             }
             for succ in pcs_block.terminator.succs {
                 let data = PcgSuccessorVisualizationData::from(&succ);
@@ -330,11 +732,28 @@ pub fn run_pcg<
                     block.index(),
                     succ.block().index()
                 );
-                let pcg_data_json = data.to_json(ctxt);
+                let pcg_data_json = output::versioned(data.to_json(ctxt));
                 std::fs::write(&pcg_data_file_path, pcg_data_json.to_string())
                     .expect("Failed to write pcg data to JSON file");
             }
         }
+
+        let timeline_file_path = format!("{dir_path}/timeline.json");
+        std::fs::write(
+            &timeline_file_path,
+            output::versioned(serde_json::Value::Array(timeline_entries)).to_string(),
+        )
+        .expect("Failed to write timeline to JSON file");
+
+        let mut stats = fpcs_analysis.stats();
+        stats.borrow_expiry_policy = options.borrow_expiry_policy();
+        stats.write_json_file(dir_path);
+
+        let diagnostics = fpcs_analysis.diagnostics();
+        diagnostics.write_json_file(dir_path);
+        diagnostics.write_sarif_file(dir_path);
+
+        timings.borrow_mut().record_visualization_io(start.elapsed());
     }
 
     fpcs_analysis
@@ -374,6 +793,46 @@ macro_rules! pcg_validity_assert {
     };
 }
 
+/// Like [`pcg_validity_assert!`], but consults the [`crate::utils::ValidityConfig`]
+/// carried by `$ctxt` (a [`crate::utils::CompilerCtxt`]) for `$category`,
+/// rather than the process-global `PCG_VALIDITY_CHECKS` environment
+/// variable. This lets embedders enable expensive checks only for selected
+/// functions, by passing a context built with
+/// [`crate::utils::CompilerCtxt::with_validity_config`].
+#[macro_export]
+macro_rules! pcg_category_validity_assert {
+    ($category:expr, $ctxt:expr, $cond:expr) => {
+        if $ctxt.validity_config().is_enabled($category) {
+            if $ctxt.validity_config().is_warn_only() {
+                #[allow(clippy::neg_cmp_op_on_partial_ord)]
+                if !$cond {
+                    tracing::error!("assertion failed: {}", stringify!($cond));
+                }
+            } else {
+                if !$cond {
+                    tracing::error!("assertion failed: {}", stringify!($cond));
+                }
+                assert!($cond);
+            }
+        }
+    };
+    ($category:expr, $ctxt:expr, $cond:expr, $($arg:tt)*) => {
+        if $ctxt.validity_config().is_enabled($category) {
+            if $ctxt.validity_config().is_warn_only() {
+                #[allow(clippy::neg_cmp_op_on_partial_ord)]
+                if !$cond {
+                    tracing::error!($($arg)*);
+                }
+            } else {
+                if !$cond {
+                    tracing::error!($($arg)*);
+                }
+                assert!($cond, $($arg)*);
+            }
+        }
+    };
+}
+
 #[macro_export]
 macro_rules! pcg_validity_warn {
     ($cond:expr, $($arg:tt)*) => {
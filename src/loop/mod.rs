@@ -6,6 +6,7 @@
 
 use crate::{
     rustc_interface::{
+        data_structures::fx::FxHashSet,
         index::{Idx, IndexVec},
         middle::mir::{BasicBlock, Body, START_BLOCK},
     },
@@ -141,6 +142,33 @@ impl LoopAnalysis {
         self.loops(bb).find(|l| self[*l] == bb)
     }
 
+    /// All blocks belonging to loop `l`, including its head and any nested
+    /// loops. Unlike walking backwards from a single back edge's source,
+    /// this reflects every back edge into `l`'s head at once, since
+    /// [`Self::find_loops`] merges all of them into the same [`LoopSet`]
+    /// entry during its single reverse-postorder sweep -- so a loop with
+    /// more than one back edge (e.g. a `loop` with two `continue` sites)
+    /// still gets one consistent block set here.
+    pub fn blocks(&self, l: LoopId) -> impl Iterator<Item = BasicBlock> + '_ {
+        self.bb_data
+            .indices()
+            .filter(move |&bb| self.in_loop(bb, l))
+    }
+
+    /// Blocks outside loop `l` that some block inside it has an edge to
+    /// (the targets of `break`-like edges). Together with [`Self::blocks`]
+    /// this is the "loop forest" query surface (headers, nesting,
+    /// membership, exits); [`crate::borrow_pcg::graph::mutate`]'s
+    /// loop-continuation filtering uses its [`Self::loop_head_of`] and
+    /// [`Self::blocks`] queries instead of re-deriving loop membership
+    /// itself from a single back edge.
+    pub fn exits(&self, l: LoopId, body: &Body) -> FxHashSet<BasicBlock> {
+        self.blocks(l)
+            .flat_map(|bb| body.basic_blocks[bb].terminator().successors())
+            .filter(|succ| !self.in_loop(*succ, l))
+            .collect()
+    }
+
     fn consistency_check(&self) {
         // Start block can be in a maximum of one loop, of which it is the head
         let mut start_loops: Vec<_> = self.loops(START_BLOCK).collect();
@@ -1,3 +1,11 @@
+//! Standalone `rustc_driver` binary (`pcg_bin`): wraps `rustc` with
+//! [`PcgCallbacks`], so running this binary on a source file or crate is
+//! enough to run PCG on every function and write its visualization/JSON
+//! output, without writing a custom driver against `BodyAndBorrows`
+//! yourself. See the "Running" section of the README for usage and the
+//! env vars (`PCG_VISUALIZATION`, `PCG_VISUALIZATION_DATA_DIR`,
+//! `PCG_POLONIUS`, ...) that control its output.
+
 #![feature(rustc_private)]
 #![feature(let_chains)]
 #![feature(stmt_expr_attributes)]
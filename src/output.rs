@@ -0,0 +1,32 @@
+// © 2023, ETH Zurich
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! A single place to track the shape of the JSON artifacts `run_pcg` writes
+//! to its visualization output directory (`*_pcg_data.json`, `timeline.json`,
+//! ...), so consumers can tell which version of the format they're reading
+//! instead of discovering a breaking change by failing to parse a field.
+//!
+//! This deliberately does not attempt to replace every ad hoc
+//! `ToJsonWithCompilerCtxt` impl in the crate (e.g. the ones nested inside
+//! `block_N_iterations.json` or `mir.json`) with a single shared model — that
+//! would mean threading a new output type through most of `borrow_pcg` and
+//! `free_pcs` for no benefit to the data itself. Instead, every *top-level*
+//! JSON file `run_pcg` writes is wrapped with [`versioned`], which is the
+//! actual point of breakage consumers care about.
+
+/// Bump this whenever a top-level JSON artifact's shape changes in a way
+/// that isn't purely additive.
+pub const SCHEMA_VERSION: u32 = 1;
+
+/// Wraps `data` (the existing, unversioned JSON produced for a given
+/// artifact) with a `schema_version` field, so a consumer can check it
+/// before relying on the shape of `data`.
+pub(crate) fn versioned(data: serde_json::Value) -> serde_json::Value {
+    serde_json::json!({
+        "schema_version": SCHEMA_VERSION,
+        "data": data,
+    })
+}
@@ -0,0 +1,69 @@
+// © 2023, ETH Zurich
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Cross-validates PCG's view of liveness against
+//! [`BorrowCheckerInterface`], to catch soundness regressions: if a node
+//! is still blocking something in the borrow PCG (i.e. PCG thinks it's
+//! live), but the borrow checker already considers it dead at this
+//! point, PCG has failed to expire a borrow it should have. The converse
+//! isn't checked: PCG is allowed to be more conservative than the borrow
+//! checker (e.g. holding a borrow live slightly longer than strictly
+//! necessary) without that being a soundness problem, only the direction
+//! above is.
+//!
+//! Enabled via `PCG_CROSS_VALIDATION`, or [`PcgOptionsBuilder::cross_validation`](crate::PcgOptionsBuilder::cross_validation).
+
+use crate::{
+    borrow_checker::BorrowCheckerInterface,
+    borrow_pcg::edge_data::EdgeData,
+    pcg::Pcg,
+    rustc_interface::middle::mir::Location,
+    utils::{display::DisplayWithCompilerCtxt, CompilerCtxt},
+};
+
+/// A single point where PCG's and the borrow checker's view of liveness
+/// disagree.
+#[derive(Clone, Debug)]
+pub struct Divergence {
+    pub location: Location,
+    pub node: String,
+}
+
+impl std::fmt::Display for Divergence {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "at {:?}: PCG considers {} live, but the borrow checker considers it dead",
+            self.location, self.node
+        )
+    }
+}
+
+/// Compares, for every node currently blocking something in `pcg`'s
+/// borrow graph, whether the borrow checker agrees it's live at
+/// `location`.
+pub fn check<'tcx>(
+    pcg: &Pcg<'tcx>,
+    location: Location,
+    ctxt: CompilerCtxt<'_, 'tcx>,
+) -> Vec<Divergence> {
+    pcg.borrow
+        .graph()
+        .edges()
+        .flat_map(|edge| edge.blocked_by_nodes(ctxt))
+        .filter_map(|node| {
+            let node = node.into();
+            if ctxt.bc().is_dead(node, location, false) {
+                Some(Divergence {
+                    location,
+                    node: node.to_short_string(ctxt),
+                })
+            } else {
+                None
+            }
+        })
+        .collect()
+}
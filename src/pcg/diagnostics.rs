@@ -0,0 +1,148 @@
+// © 2023, ETH Zurich
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use serde_derive::Serialize;
+
+use crate::{
+    rustc_interface::middle::mir::Location,
+    utils::CompilerCtxt,
+};
+
+/// The category of an imprecision [`PcgDiagnostics`] can record.
+///
+/// This isn't an exhaustive catalog of every approximation the PCG makes --
+/// only the ones that are both easy to pinpoint at a single [`Location`] and
+/// plausibly actionable for a consumer deciding how much to trust the
+/// analysis at that point. Notably, dynamic/symbolic array indexing (`x[_1]`
+/// vs `x[_2]`) is also handled conservatively rather than precisely (see
+/// [`crate::utils::place::Place::partial_cmp`]'s treatment of
+/// [`rustc_interface::middle::mir::ProjectionElem::Index`](crate::rustc_interface::middle::mir::ProjectionElem::Index)),
+/// but isn't recorded here: that comparison is a pure function called from
+/// many unrelated places with neither a [`Location`] nor a diagnostics sink
+/// in scope, and threading both through it for this alone isn't worth the
+/// churn.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum DiagnosticCategory {
+    /// A call through a function pointer or `dyn Trait` vtable rather than a
+    /// statically resolved `FnDef`. The PCG still builds a function-call
+    /// abstraction edge for the call (see
+    /// `pcg::visitor::function_call::get_function_data`), but without a
+    /// callee to derive borrow behavior from, that edge uses the generic
+    /// conservative model instead of one informed by the callee's lifetime
+    /// structure.
+    IndirectCallFallback,
+    /// A place's address was taken with `&raw (const|mut)`. The PCG marks it
+    /// as escaped (see
+    /// [`crate::pcg::place_capabilities::PlaceCapabilities::mark_escaped`])
+    /// and stops tracking what, if anything, is later read or written
+    /// through the resulting raw pointer.
+    RawPointerEscape,
+}
+
+/// One recorded imprecision, as described in [`DiagnosticCategory`].
+#[derive(Debug, Clone, Serialize)]
+pub struct Diagnostic {
+    pub category: DiagnosticCategory,
+    /// The source span of the statement/terminator this was recorded at,
+    /// formatted via `{:?}` for readability outside a debugger (the same
+    /// approach `PCGStmtVisualizationData` uses for its `span` field).
+    pub location: String,
+    pub message: String,
+}
+
+/// Every [`Diagnostic`] recorded while a single [`crate::run_pcg`]
+/// invocation ran, gathered incrementally alongside
+/// [`crate::pcg::stats::PcgStats`]. See
+/// [`crate::free_pcs::PcgAnalysis::diagnostics`].
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct PcgDiagnostics(Vec<Diagnostic>);
+
+impl PcgDiagnostics {
+    pub(crate) fn record(
+        &mut self,
+        category: DiagnosticCategory,
+        location: Location,
+        ctxt: CompilerCtxt<'_, '_>,
+        message: impl Into<String>,
+    ) {
+        self.0.push(Diagnostic {
+            category,
+            location: format!("{:?}", ctxt.body().source_info(location).span),
+            message: message.into(),
+        });
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Diagnostic> {
+        self.0.iter()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Writes this summary as `diagnostics.json` in `dir_path`, alongside
+    /// the visualization output produced by [`crate::run_pcg_with_options`]
+    /// (see [`crate::pcg::stats::PcgStats::write_json_file`] for the same
+    /// pattern).
+    pub(crate) fn write_json_file(&self, dir_path: &str) {
+        let path = format!("{dir_path}/diagnostics.json");
+        std::fs::write(&path, serde_json::to_string_pretty(self).unwrap())
+            .expect("Failed to write diagnostics.json");
+    }
+
+    /// Renders these diagnostics as a
+    /// [SARIF 2.1.0](https://docs.oasis-open.org/sarif/sarif/v2.1.0/sarif-v2.1.0.html)
+    /// log, for consumption by editors/CI tooling that already know how to
+    /// render a SARIF run (e.g. GitHub code scanning). Only the subset of
+    /// the schema needed to locate and describe each [`Diagnostic`] is
+    /// populated; in particular, since a [`Diagnostic`]'s `location` is a
+    /// formatted `Span` rather than a structured file/line/column (see its
+    /// field docs), each SARIF result carries it as a
+    /// `physicalLocation.artifactLocation.uri` placeholder rather than a
+    /// proper `region`.
+    pub fn to_sarif(&self) -> serde_json::Value {
+        let results = self
+            .0
+            .iter()
+            .map(|diagnostic| {
+                serde_json::json!({
+                    "ruleId": format!("{:?}", diagnostic.category),
+                    "level": "warning",
+                    "message": { "text": diagnostic.message },
+                    "locations": [{
+                        "physicalLocation": {
+                            "artifactLocation": { "uri": diagnostic.location },
+                        },
+                    }],
+                })
+            })
+            .collect::<Vec<_>>();
+        serde_json::json!({
+            "$schema": "https://raw.githubusercontent.com/oasis-tcs/sarif-spec/master/Schemata/sarif-schema-2.1.0.json",
+            "version": "2.1.0",
+            "runs": [{
+                "tool": {
+                    "driver": {
+                        "name": "pcg",
+                        "rules": [
+                            { "id": "IndirectCallFallback" },
+                            { "id": "RawPointerEscape" },
+                        ],
+                    },
+                },
+                "results": results,
+            }],
+        })
+    }
+
+    /// Writes [`Self::to_sarif`]'s output as `diagnostics.sarif` in
+    /// `dir_path`.
+    pub(crate) fn write_sarif_file(&self, dir_path: &str) {
+        let path = format!("{dir_path}/diagnostics.sarif");
+        std::fs::write(&path, serde_json::to_string_pretty(&self.to_sarif()).unwrap())
+            .expect("Failed to write diagnostics.sarif");
+    }
+}
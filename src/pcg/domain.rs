@@ -12,12 +12,12 @@ use std::{
 };
 
 use derive_more::TryInto;
-use serde::{Serialize, Serializer};
+use serde::{Deserialize, Serialize, Serializer};
 
 use crate::{
-    action::PcgActions,
-    borrow_pcg::state::BorrowsState,
-    borrows_imgcat_debug,
+    action::{OwnedPcgAction, PcgAction, PcgActions},
+    borrow_pcg::{borrow_pcg_expansion::PlaceExpansion, state::BorrowsState},
+    borrows_imgcat_debug, borrows_imgcat_debug_for,
     pcg::{
         dot_graphs::{generate_dot_graph, PcgDotGraphsForBlock, ToGraph},
         triple::Triple,
@@ -40,8 +40,11 @@ use crate::{
     AnalysisEngine, DebugLines,
 };
 
-use super::{place_capabilities::PlaceCapabilities, PcgEngine};
-use crate::free_pcs::FreePlaceCapabilitySummary;
+use super::{
+    place_capabilities::PlaceCapabilities, stats::PcgStats, timing::PcgTimings, PcgEngine,
+    RecordingFlag,
+};
+use crate::free_pcs::{CapabilityKind, CapabilityLattice, FreePlaceCapabilitySummary, RepackOp};
 
 #[derive(Copy, Clone)]
 pub struct DataflowIterationDebugInfo {
@@ -144,6 +147,11 @@ impl Serialize for DataflowStmtPhase {
 pub(crate) struct PcgDebugData {
     pub(crate) dot_output_dir: String,
     pub(crate) dot_graphs: Rc<RefCell<PcgDotGraphsForBlock>>,
+    /// Shared with the owning [`crate::pcg::PcgEngine`], so that turning
+    /// recording on/off there (see [`RecordingFlag`]) is visible here
+    /// without needing to pass a fresh [`PcgDebugData`] through for every
+    /// statement.
+    pub(crate) recording: RecordingFlag,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, Default)]
@@ -235,7 +243,7 @@ impl<'mir, 'tcx: 'mir> Pcg<'tcx> {
         location: mir::Location,
         comment: &str,
     ) {
-        if borrows_imgcat_debug() {
+        if borrows_imgcat_debug_for(Some(location.block), Some(location.statement_index), comment) {
             let dot_graph = generate_pcg_dot_graph(self, ctxt, location).unwrap();
             DotGraph::render_with_imgcat(&dot_graph, comment).unwrap_or_else(|e| {
                 eprintln!("Error rendering self graph: {e}");
@@ -259,6 +267,108 @@ impl<'mir, 'tcx: 'mir> Pcg<'tcx> {
         self.owned.locals_mut().ensures(t, &mut self.capabilities);
     }
 
+    /// Applies `action` to this state, mutating it in place. This is the
+    /// single source of truth for what each [`PcgAction`] *means*: both the
+    /// engine (while recording actions during the dataflow analysis, see
+    /// [`crate::pcg::visitor::PcgVisitor::record_and_apply_action`]) and the
+    /// action replay engine ([`crate::action::replay`]) go through this
+    /// method, so the two can never disagree about what an action does.
+    pub(crate) fn apply_action(
+        &mut self,
+        action: &PcgAction<'tcx>,
+        ctxt: CompilerCtxt<'mir, 'tcx>,
+    ) -> std::result::Result<bool, PcgError> {
+        match action {
+            PcgAction::Borrow(action) => Ok(self.borrow.apply_action(
+                action.clone(),
+                &mut self.capabilities,
+                ctxt,
+            )?),
+            PcgAction::Owned(owned_action) => self.apply_owned_action(owned_action, ctxt),
+        }
+    }
+
+    fn apply_owned_action(
+        &mut self,
+        owned_action: &OwnedPcgAction<'tcx>,
+        ctxt: CompilerCtxt<'mir, 'tcx>,
+    ) -> std::result::Result<bool, PcgError> {
+        Ok(match owned_action.kind {
+            RepackOp::Allocate(_) | RepackOp::Deallocate(_) => {
+                // The allocation-state transition itself is applied by
+                // `ensure_triple` (via `PlaceCondition::Unalloc` /
+                // `AllocateOrDeallocate`) before this is recorded; this
+                // action exists only to surface the transition to
+                // consumers.
+                true
+            }
+            RepackOp::RequireRead(_) => {
+                // The read itself was already obtained by `require_triple`
+                // for the rvalue's operand triple (see `pcg::triple`); this
+                // action exists only to surface that read as an explicit
+                // obligation for `Len`/`Discriminant`, which (unlike a
+                // `Copy` operand) don't otherwise produce one of their own.
+                true
+            }
+            RepackOp::RegainLoanedCapability(place, capability_kind) => {
+                self.capabilities.insert((*place).into(), capability_kind)
+            }
+            RepackOp::Expand(expand) => {
+                let target_places = expand.target_places(ctxt);
+                let capability_projections =
+                    self.owned.locals_mut()[expand.local()].get_allocated_mut();
+                capability_projections.insert_expansion(
+                    expand.from,
+                    PlaceExpansion::from_places(target_places.clone(), ctxt),
+                );
+                let source_cap = if expand.capability.is_read() {
+                    expand.capability
+                } else {
+                    self.capabilities.get(expand.from).unwrap()
+                };
+                tracing::debug!("source_cap for {:?}: {:?}", owned_action, source_cap);
+                for target_place in target_places {
+                    self.capabilities.insert(target_place, source_cap);
+                }
+                if expand.capability.is_read() {
+                    self.capabilities.insert(expand.from, CapabilityKind::Read);
+                } else {
+                    self.capabilities.remove(expand.from);
+                }
+                true
+            }
+            RepackOp::DerefShallowInit(from, to) => {
+                let target_places = from.expand_one_level(to, ctxt)?.expansion();
+                let capability_projections =
+                    self.owned.locals_mut()[from.local].get_allocated_mut();
+                capability_projections.insert_expansion(
+                    from,
+                    PlaceExpansion::from_places(target_places.clone(), ctxt),
+                );
+                for target_place in target_places {
+                    self.capabilities.insert(target_place, CapabilityKind::Read);
+                }
+                true
+            }
+            RepackOp::Collapse(collapse) => {
+                let capability_projections =
+                    self.owned.locals_mut()[collapse.local()].get_allocated_mut();
+                let expansion_places = collapse.expansion_places(ctxt);
+                let retained_cap = expansion_places.iter().fold(
+                    CapabilityKind::Exclusive,
+                    |acc, place| match self.capabilities.remove(*place) {
+                        Some(cap) => acc.meet(cap).unwrap_or(CapabilityKind::Write),
+                        None => acc,
+                    },
+                );
+                self.capabilities.insert(collapse.to, retained_cap);
+                capability_projections.expansions.remove(&collapse.to);
+                true
+            }
+            _ => unreachable!(),
+        })
+    }
+
     #[tracing::instrument(skip(self, other, ctxt))]
     pub(crate) fn join(
         &mut self,
@@ -333,6 +443,14 @@ pub struct PcgDomain<'a, 'tcx, A: Allocator> {
     pub(crate) data: std::result::Result<PcgDomainData<'tcx, A>, PcgError>,
     pub(crate) debug_data: Option<PcgDebugData>,
     pub(crate) reachable: bool,
+    /// Shared with the owning [`PcgEngine`] (see [`PcgEngine::stats_handle`]),
+    /// so that joins observed here (the engine has no visibility into
+    /// individual join calls) are still recorded in the same [`PcgStats`]
+    /// the engine accumulates action counts into.
+    stats: Rc<RefCell<PcgStats>>,
+    /// Shared with the owning [`PcgEngine`] (see
+    /// [`PcgEngine::timings_handle`]), for the same reason as `stats`.
+    timings: Rc<RefCell<PcgTimings>>,
 }
 
 impl<A: Allocator + Debug> Debug for PcgDomain<'_, '_, A> {
@@ -356,7 +474,7 @@ impl ErrorState {
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct PcgError {
     pub(crate) kind: PCGErrorKind,
     pub(crate) context: Vec<String>,
@@ -377,7 +495,7 @@ impl PcgError {
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum PCGErrorKind {
     Unsupported(PCGUnsupportedError),
     Internal(PCGInternalError),
@@ -399,7 +517,7 @@ impl PcgError {
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct PCGInternalError(String);
 
 impl PCGInternalError {
@@ -414,7 +532,7 @@ impl From<PCGInternalError> for PcgError {
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum PCGUnsupportedError {
     AssignBorrowToNonReferenceType,
     DerefUnsafePtr,
@@ -422,6 +540,8 @@ pub enum PCGUnsupportedError {
     FunctionCallWithUnsafePtrArgument,
     IndexingNonIndexableType,
     InlineAssembly,
+    TailCall,
+    UnsupportedStatement,
 }
 
 impl<'a, 'tcx, A: Allocator + Clone> PcgDomain<'a, 'tcx, A> {
@@ -466,10 +586,12 @@ impl<'a, 'tcx, A: Allocator + Clone> PcgDomain<'a, 'tcx, A> {
         &mut self,
         output_dir: String,
         dot_graphs: Rc<RefCell<PcgDotGraphsForBlock>>,
+        recording: RecordingFlag,
     ) {
         self.debug_data = Some(PcgDebugData {
             dot_output_dir: output_dir,
             dot_graphs,
+            recording,
         });
     }
 
@@ -510,6 +632,8 @@ impl<'a, 'tcx, A: Allocator + Clone> PcgDomain<'a, 'tcx, A> {
         block: Option<BasicBlock>,
         debug_data: Option<PcgDebugData>,
         arena: A,
+        stats: Rc<RefCell<PcgStats>>,
+        timings: Rc<RefCell<PcgTimings>>,
     ) -> Self {
         Self {
             ctxt: repacker,
@@ -517,6 +641,8 @@ impl<'a, 'tcx, A: Allocator + Clone> PcgDomain<'a, 'tcx, A> {
             data: Ok(PcgDomainData::new(arena)),
             debug_data,
             reachable: false,
+            stats,
+            timings,
         }
     }
 }
@@ -529,19 +655,14 @@ impl<A: Allocator + Clone> PartialEq for PcgDomain<'_, '_, A> {
     }
 }
 
-impl<A: Allocator + Clone> JoinSemiLattice for PcgDomain<'_, '_, A> {
-    fn join(&mut self, other: &Self) -> bool {
-        if !self.reachable && !other.reachable {
-            return false;
-        }
-        if other.has_error() && !self.has_error() {
-            self.data = other.data.clone();
-            return true;
-        }
-
+impl<A: Allocator + Clone> PcgDomain<'_, '_, A> {
+    #[tracing::instrument(skip(self, other))]
+    fn join_reachable(&mut self, other: &Self) -> bool {
         let self_block = self.block();
         let other_block = other.block();
 
+        self.stats.borrow_mut().record_join(self_block);
+
         let data = match &mut self.data {
             Ok(data) => data,
             Err(_) => return false,
@@ -600,6 +721,23 @@ impl<A: Allocator + Clone> JoinSemiLattice for PcgDomain<'_, '_, A> {
     }
 }
 
+impl<A: Allocator + Clone> JoinSemiLattice for PcgDomain<'_, '_, A> {
+    fn join(&mut self, other: &Self) -> bool {
+        if !self.reachable && !other.reachable {
+            return false;
+        }
+        if other.has_error() && !self.has_error() {
+            self.data = other.data.clone();
+            return true;
+        }
+
+        let start = std::time::Instant::now();
+        let result = self.join_reachable(other);
+        self.timings.borrow_mut().record_join(start.elapsed());
+        result
+    }
+}
+
 impl<'a, 'tcx, A: Allocator + Clone + Debug>
     DebugWithContext<AnalysisEngine<PcgEngine<'a, 'tcx, A>>> for PcgDomain<'a, 'tcx, A>
 {
@@ -1,22 +1,98 @@
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 
 use serde_derive::Serialize;
 
 use crate::pcg::{DataflowStmtPhase, EvalStmtPhase, Pcg, PcgDebugData};
 use crate::rustc_interface::middle::mir::{self, BasicBlock};
+use crate::utils::display::DisplayWithCompilerCtxt;
 use crate::utils::CompilerCtxt;
 use crate::visualization::write_pcg_dot_graph_to_file;
-use crate::RECORD_PCG;
+
+/// A snapshot of a statement's entry state (borrow graph edges and place
+/// capabilities), used to diff consecutive fixpoint iterations of a block
+/// against each other; see [`IterationDiff`].
+#[derive(Clone, Default)]
+struct IterationSnapshot {
+    edges: BTreeSet<String>,
+    capabilities: BTreeMap<String, String>,
+}
+
+impl IterationSnapshot {
+    fn of<'tcx>(pcg: &Pcg<'tcx>, ctxt: CompilerCtxt<'_, 'tcx>) -> Self {
+        Self {
+            edges: pcg
+                .borrow
+                .graph()
+                .edges()
+                .map(|edge| format!("{edge:?}"))
+                .collect(),
+            capabilities: pcg
+                .capabilities
+                .iter()
+                .map(|(place, cap)| (place.to_short_string(ctxt), format!("{cap:?}")))
+                .collect(),
+        }
+    }
+}
+
+/// The difference between a block's entry state on one fixpoint iteration
+/// and the previous one, explaining why (if at all) another iteration was
+/// needed.
+#[derive(Clone, Serialize, Default)]
+pub(crate) struct IterationDiff {
+    added_edges: Vec<String>,
+    removed_edges: Vec<String>,
+    capability_changes: Vec<String>,
+}
+
+impl IterationDiff {
+    fn between(previous: &IterationSnapshot, current: &IterationSnapshot) -> Self {
+        let added_edges = current
+            .edges
+            .difference(&previous.edges)
+            .cloned()
+            .collect();
+        let removed_edges = previous
+            .edges
+            .difference(&current.edges)
+            .cloned()
+            .collect();
+        let mut capability_changes = Vec::new();
+        for (place, cap) in &current.capabilities {
+            match previous.capabilities.get(place) {
+                Some(prev_cap) if prev_cap != cap => {
+                    capability_changes.push(format!("{place}: {prev_cap} -> {cap}"));
+                }
+                None => capability_changes.push(format!("{place}: (none) -> {cap}")),
+                _ => {}
+            }
+        }
+        for (place, prev_cap) in &previous.capabilities {
+            if !current.capabilities.contains_key(place) {
+                capability_changes.push(format!("{place}: {prev_cap} -> (none)"));
+            }
+        }
+        Self {
+            added_edges,
+            removed_edges,
+            capability_changes,
+        }
+    }
+}
 
 #[derive(Clone, Serialize)]
 #[derive(Default)]
-pub(crate) struct PcgDotGraphsForBlock(Vec<PcgDotGraphsForStmt>);
+pub(crate) struct PcgDotGraphsForBlock(
+    Vec<PcgDotGraphsForStmt>,
+    #[serde(skip)] BTreeMap<usize, IterationSnapshot>,
+);
 
 #[derive(Clone, Serialize)]
 #[derive(Default)]
 pub(crate) struct PcgDotGraphsForIteration {
     at_phase: Vec<(DataflowStmtPhase, String)>,
     actions: BTreeMap<EvalStmtPhase, Vec<String>>,
+    diff: Option<IterationDiff>,
 }
 
 
@@ -42,20 +118,22 @@ impl PcgDotGraphsForBlock {
         to_graph: ToGraph,
     ) -> String {
         let iteration = self.num_iterations(statement_index);
+        let ext = crate::visualization::OUTPUT_FORMAT.extension();
         match to_graph {
             ToGraph::Phase(phase) => {
                 format!(
-                    "{:?}_stmt_{}_iteration_{}_{}.dot",
+                    "{:?}_stmt_{}_iteration_{}_{}.{}",
                     block,
                     statement_index,
                     iteration,
-                    phase.to_filename_str_part()
+                    phase.to_filename_str_part(),
+                    ext
                 )
             }
             ToGraph::Action(phase, action_idx) => {
                 format!(
-                    "{:?}_stmt_{}_iteration_{}_{:?}_action_{}.dot",
-                    block, statement_index, iteration, phase, action_idx,
+                    "{:?}_stmt_{}_iteration_{}_{:?}_action_{}.{}",
+                    block, statement_index, iteration, phase, action_idx, ext
                 )
             }
         }
@@ -121,6 +199,23 @@ impl PcgDotGraphsForBlock {
     pub(crate) fn write_json_file(&self, filename: &str) {
         std::fs::write(filename, serde_json::to_string_pretty(&self.0).unwrap()).unwrap();
     }
+
+    /// Diffs `pcg`'s current borrow-graph edges and place capabilities
+    /// against the snapshot taken at the previous fixpoint iteration of
+    /// `statement_index` (if any), recording the result on the
+    /// just-registered iteration so it's visible in `block_N_iterations.json`.
+    pub(crate) fn compute_and_store_diff<'tcx>(
+        &mut self,
+        statement_index: usize,
+        pcg: &Pcg<'tcx>,
+        ctxt: CompilerCtxt<'_, 'tcx>,
+    ) {
+        let current = IterationSnapshot::of(pcg, ctxt);
+        if let Some(previous) = self.1.get(&statement_index) {
+            self.last_iteration_mut(statement_index).diff = Some(IterationDiff::between(previous, &current));
+        }
+        self.1.insert(statement_index, current);
+    }
 }
 
 fn dot_filename_for(output_dir: &str, relative_filename: &str) -> String {
@@ -141,7 +236,10 @@ pub(crate) fn generate_dot_graph<'tcx>(
     debug_data: &Option<PcgDebugData>,
     ctxt: CompilerCtxt<'_, 'tcx>,
 ) {
-    if !*RECORD_PCG.lock().unwrap() {
+    if !debug_data
+        .as_ref()
+        .is_some_and(|debug_data| debug_data.recording.get())
+    {
         return;
     }
     if block.as_usize() == 0 {
@@ -166,11 +264,20 @@ pub(crate) fn generate_dot_graph<'tcx>(
                     relative_filename,
                 );
             }
-            ToGraph::Phase(phase) => debug_data.dot_graphs.borrow_mut().insert_for_phase(
-                statement_index,
-                phase,
-                relative_filename,
-            ),
+            ToGraph::Phase(phase) => {
+                debug_data.dot_graphs.borrow_mut().insert_for_phase(
+                    statement_index,
+                    phase,
+                    relative_filename,
+                );
+                if matches!(phase, DataflowStmtPhase::Initial) {
+                    debug_data.dot_graphs.borrow_mut().compute_and_store_diff(
+                        statement_index,
+                        pcg,
+                        ctxt,
+                    );
+                }
+            }
         }
 
         write_pcg_dot_graph_to_file(
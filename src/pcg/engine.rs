@@ -15,12 +15,17 @@ use bit_set::BitSet;
 use derive_more::From;
 
 use super::{
-    domain::PcgDomain, visitor::PcgVisitor, DataflowStmtPhase, ErrorState, EvalStmtPhase,
+    diagnostics::PcgDiagnostics, domain::PcgDomain,
+    function_call_cache::FunctionCallAbstractionCache,
+    function_summary::FunctionSummaryRegistry, observer::PcgObserver, stats::PcgStats,
+    timing::PcgTimings, visitor::PcgVisitor, DataflowStmtPhase, ErrorState, EvalStmtPhase,
     PcgDebugData, PcgError,
 };
 use crate::{
+    action::replay,
     pcg::dot_graphs::PcgDotGraphsForBlock,
     utils::{arena::ArenaRef, CompilerCtxt},
+    validity_checks_enabled, validity_checks_warn_only,
 };
 use crate::{
     pcg::triple::TripleWalker,
@@ -66,8 +71,8 @@ impl<'tcx> BodyAndBorrows<'tcx> for BodyWithBorrowckFacts<'tcx> {
         self.location_table.as_ref().unwrap()
     }
 
-    fn input_facts(&self) -> &PoloniusInput {
-        self.input_facts.as_ref().unwrap()
+    fn input_facts(&self) -> Option<&PoloniusInput> {
+        self.input_facts.as_deref()
     }
 }
 
@@ -119,15 +124,35 @@ struct PCGEngineDebugData {
     dot_graphs: IndexVec<BasicBlock, Rc<RefCell<PcgDotGraphsForBlock>>>,
 }
 
+/// Whether an engine is currently recording visualization output for its
+/// analysis. Recording is enabled only while the initial fixpoint
+/// computation is running (see [`PcgEngine::recording_flag`]); it's disabled
+/// again before the results cursor is used to examine the computed fixpoint,
+/// so that re-visiting statements to read results doesn't also re-write
+/// their debugging output.
+///
+/// This is per-engine (rather than a crate-wide global) so that concurrent
+/// [`crate::run_pcg`] invocations on different threads (e.g. from
+/// [`crate::utils::callbacks::run_pcg_all`]) don't observe or clobber each
+/// other's flag.
+pub(crate) type RecordingFlag = Rc<Cell<bool>>;
+
 type Block = usize;
 
 pub struct PcgEngine<'a, 'tcx: 'a, A: Allocator + Clone> {
     pub(crate) ctxt: CompilerCtxt<'a, 'tcx>,
     debug_data: Option<PCGEngineDebugData>,
+    recording: RecordingFlag,
+    stats: Rc<RefCell<PcgStats>>,
+    timings: Rc<RefCell<PcgTimings>>,
+    diagnostics: Rc<RefCell<PcgDiagnostics>>,
     curr_block: Cell<BasicBlock>,
     pub(crate) reachable_blocks: BitSet<Block>,
     pub(crate) first_error: ErrorState,
     pub(crate) arena: A,
+    observer: Option<Rc<RefCell<dyn PcgObserver<'tcx> + 'a>>>,
+    function_summaries: Option<Rc<FunctionSummaryRegistry>>,
+    function_call_cache: Rc<FunctionCallAbstractionCache<'tcx>>,
 }
 pub(crate) fn edges_to_analyze<'tcx, 'mir>(
     terminator: &'mir Terminator<'tcx>,
@@ -197,6 +222,7 @@ impl<'a, 'tcx, A: Allocator + Clone> PcgEngine<'a, 'tcx, A> {
             state.set_debug_data(
                 debug_data.debug_output_dir.clone(),
                 debug_data.dot_graphs[block].clone(),
+                self.recording.clone(),
             );
         }
         assert!(state.is_initialized());
@@ -221,6 +247,21 @@ impl<'a, 'tcx, A: Allocator + Clone> PcgEngine<'a, 'tcx, A> {
             }
         }
 
+        let start = std::time::Instant::now();
+        let result = self.apply_transfer_function(state, object, location);
+        self.timings
+            .borrow_mut()
+            .record_transfer_function(start.elapsed());
+        result
+    }
+
+    #[tracing::instrument(skip(self, state, object))]
+    fn apply_transfer_function(
+        &mut self,
+        state: &mut PcgDomain<'a, 'tcx, A>,
+        object: AnalysisObject<'_, 'tcx>,
+        location: Location,
+    ) -> Result<(), PcgError> {
         self.initialize(state, location.block);
         state.register_new_debug_iteration(location);
 
@@ -245,6 +286,7 @@ impl<'a, 'tcx, A: Allocator + Clone> PcgEngine<'a, 'tcx, A> {
 
         for phase in EvalStmtPhase::phases() {
             let curr = ArenaRef::make_mut(&mut pcg.states.0[phase]);
+            let pre = validity_checks_enabled().then(|| curr.clone());
             pcg_data.actions[phase] = PcgVisitor::visit(
                 curr,
                 self.ctxt,
@@ -253,7 +295,33 @@ impl<'a, 'tcx, A: Allocator + Clone> PcgEngine<'a, 'tcx, A> {
                 object,
                 location,
                 state.debug_data.clone(),
+                self.function_summaries.clone(),
+                self.function_call_cache.clone(),
+                self.diagnostics.clone(),
             )?;
+            if let Some(pre) = &pre
+                && let Err(mismatch) =
+                    replay::replay_and_check(pre, &pcg_data.actions[phase], curr, self.ctxt)
+            {
+                if validity_checks_warn_only() {
+                    tracing::error!("Replay check failed at {location:?} ({phase:?}): {mismatch:?}");
+                } else {
+                    panic!("Replay check failed at {location:?} ({phase:?}): {mismatch:?}");
+                }
+            }
+            if let Some(observer) = &self.observer {
+                let mut observer = observer.borrow_mut();
+                for action in pcg_data.actions[phase].iter() {
+                    observer.on_action(location, phase, action);
+                }
+            }
+            {
+                let mut stats = self.stats.borrow_mut();
+                for kind in pcg_data.actions[phase].kinds() {
+                    stats.record_action_kind(&kind);
+                }
+                stats.record_graph_size(curr.borrow.graph().edges().count());
+            }
             if let Some(next_phase) = phase.next() {
                 pcg.states.0[next_phase] = pcg.states.0[phase].clone();
             }
@@ -271,6 +339,8 @@ impl<'a, 'tcx, A: Allocator + Clone> PcgEngine<'a, 'tcx, A> {
         ctxt: CompilerCtxt<'a, 'tcx>,
         arena: A,
         debug_output_dir: Option<&str>,
+        observer: Option<Rc<RefCell<dyn PcgObserver<'tcx> + 'a>>>,
+        function_summaries: Option<Rc<FunctionSummaryRegistry>>,
     ) -> Self {
         let debug_data = debug_output_dir.map(|dir_path| {
             if std::path::Path::new(&dir_path).exists() {
@@ -289,16 +359,67 @@ impl<'a, 'tcx, A: Allocator + Clone> PcgEngine<'a, 'tcx, A> {
         let mut reachable_blocks = BitSet::default();
         reachable_blocks.reserve_len(ctxt.body().basic_blocks.len());
         reachable_blocks.insert(START_BLOCK.index());
+        let mut stats = PcgStats::default();
+        let loop_analysis = crate::r#loop::LoopAnalysis::find_loops(ctxt.body());
+        for block in ctxt.body().basic_blocks.indices() {
+            let depth = loop_analysis.loop_depth(block);
+            if depth > 0 {
+                stats.loop_depths.insert(block.index(), depth);
+            }
+        }
         Self {
             first_error: ErrorState::default(),
             reachable_blocks,
             ctxt,
             debug_data,
+            recording: Rc::new(Cell::new(false)),
+            stats: Rc::new(RefCell::new(stats)),
+            timings: Rc::new(RefCell::new(PcgTimings::default())),
+            diagnostics: Rc::new(RefCell::new(PcgDiagnostics::default())),
             curr_block: Cell::new(START_BLOCK),
             arena,
+            observer,
+            function_summaries,
+            function_call_cache: Rc::new(FunctionCallAbstractionCache::new()),
         }
     }
 
+    /// A handle that can be used to toggle whether this engine writes
+    /// visualization output while it runs, even after the engine itself has
+    /// been moved into the dataflow framework's fixpoint solver. See
+    /// [`RecordingFlag`].
+    pub(crate) fn recording_flag(&self) -> RecordingFlag {
+        self.recording.clone()
+    }
+
+    /// A handle to the [`PcgStats`] this engine accumulates while its
+    /// fixpoint analysis runs. Shared with every [`PcgDomain`] it produces,
+    /// which records join-iteration counts directly (the engine itself
+    /// never sees individual join calls), and with
+    /// [`crate::free_pcs::PcgAnalysis`] once the analysis has finished, so
+    /// callers can inspect it without the engine needing to re-derive it
+    /// after the fact.
+    pub(crate) fn stats_handle(&self) -> Rc<RefCell<PcgStats>> {
+        self.stats.clone()
+    }
+
+    /// A handle to the [`PcgTimings`] this engine accumulates while its
+    /// fixpoint analysis runs. Shared with every [`PcgDomain`] it produces,
+    /// which records join timings directly (the engine itself never sees
+    /// individual join calls), and with [`crate::free_pcs::PcgAnalysis`]
+    /// once the analysis has finished.
+    pub(crate) fn timings_handle(&self) -> Rc<RefCell<PcgTimings>> {
+        self.timings.clone()
+    }
+
+    /// A handle to the [`PcgDiagnostics`] this engine accumulates while its
+    /// fixpoint analysis runs. Shared with every [`PcgVisitor`] it creates,
+    /// which records each imprecision directly as it's encountered, and
+    /// with [`crate::free_pcs::PcgAnalysis`] once the analysis has finished.
+    pub(crate) fn diagnostics_handle(&self) -> Rc<RefCell<PcgDiagnostics>> {
+        self.diagnostics.clone()
+    }
+
     fn generate_dot_graph(
         &self,
         state: &mut PcgDomain<'a, 'tcx, A>,
@@ -326,13 +447,21 @@ impl<'a, 'tcx, A: Allocator + Copy> Analysis<'tcx> for PcgEngine<'a, 'tcx, A> {
             let debug_data = self.debug_output_dir().map(|dir| PcgDebugData {
                 dot_output_dir: dir,
                 dot_graphs: self.dot_graphs(curr_block).unwrap(),
+                recording: self.recording.clone(),
             });
             (Some(curr_block), debug_data)
         } else {
             // For results cursor, don't set block or consider debug data
             (None, None)
         };
-        PcgDomain::new(self.ctxt, block, debug_data, self.arena)
+        PcgDomain::new(
+            self.ctxt,
+            block,
+            debug_data,
+            self.arena,
+            self.stats.clone(),
+            self.timings.clone(),
+        )
     }
 
     fn initialize_start_block(&self, _body: &Body<'tcx>, state: &mut Self::Domain) {
@@ -360,6 +489,7 @@ impl<'a, 'tcx, A: Allocator + Copy> Analysis<'tcx> for PcgEngine<'a, 'tcx, A> {
         }
     }
 
+    #[tracing::instrument(skip(self, state, terminator))]
     fn apply_terminator_effect<'mir>(
         &mut self,
         state: &mut Self::Domain,
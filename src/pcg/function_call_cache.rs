@@ -0,0 +1,58 @@
+// © 2023, ETH Zurich
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use std::{cell::RefCell, rc::Rc};
+
+use crate::{
+    borrow_pcg::region_projection::PcgRegion,
+    rustc_interface::{
+        data_structures::fx::{FxHashMap, FxHashSet},
+        hir::def_id::DefId,
+        middle::ty::GenericArgsRef,
+    },
+};
+
+/// Caches the disjoint-lifetime-class skeleton `pcg::visitor::function_call`
+/// computes for a call's argument region projections, keyed by the callee's
+/// `(DefId, substs)`. Because `substs` includes the caller's own region
+/// arguments for the call, this key is effectively specific to a single call
+/// site; the cache pays off when the dataflow fixpoint revisits that call
+/// site (e.g. one inside a loop body) on a later iteration, rather than
+/// across distinct call sites.
+///
+/// This lives on [`super::PcgEngine`] rather than on [`crate::utils::CompilerCtxt`]
+/// itself, since `CompilerCtxt` is `Copy` and cloned pervasively throughout
+/// the crate; giving it a `Rc<RefCell<_>>` field would force it to give up
+/// `Copy`, a much larger change than this optimization warrants.
+#[derive(Default)]
+pub(crate) struct FunctionCallAbstractionCache<'tcx> {
+    disjoint_lifetime_sets: RefCell<FxHashMap<(DefId, GenericArgsRef<'tcx>), Rc<Vec<FxHashSet<PcgRegion>>>>>,
+}
+
+impl<'tcx> FunctionCallAbstractionCache<'tcx> {
+    pub(crate) fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the cached disjoint lifetime sets for `(def_id, substs)`, or
+    /// computes and caches them via `compute` if this is the first time
+    /// they've been requested.
+    pub(crate) fn get_or_compute(
+        &self,
+        def_id: DefId,
+        substs: GenericArgsRef<'tcx>,
+        compute: impl FnOnce() -> Vec<FxHashSet<PcgRegion>>,
+    ) -> Rc<Vec<FxHashSet<PcgRegion>>> {
+        if let Some(cached) = self.disjoint_lifetime_sets.borrow().get(&(def_id, substs)) {
+            return cached.clone();
+        }
+        let computed = Rc::new(compute());
+        self.disjoint_lifetime_sets
+            .borrow_mut()
+            .insert((def_id, substs), computed.clone());
+        computed
+    }
+}
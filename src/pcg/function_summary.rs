@@ -0,0 +1,52 @@
+// © 2023, ETH Zurich
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use crate::rustc_interface::{data_structures::fx::FxHashMap, hir::def_id::DefId, middle::ty::TyCtxt};
+
+/// A hand-written description of which of a call's argument region
+/// projections its return value borrows from, for functions whose actual
+/// signature doesn't convey this precisely enough for
+/// `pcg::visitor::function_call` to derive it from lifetimes alone -- e.g.
+/// `Option::as_mut(&mut self) -> Option<&mut T>`, where the link between the
+/// return value and `self` is carried by a generic parameter rather than a
+/// distinct region.
+#[derive(Clone, Debug, Default)]
+pub struct FunctionSummary {
+    /// Indices into the call's argument list that the return value borrows
+    /// from.
+    pub borrows_from_args: Vec<usize>,
+}
+
+impl FunctionSummary {
+    pub fn new(borrows_from_args: Vec<usize>) -> Self {
+        Self { borrows_from_args }
+    }
+}
+
+/// User-supplied [`FunctionSummary`]s, keyed by `def_path_str` (e.g.
+/// `"std::option::Option::<T>::as_mut"`), that override the
+/// signature-derived default computed for a call's abstraction edges. Set
+/// via [`crate::PcgOptionsBuilder::function_summaries`].
+#[derive(Clone, Default)]
+pub struct FunctionSummaryRegistry {
+    by_def_path: FxHashMap<String, FunctionSummary>,
+}
+
+impl FunctionSummaryRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers `summary` as the override for the function at `def_path`,
+    /// the string `TyCtxt::def_path_str` would return for it.
+    pub fn register(&mut self, def_path: impl Into<String>, summary: FunctionSummary) {
+        self.by_def_path.insert(def_path.into(), summary);
+    }
+
+    pub(crate) fn lookup(&self, tcx: TyCtxt<'_>, def_id: DefId) -> Option<&FunctionSummary> {
+        self.by_def_path.get(&tcx.def_path_str(def_id))
+    }
+}
@@ -7,14 +7,28 @@
 mod engine;
 mod domain;
 mod dot_graphs;
+mod function_call_cache;
+mod function_summary;
 mod node;
+mod observer;
 mod successor;
 
+pub mod cross_validation;
+pub mod diagnostics;
 pub mod place_capabilities;
+pub mod query;
+pub mod stats;
+pub mod summaries;
+pub mod timing;
 pub(crate) mod triple;
 pub(crate) mod visitor;
 
 pub use engine::*;
+pub use diagnostics::{Diagnostic, DiagnosticCategory, PcgDiagnostics};
 pub use domain::*;
+pub use function_summary::*;
 pub use node::*;
+pub use observer::*;
+pub use stats::PcgStats;
 pub use successor::*;
+pub use timing::PcgTimings;
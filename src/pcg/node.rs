@@ -6,6 +6,7 @@ use crate::utils::json::ToJsonWithCompilerCtxt;
 use crate::utils::maybe_old::MaybeOldPlace;
 use crate::utils::place::maybe_remote::MaybeRemotePlace;
 use crate::utils::remote::RemotePlace;
+use crate::utils::static_place::StaticPlace;
 use crate::utils::{Place, SnapshotLocation};
 use crate::{
     borrow_pcg::{
@@ -178,7 +179,7 @@ pub trait PCGNodeLike<'tcx>:
                 MaybeRemotePlace::Local(maybe_old_place) => {
                     Some(maybe_old_place.to_local_node(repacker))
                 }
-                MaybeRemotePlace::Remote(_) => None,
+                MaybeRemotePlace::Remote(_) | MaybeRemotePlace::Static(_) => None,
             },
             PCGNode::RegionProjection(rp) => match rp.base() {
                 MaybeRemoteRegionProjectionBase::Place(maybe_remote_place) => {
@@ -186,7 +187,7 @@ pub trait PCGNodeLike<'tcx>:
                         MaybeRemotePlace::Local(maybe_old_place) => {
                             Some(rp.with_base(maybe_old_place).to_local_node(repacker))
                         }
-                        MaybeRemotePlace::Remote(_) => None,
+                        MaybeRemotePlace::Remote(_) | MaybeRemotePlace::Static(_) => None,
                     }
                 }
                 MaybeRemoteRegionProjectionBase::Const(_) => None,
@@ -204,3 +205,9 @@ impl From<RemotePlace> for PCGNode<'_> {
         PCGNode::Place(remote_place.into())
     }
 }
+
+impl From<StaticPlace> for PCGNode<'_> {
+    fn from(static_place: StaticPlace) -> Self {
+        PCGNode::Place(static_place.into())
+    }
+}
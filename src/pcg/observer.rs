@@ -0,0 +1,21 @@
+// © 2023, ETH Zurich
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use crate::{
+    action::PcgAction,
+    pcg::EvalStmtPhase,
+    rustc_interface::middle::mir::Location,
+};
+
+/// Hook invoked by [`crate::pcg::PcgEngine`] as it applies PCG actions
+/// (`Weaken`, `RestoreCapability`, reborrow add/remove, expansion, ...)
+/// during the analysis. Implement this to collect statistics or build custom
+/// outputs without having to post-process the JSON visualization files.
+pub trait PcgObserver<'tcx> {
+    /// Called once for each action applied at `location` during `phase`, in
+    /// the order the actions were applied.
+    fn on_action(&mut self, location: Location, phase: EvalStmtPhase, action: &PcgAction<'tcx>);
+}
@@ -1,8 +1,11 @@
 use itertools::Itertools;
 
 use crate::{
-    free_pcs::CapabilityKind,
-    rustc_interface::{data_structures::fx::FxHashMap, middle::mir},
+    free_pcs::{CapabilityKind, CapabilityLattice},
+    rustc_interface::{
+        data_structures::fx::{FxHashMap, FxHashSet},
+        middle::mir,
+    },
     utils::{
         display::{DebugLines, DisplayWithCompilerCtxt},
         CompilerCtxt, Place,
@@ -10,7 +13,13 @@ use crate::{
 };
 
 #[derive(Clone, Debug, PartialEq, Eq, Default)]
-pub struct PlaceCapabilities<'tcx>(pub(crate) FxHashMap<Place<'tcx>, CapabilityKind>);
+pub struct PlaceCapabilities<'tcx> {
+    capabilities: FxHashMap<Place<'tcx>, CapabilityKind>,
+    /// Places whose address has been taken via `&raw (const|mut)`. Capability
+    /// transfers for these places can't assume that no alias escaped through
+    /// the raw pointer, even once the pointer itself is no longer live.
+    escaped: FxHashSet<Place<'tcx>>,
+}
 
 impl<'tcx> DebugLines<CompilerCtxt<'_, 'tcx>> for PlaceCapabilities<'tcx> {
     fn debug_lines(&self, repacker: CompilerCtxt<'_, 'tcx>) -> Vec<String> {
@@ -36,7 +45,7 @@ impl<'tcx> PlaceCapabilities<'tcx> {
         local: mir::Local,
         ctxt: CompilerCtxt<'mir, 'tcx>,
     ) -> impl Iterator<Item = (Place<'tcx>, &'slf mut CapabilityKind)> + use<'tcx, 'slf, 'mir> {
-        self.0.iter_mut().filter_map(move |(place, capability)| {
+        self.capabilities.iter_mut().filter_map(move |(place, capability)| {
             if place.local == local && place.is_owned(ctxt) {
                 Some((*place, capability))
             } else {
@@ -48,39 +57,53 @@ impl<'tcx> PlaceCapabilities<'tcx> {
     /// Returns true iff the capability was changed.
     pub(crate) fn insert(&mut self, place: Place<'tcx>, capability: CapabilityKind) -> bool {
         tracing::debug!("inserting {:?} with {:?}", place, capability);
-        self.0.insert(place, capability) != Some(capability)
+        self.capabilities.insert(place, capability) != Some(capability)
     }
 
     pub(crate) fn remove(&mut self, place: Place<'tcx>) -> Option<CapabilityKind> {
-        self.0.remove(&place)
+        self.capabilities.remove(&place)
     }
 
     pub fn iter(&self) -> impl Iterator<Item = (Place<'tcx>, CapabilityKind)> + '_ {
-        self.0.iter().map(|(k, v)| (*k, *v))
+        self.capabilities.iter().map(|(k, v)| (*k, *v))
     }
 
     pub(crate) fn get(&self, place: Place<'tcx>) -> Option<CapabilityKind> {
-        self.0.get(&place).copied()
+        self.capabilities.get(&place).copied()
     }
 
     pub(crate) fn join(&mut self, other: &Self) -> bool {
         let mut changed = false;
         for (place, other_capability) in other.iter() {
-            match self.0.get(&place) {
+            match self.capabilities.get(&place) {
                 Some(self_capability) => {
-                    if let Some(c) = self_capability.minimum(other_capability) {
-                        changed |= self.0.insert(place, c) != Some(c);
+                    if let Some(c) = self_capability.meet(other_capability) {
+                        changed |= self.capabilities.insert(place, c) != Some(c);
                     } else {
-                        self.0.remove(&place);
+                        self.capabilities.remove(&place);
                         changed = true;
                     }
                 }
                 None => {
-                    self.0.insert(place, other_capability);
+                    self.capabilities.insert(place, other_capability);
                     changed = true;
                 }
             }
         }
+        for place in other.escaped.iter() {
+            changed |= self.escaped.insert(*place);
+        }
         changed
     }
+
+    /// Records that `place`'s address was taken via `&raw (const|mut)`, so
+    /// it may have raw-pointer aliases that outlive any borrow tracked in
+    /// the borrow PCG.
+    pub(crate) fn mark_escaped(&mut self, place: Place<'tcx>) {
+        self.escaped.insert(place);
+    }
+
+    pub fn has_escaped(&self, place: Place<'tcx>) -> bool {
+        self.escaped.contains(&place)
+    }
 }
@@ -0,0 +1,38 @@
+// © 2023, ETH Zurich
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Small read-only queries over a single [`Pcg`] snapshot, e.g. "what
+//! capability does this place have here" and "what's currently blocking
+//! this place". Factored out so that interactive consumers (e.g. an
+//! editor integration answering hover requests) can ask these questions
+//! without reaching into [`PlaceCapabilities`] or [`BorrowsGraph`]
+//! themselves.
+
+use crate::{
+    free_pcs::CapabilityKind,
+    pcg::Pcg,
+    utils::{display::DisplayWithCompilerCtxt, CompilerCtxt, Place},
+};
+
+/// The capability `pcg` currently assigns to `place`, if any.
+pub fn capability_of<'tcx>(pcg: &Pcg<'tcx>, place: Place<'tcx>) -> Option<CapabilityKind> {
+    pcg.capabilities().get(place)
+}
+
+/// Short descriptions of the borrow PCG edges currently blocking `place`,
+/// i.e. the borrows/reborrows that must expire before `place` regains the
+/// capability they're holding back.
+pub fn blocking_edges<'tcx>(
+    pcg: &Pcg<'tcx>,
+    place: Place<'tcx>,
+    ctxt: CompilerCtxt<'_, 'tcx>,
+) -> Vec<String> {
+    pcg.borrow
+        .graph()
+        .edges_blocking(place.into(), ctxt)
+        .map(|edge| edge.to_short_string(ctxt))
+        .collect()
+}
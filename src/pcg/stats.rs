@@ -0,0 +1,91 @@
+// © 2023, ETH Zurich
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use std::collections::BTreeMap;
+
+use serde_derive::Serialize;
+
+use crate::{
+    action::PcgActionKind,
+    rustc_interface::{index::Idx, middle::mir::BasicBlock},
+    BorrowExpiryPolicy,
+};
+
+/// Aggregate counters describing a single [`crate::run_pcg`] invocation,
+/// gathered incrementally while the fixpoint analysis runs. Intended for
+/// performance triage on large functions; see
+/// [`crate::free_pcs::PcgAnalysis::stats`].
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct PcgStats {
+    /// The [`BorrowExpiryPolicy`] the run was configured with (see
+    /// [`crate::PcgOptions::borrow_expiry_policy`]). Recorded here so that
+    /// tools consuming `stats.json` can see which policy was requested,
+    /// even though only [`BorrowExpiryPolicy::Eager`] is currently
+    /// implemented.
+    pub borrow_expiry_policy: BorrowExpiryPolicy,
+    /// Number of [`PcgActionKind::Expand`] actions applied across the whole
+    /// analysis.
+    pub expansions: usize,
+    /// Number of [`PcgActionKind::Collapse`] actions applied across the
+    /// whole analysis.
+    pub collapses: usize,
+    /// Number of borrow/borrow-flow edges added to the borrow PCG.
+    pub reborrows_created: usize,
+    /// Number of borrow/borrow-flow edges that expired (were removed from
+    /// the borrow PCG).
+    pub reborrows_expired: usize,
+    /// How many times the dataflow framework joined into each block's entry
+    /// state, keyed by block index. A high count for a block usually means
+    /// a loop head that took many iterations to reach a fixpoint.
+    pub join_iterations: BTreeMap<usize, usize>,
+    /// The largest number of borrow PCG edges observed in any single state
+    /// during the analysis.
+    pub peak_graph_size: usize,
+    /// Loop nesting depth of each block that's inside at least one loop
+    /// (0 = not in a loop; see [`crate::r#loop::LoopAnalysis::loop_depth`]),
+    /// keyed by block index. Read alongside `join_iterations`: a block with
+    /// a high join count and a high loop depth is a nested loop that's the
+    /// likely cause, rather than an artifact of visitation order. There's
+    /// no way to expose a configurable worklist strategy to change that
+    /// order in the first place -- `PcgEngine` only implements
+    /// [`crate::rustc_interface::dataflow::Analysis`]'s per-block
+    /// callbacks; the worklist itself is owned entirely by rustc's
+    /// `mir_dataflow::Engine`/`MirAnalysis::iterate_to_fixpoint`, which
+    /// exposes no pluggable strategy hook. This field is the scoped
+    /// diagnostic substitute: it can't change iteration order, but it can
+    /// explain why a given order needed more iterations.
+    pub loop_depths: BTreeMap<usize, usize>,
+}
+
+impl PcgStats {
+    pub(crate) fn record_action_kind(&mut self, kind: &PcgActionKind<'_>) {
+        match kind {
+            PcgActionKind::Expand { .. } => self.expansions += 1,
+            PcgActionKind::Collapse { .. } => self.collapses += 1,
+            PcgActionKind::ReborrowAdded => self.reborrows_created += 1,
+            PcgActionKind::ReborrowExpired => self.reborrows_expired += 1,
+            _ => {}
+        }
+    }
+
+    pub(crate) fn record_join(&mut self, block: BasicBlock) {
+        *self.join_iterations.entry(block.index()).or_insert(0) += 1;
+    }
+
+    pub(crate) fn record_graph_size(&mut self, size: usize) {
+        if size > self.peak_graph_size {
+            self.peak_graph_size = size;
+        }
+    }
+
+    /// Writes this summary as `stats.json` in `dir_path`, alongside the
+    /// visualization output produced by [`crate::run_pcg_with_options`].
+    pub(crate) fn write_json_file(&self, dir_path: &str) {
+        let path = format!("{dir_path}/stats.json");
+        std::fs::write(&path, serde_json::to_string_pretty(self).unwrap())
+            .expect("Failed to write stats.json");
+    }
+}
@@ -17,6 +17,7 @@ pub struct PcgSuccessor<'tcx> {
     block: BasicBlock,
     pub(crate) actions: PcgActions<'tcx>,
     entry_state: Rc<BorrowsState<'tcx>>,
+    is_cleanup: bool,
 }
 
 impl<'tcx> PcgSuccessor<'tcx> {
@@ -32,15 +33,27 @@ impl<'tcx> PcgSuccessor<'tcx> {
     pub fn entry_graph(&self) -> &BorrowsGraph<'tcx> {
         self.entry_state.graph()
     }
+    /// Whether this successor is reached only via unwinding, i.e. `block` is
+    /// a MIR cleanup block (`BasicBlockData::is_cleanup`). Callers that want
+    /// to reason about the normal (non-panicking) control-flow graph
+    /// separately from panic paths can use this to partition
+    /// [`PcgTerminator::succs`](crate::free_pcs::PcgTerminator::succs)
+    /// accordingly, or consult [`crate::PcgOptions::ignore_unwind_paths`] to
+    /// have such successors omitted entirely.
+    pub fn is_cleanup(&self) -> bool {
+        self.is_cleanup
+    }
     pub(crate) fn new(
         block: BasicBlock,
         actions: PcgActions<'tcx>,
         entry_state: Rc<BorrowsState<'tcx>>,
+        is_cleanup: bool,
     ) -> Self {
         Self {
             block,
             actions,
             entry_state,
+            is_cleanup,
         }
     }
 }
@@ -52,6 +65,7 @@ impl<'tcx, 'a> ToJsonWithCompilerCtxt<'tcx, &'a dyn BorrowCheckerInterface<'tcx>
         json!({
             "block": self.block().index(),
             "actions": self.actions.to_json(repacker),
+            "is_cleanup": self.is_cleanup(),
         })
     }
 }
@@ -0,0 +1,77 @@
+// © 2023, ETH Zurich
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use crate::pcg::function_summary::FunctionSummary;
+use crate::rustc_interface::hir::def_id::DefId;
+use crate::rustc_interface::middle::ty::{self, TyCtxt, TypeVisitable, TypeVisitor};
+
+struct SignatureRegions<'tcx> {
+    regions: Vec<ty::Region<'tcx>>,
+}
+
+impl<'tcx> TypeVisitor<TyCtxt<'tcx>> for SignatureRegions<'tcx> {
+    fn visit_region(&mut self, region: ty::Region<'tcx>) {
+        if !self.regions.contains(&region) {
+            self.regions.push(region);
+        }
+    }
+}
+
+fn regions_in<'tcx>(ty: ty::Ty<'tcx>) -> Vec<ty::Region<'tcx>> {
+    let mut visitor = SignatureRegions { regions: vec![] };
+    ty.visit_with(&mut visitor);
+    visitor.regions
+}
+
+/// Approximates a [`FunctionSummary`] for `def_id` purely from its type
+/// signature: argument `i` is included in [`FunctionSummary::borrows_from_args`]
+/// if some region appearing in its type also appears in the return type.
+///
+/// This deliberately never looks at `def_id`'s body (MIR, borrow facts,
+/// or a PCG analysis of it), so it's equally well-defined for a function
+/// that is directly or mutually recursive -- there's no body-shaped fixpoint
+/// to get stuck on, because nothing here depends on one.
+///
+/// The tradeoff is precision: this is the same kind of lifetime-matching
+/// `pcg::visitor::function_call` does when building a call-site's
+/// abstraction, but against the signature's generic region parameters
+/// instead of a specific call site's concrete argument/destination types, so it's
+/// never more precise than (and for a call site PCG analyzes directly,
+/// strictly less precise than) what that per-call-site matching already
+/// produces. This function exists for interprocedural clients that want
+/// an approximate, body-independent summary of an arbitrary function --
+/// including one whose body isn't available to analyze at all, e.g. an
+/// external crate compiled without MIR-for-borrowck -- not to improve
+/// PCG's own call-site abstraction.
+///
+/// A genuinely recursion-aware *dataflow* summary -- one computed by
+/// actually running PCG on `def_id`'s body, with mutually-recursive
+/// strongly-connected components resolved to a fixpoint by re-analyzing
+/// each member against the others' previous-round summary -- is out of
+/// scope here. It would need call-graph construction and a convergence
+/// loop integrated into [`crate::utils::callbacks::run_pcg_on_all_fns`],
+/// a correctness-sensitive change to the top-level driver that needs a
+/// compiler and test suite to validate against, neither of which is
+/// available in this environment.
+pub fn function_summary_from_signature<'tcx>(
+    def_id: DefId,
+    tcx: TyCtxt<'tcx>,
+) -> FunctionSummary {
+    let sig = tcx.fn_sig(def_id).instantiate_identity().skip_binder();
+    let output_regions = regions_in(sig.output());
+    let borrows_from_args = sig
+        .inputs()
+        .iter()
+        .enumerate()
+        .filter(|(_, ty)| {
+            regions_in(**ty)
+                .iter()
+                .any(|region| output_regions.contains(region))
+        })
+        .map(|(i, _)| i)
+        .collect();
+    FunctionSummary::new(borrows_from_args)
+}
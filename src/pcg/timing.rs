@@ -0,0 +1,40 @@
+// © 2023, ETH Zurich
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use std::time::Duration;
+
+use serde_derive::Serialize;
+
+/// A coarse wall-clock breakdown of a single [`crate::run_pcg`] invocation,
+/// gathered alongside the `tracing` spans already covering the same phases
+/// (the `#[tracing::instrument]` attributes on [`super::PcgEngine`]'s
+/// transfer functions and [`super::domain::PcgDomain`]'s `join`), for
+/// callers that want a quick summary without wiring up a tracing
+/// subscriber to collect span durations themselves.
+#[derive(Debug, Clone, Copy, Default, Serialize)]
+pub struct PcgTimings {
+    /// Time spent applying statement/terminator transfer functions.
+    pub transfer_functions: Duration,
+    /// Time spent computing joins between predecessor states.
+    pub joins: Duration,
+    /// Time spent writing visualization output (DOT graphs, JSON, the HTML
+    /// report), if any was requested.
+    pub visualization_io: Duration,
+}
+
+impl PcgTimings {
+    pub(crate) fn record_transfer_function(&mut self, elapsed: Duration) {
+        self.transfer_functions += elapsed;
+    }
+
+    pub(crate) fn record_join(&mut self, elapsed: Duration) {
+        self.joins += elapsed;
+    }
+
+    pub(crate) fn record_visualization_io(&mut self, elapsed: Duration) {
+        self.visualization_io += elapsed;
+    }
+}
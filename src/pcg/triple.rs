@@ -50,6 +50,22 @@ pub(crate) enum PlaceCondition<'tcx> {
     AllocateOrDeallocate(Local),
     Unalloc(Local),
     Return,
+    /// Like `Capability(place, Write)`, but for a `Drop` terminator: if
+    /// `place` has no tracked capability at all (e.g. it was unconditionally
+    /// moved out on every path reaching this drop), there's nothing to drop
+    /// and this is a no-op rather than an error.
+    DropWrite(Place<'tcx>),
+    /// Like `Capability(place, Write)`, but for a move out of `*box_place`
+    /// (where `place = *box_place` and `box_place: Box<_>`): instead of
+    /// giving `place` `Write`, removes `place`'s capability and collapses it
+    /// back into `box_place` with
+    /// [`CapabilityKind::ShallowExclusive`], the same capability a fresh
+    /// `Box::new` starts with. This way, writing back through the box later
+    /// goes through the same [`crate::free_pcs::RepackOp::DerefShallowInit`]
+    /// path used for box initialization, instead of leaving the box
+    /// permanently expanded with a `Write`-only payload place that nothing
+    /// ever re-collapses.
+    BoxDerefMoveWrite(Place<'tcx>),
 }
 
 impl<'tcx> PlaceCondition<'tcx> {
@@ -108,10 +124,19 @@ impl<'tcx> FallableVisitor<'tcx> for TripleWalker<'_, 'tcx> {
                 pre: PlaceCondition::read(place),
                 post: None,
             },
-            Operand::Move(place) => Triple {
-                pre: PlaceCondition::exclusive(place, self.ctxt),
-                post: Some(PlaceCondition::write(place)),
-            },
+            Operand::Move(place) => {
+                let place: Place<'tcx> = place.into();
+                let post = match place.target_place() {
+                    Some(box_place) if box_place.ty(self.ctxt).ty.is_box() => {
+                        PlaceCondition::BoxDerefMoveWrite(place)
+                    }
+                    _ => PlaceCondition::write(place),
+                };
+                Triple {
+                    pre: PlaceCondition::exclusive(place, self.ctxt),
+                    post: Some(post),
+                }
+            }
             Operand::Constant(..) => return Ok(()),
         };
         self.operand_triples.push(triple);
@@ -162,7 +187,7 @@ impl<'tcx> FallableVisitor<'tcx> for TripleWalker<'_, 'tcx> {
             &Len(place) | &Discriminant(place) | &CopyForDeref(place) => {
                 PlaceCondition::read(place)
             }
-            _ => todo!(),
+            _ => return Err(PCGUnsupportedError::UnsupportedStatement.into()),
         };
         tracing::debug!("Pre: {pre:?}");
         self.operand_triples.push(Triple { pre, post: None });
@@ -189,10 +214,23 @@ impl<'tcx> FallableVisitor<'tcx> for TripleWalker<'_, 'tcx> {
             },
             // Looking into `rustc` it seems that `PlaceMention` is effectively ignored.
             PlaceMention(_) => return Ok(()),
+            // `SetDiscriminant` only writes the enum tag, not any field
+            // payload, so requiring `Exclusive` on `place` (and leaving its
+            // capability unchanged afterwards) is already the complete
+            // model: unlike `Assign`/`Deinit` it doesn't invalidate
+            // existing borrows of `place`'s fields, since it doesn't
+            // overwrite them.
             SetDiscriminant { box place, .. } => Triple {
                 pre: PlaceCondition::exclusive(place, self.ctxt),
                 post: None,
             },
+            // `Deinit` makes `place` uninitialized, the same outcome an
+            // `Assign` to it would have, so its capability is taken down to
+            // `Write` here exactly as `Assign`'s is; see
+            // `PcgVisitor::make_overwritten_place_old_and_remove_blocked_edges`
+            // for the accompanying borrow-graph cleanup, which (like the
+            // capability transition here) mirrors `Assign`'s target
+            // handling.
             Deinit(box place) => Triple {
                 pre: PlaceCondition::exclusive(place, self.ctxt),
                 post: Some(PlaceCondition::write(place)),
@@ -205,10 +243,44 @@ impl<'tcx> FallableVisitor<'tcx> for TripleWalker<'_, 'tcx> {
                 pre: PlaceCondition::AllocateOrDeallocate(local),
                 post: Some(PlaceCondition::Unalloc(local)),
             },
+            // `Retag` (Stacked/Tree Borrows re-tagging, meaningful only
+            // under Miri) requires `Exclusive` on `place` since retagging
+            // the pointer it holds needs to observe/replace that exact
+            // pointer value. It has no post-condition: this PCG tracks
+            // borrows by place/region identity, not by an abstract
+            // Stacked-/Tree-Borrows tag, so there's no tag-shaped state
+            // here for a retag to refresh -- `place`'s existing borrow
+            // edges and capability describe the same value before and
+            // after. A model that actually refreshed something would need
+            // its own notion of borrow provenance/tags in parallel to the
+            // region-based one this PCG already has, which is a much
+            // larger, separate feature, not a gap in this statement's
+            // handling specifically.
             Retag(_, box place) => Triple {
                 pre: PlaceCondition::exclusive(place, self.ctxt),
                 post: None,
             },
+            // `copy_nonoverlapping`/`write`/`read`-style intrinsics
+            // (`NonDivergingIntrinsic::CopyNonOverlapping`) and `Assume`
+            // only expose their `src`/`dst`/`count`/assumed-condition
+            // operands here, not a place; `super_statement_fallable`
+            // (called above) already walks those operands and requires
+            // `Read`/`Move` capability on whatever place each one reads
+            // from, exactly like an ordinary operand in any other
+            // statement. There's deliberately no place-level triple for
+            // the statement itself: `src`/`dst` are pointer *values*, and
+            // modeling a transfer between the pointee places they refer to
+            // would require dereferencing them, which `contains_unsafe_deref`
+            // (see `utils::place::Place::contains_unsafe_deref`) always
+            // rejects with `PCGUnsupportedError::DerefUnsafePtr`, and this
+            // PCG has no raw-pointer provenance tracking (the
+            // `TRACK_RAW_POINTERS`/`PlaceCapabilities::escaped` marker
+            // records only that a place's address was taken, not which
+            // local now holds that address) that could otherwise resolve
+            // `src`/`dst` back to the places they point to. So this arm is
+            // intentionally a no-op beyond the operand-level handling
+            // already performed.
+            Intrinsic(_) => return Ok(()),
             _ => return Ok(()),
         };
         self.main_triples.push(t);
@@ -258,7 +330,7 @@ impl<'tcx> FallableVisitor<'tcx> for TripleWalker<'_, 'tcx> {
                 post: Some(PlaceCondition::write(RETURN_PLACE)),
             },
             &Drop { place, .. } => Triple {
-                pre: PlaceCondition::write(place),
+                pre: PlaceCondition::DropWrite(place.into()),
                 post: None,
             },
             &Call { destination, .. } => Triple {
@@ -33,9 +33,18 @@ impl<'tcx> PcgVisitor<'_, '_, 'tcx> {
             Rvalue::Aggregate(
                 box (mir::AggregateKind::Adt(..)
                 | mir::AggregateKind::Tuple
-                | mir::AggregateKind::Array(..)),
+                | mir::AggregateKind::Array(..)
+                | mir::AggregateKind::Closure(..)
+                | mir::AggregateKind::Coroutine(..)
+                | mir::AggregateKind::CoroutineClosure(..)),
                 fields,
             ) => {
+                // Closure/coroutine upvar captures are represented as
+                // aggregate fields just like a struct's, so the same
+                // field-wise region-projection connection soundly models
+                // captures by reference (the capture operand is a place
+                // projecting into
+                // the captured variable).
                 let target: utils::Place<'tcx> = (*target).into();
                 for (field_idx, field) in fields.iter().enumerate() {
                     let operand_place: utils::Place<'tcx> = if let Some(place) = field.place() {
@@ -204,6 +213,15 @@ impl<'tcx> PcgVisitor<'_, '_, 'tcx> {
                     )?;
                 }
             }
+            Rvalue::ThreadLocalRef(def_id) => {
+                // Unlike `&STATIC` (which is a `Operand::Constant`, invisible
+                // to this visitor), `&THREAD_LOCAL`/`&mut THREAD_LOCAL` is a
+                // distinct `Rvalue` with its own root: the static item itself.
+                // Give it one, so the borrow doesn't simply vanish.
+                self.pcg
+                    .borrow
+                    .add_static_borrow(*def_id, target, self.ctxt);
+            }
             _ => {}
         }
         Ok(())
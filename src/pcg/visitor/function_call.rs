@@ -8,6 +8,7 @@ use crate::borrow_pcg::region_projection::{
     PcgRegion, RegionProjection,
     RegionProjectionBaseLike, RegionProjectionLabel,
 };
+use crate::free_pcs::CapabilityKind;
 use crate::pcg::PCGUnsupportedError;
 use crate::rustc_interface::middle::mir::{Location, Operand};
 use crate::utils::display::DisplayWithCompilerCtxt;
@@ -17,18 +18,66 @@ use crate::rustc_interface::data_structures::fx::FxHashSet;
 use crate::rustc_interface::middle::ty::{self};
 use crate::utils::maybe_old::MaybeOldPlace;
 use crate::utils::{self, CompilerCtxt, PlaceSnapshot, SnapshotLocation};
+use std::rc::Rc;
+
+/// `def_path_str`s of functions that move values through `&mut` arguments
+/// without that being visible in their signature (their lifetimes just say
+/// "some region outlives another", not "this moves a value"), handled by
+/// [`PcgVisitor::try_apply_swap_like_model`] below instead of the generic,
+/// lifetime-based abstraction built by the rest of this file.
+const SWAP_LIKE_FNS: &[&str] = &[
+    "std::mem::swap",
+    "core::mem::swap",
+    "std::ptr::swap",
+    "core::ptr::swap",
+];
+
+const REPLACE_LIKE_FNS: &[&str] = &["std::mem::replace", "core::mem::replace"];
 
 fn get_function_data<'tcx>(
     func: &Operand<'tcx>,
     ctxt: CompilerCtxt<'_, 'tcx>,
 ) -> Option<FunctionData<'tcx>> {
     match func.ty(ctxt.body(), ctxt.tcx()).kind() {
-        ty::TyKind::FnDef(def_id, substs) => Some(FunctionData::new(*def_id, substs)),
+        ty::TyKind::FnDef(def_id, substs) => {
+            if ctxt.inline_trivial_getters()
+                && crate::utils::mir_inline::is_trivial_getter(ctxt.tcx(), *def_id)
+            {
+                tracing::debug!(
+                    "{:?} is a trivial getter; modeling it with a FunctionCallAbstraction \
+                     is more conservative than inlining it would be (see \
+                     `PcgOptions::inline_trivial_getters`)",
+                    def_id
+                );
+            }
+            Some(FunctionData::new(*def_id, substs))
+        }
         ty::TyKind::FnPtr(..) => None,
         _ => None,
     }
 }
 
+// A direct call through `Fn`/`FnMut`/`FnOnce` to a closure whose concrete
+// type is visible at the call site (i.e. the common case: the closure was
+// created earlier in the same function, not received as an opaque `F: Fn()`
+// parameter or a type-erased `dyn Fn()`) desugars to a `FnDef` call to the
+// closure's generated `call`/`call_mut`/`call_once` shim, with the closure
+// value itself (of its own concrete `TyKind::Closure` type) as one of
+// `args`. That closure-typed argument already flows through the same
+// `arg_region_projections`/`get_disjoint_lifetime_sets` machinery as any
+// other argument below, and `extract_regions`'s `TyKind::Closure` case (see
+// `borrow_pcg::visitor::extract_regions`) surfaces the regions inside its
+// captured upvars for matching -- so the captured state is already linked to
+// the call's outputs without any closure-specific code here.
+//
+// There isn't a fix available for the two cases where that concrete type
+// genuinely isn't visible: a generic `F: Fn() -> R` bound parameter is
+// analyzed here as just the type parameter `F` (this function's body is
+// analyzed once, generically, not once per instantiation), and a `dyn
+// Fn() -> R` trait object has erased which closure it is entirely. Neither
+// is a gap in this pass; the type information to close them doesn't exist
+// in the MIR this analysis sees.
+
 impl<'tcx> PcgVisitor<'_, '_, 'tcx> {
     #[tracing::instrument(skip(self, func, args, destination))]
     pub(super) fn make_function_call_abstraction(
@@ -63,6 +112,26 @@ impl<'tcx> PcgVisitor<'_, '_, 'tcx> {
         }
         let function_data = get_function_data(func, self.ctxt);
 
+        if function_data.is_none() {
+            self.diagnostics.borrow_mut().record(
+                crate::pcg::diagnostics::DiagnosticCategory::IndirectCallFallback,
+                location,
+                self.ctxt,
+                "call through a function pointer or trait object; falling back to the \
+                 generic function-call abstraction model"
+                    .to_string(),
+            );
+        }
+
+        if let Some(function_data) = &function_data {
+            if self.try_apply_swap_like_model(function_data, args)? {
+                self.pcg
+                    .render_debug_graph(self.ctxt, location, "final borrow_graph");
+                return Ok(());
+            }
+            self.try_apply_replace_model(function_data, args, location)?;
+        }
+
         let path_conditions = self.pcg.borrow.path_conditions.clone();
         let ctxt = self.ctxt;
 
@@ -80,9 +149,65 @@ impl<'tcx> PcgVisitor<'_, '_, 'tcx> {
                 BorrowPcgAction::add_edge(edge, context, false)
             };
 
+        // If the caller registered a hand-written summary for this function
+        // (because its signature doesn't convey the borrow through lifetimes
+        // alone, e.g. `Option::as_mut`), build the abstraction edge directly
+        // from the summarized argument indices instead of deriving it from
+        // `get_disjoint_lifetime_sets` below.
+        if let Some(summary) = function_data.as_ref().and_then(|function_data| {
+            self.function_summaries
+                .as_ref()
+                .and_then(|summaries| summaries.lookup(self.ctxt.tcx(), function_data.def_id()))
+        }) {
+            let inputs: Vec<FunctionCallAbstractionInput<'tcx>> = summary
+                .borrows_from_args
+                .iter()
+                .filter_map(|&i| args.get(i))
+                .filter_map(|arg| arg.place())
+                .flat_map(|mir_place| {
+                    let input_place: utils::Place<'tcx> = mir_place.into();
+                    let input_place = MaybeOldPlace::OldPlace(PlaceSnapshot::new(
+                        input_place,
+                        self.pcg.borrow.get_latest(input_place, self.ctxt),
+                    ));
+                    input_place.region_projections(self.ctxt)
+                })
+                .collect();
+            let outputs: Vec<RegionProjection<MaybeOldPlace<'tcx>>> = destination
+                .region_projections(self.ctxt)
+                .iter()
+                .map(|rp| (*rp).into())
+                .collect();
+            if !inputs.is_empty() && !outputs.is_empty() {
+                self.record_and_apply_action(
+                    mk_create_edge_action(
+                        inputs,
+                        outputs,
+                        "Function call: edges from hand-written function summary",
+                    )
+                    .into(),
+                )?;
+            }
+            self.pcg
+                .render_debug_graph(self.ctxt, location, "final borrow_graph");
+            return Ok(());
+        }
+
         // The versions of the region projections for the function inputs just
         // before they were moved out, labelled with their last modification
-        // time
+        // time.
+        //
+        // `region_projections` (via `extract_regions`'s recursive
+        // `TypeVisitor`) already walks every nesting depth of the argument's
+        // type, not just its outermost reference, so a multiply-nested
+        // input like `&mut &mut T` or `&mut Vec<&mut T>` yields one
+        // projection per region found at any depth -- the matching loop
+        // below (over `disjoint_lifetime_sets`) then connects each of them
+        // independently. This is unlike the narrow, single-level
+        // `project_deref` calls in `try_apply_swap_like_model` and
+        // `try_apply_replace_model` above, which deliberately only model
+        // the flat `&mut T, &mut T` / `&mut T` signatures of the specific
+        // stdlib functions they special-case.
         let arg_region_projections = args
             .iter()
             .filter_map(|arg| arg.place())
@@ -97,7 +222,24 @@ impl<'tcx> PcgVisitor<'_, '_, 'tcx> {
             .collect::<Vec<_>>();
 
         // The subset of the argument region projections that are nested
-        // (and labelled, since the set of borrows inside may be modified)
+        // (and labelled, since the set of borrows inside may be modified).
+        //
+        // This is already variance-aware: [`RegionProjection::is_invariant_in_type`]
+        // (via `TyVarianceVisitor`) walks the argument's type with
+        // `tcx.variances_of`, and only flags a region as invariant (e.g.
+        // `Cell<&'a T>`, or anything under a `&mut`, which is invariant
+        // regardless of what `variances_of` says about the pointee) when
+        // it's genuinely not covariant. Labelling such a projection here
+        // gives it a distinct "before the call" identity from its
+        // "after the call" one below, which is effectively the
+        // bidirectional treatment invariance needs: the old and new sets of
+        // borrows it may contain are tracked as separate nodes instead of
+        // being silently conflated. Contravariant positions (which in
+        // practice only arise inside a function pointer or `Fn*` type, e.g.
+        // `fn(&'a T)`) never reach this loop at all: `extract_regions`
+        // (`src/borrow_pcg/visitor/mod.rs`) deliberately doesn't recurse
+        // into `TyKind::FnPtr`, so no region projection -- and hence no
+        // abstraction edge -- is ever created for them.
         let mut labelled_rps = FxHashSet::default();
         for arg in arg_region_projections.iter() {
             if arg.is_invariant_in_type(self.ctxt) {
@@ -142,7 +284,20 @@ impl<'tcx> PcgVisitor<'_, '_, 'tcx> {
             })
             .collect::<Vec<_>>();
 
-        let disjoint_lifetime_sets = get_disjoint_lifetime_sets(&arg_region_projections, self.ctxt);
+        // The callee's signature (and hence its disjoint lifetime classes)
+        // only depends on `(def_id, substs)`, so repeated visits of this
+        // call site during the dataflow fixpoint (e.g. one inside a loop
+        // body) can reuse the result rather than re-running the pairwise
+        // `same_region` queries each time.
+        let disjoint_lifetime_sets = if let Some(function_data) = &function_data {
+            self.function_call_cache.get_or_compute(
+                function_data.def_id(),
+                function_data.substs(),
+                || get_disjoint_lifetime_sets(&arg_region_projections, self.ctxt),
+            )
+        } else {
+            Rc::new(get_disjoint_lifetime_sets(&arg_region_projections, self.ctxt))
+        };
         for ls in disjoint_lifetime_sets.iter() {
             let this_region = ls.iter().next().unwrap();
             let inputs: Vec<FunctionCallAbstractionInput<'tcx>> = source_arg_projections
@@ -181,6 +336,117 @@ impl<'tcx> PcgVisitor<'_, '_, 'tcx> {
             .render_debug_graph(self.ctxt, location, "final borrow_graph");
         Ok(())
     }
+
+    /// If `function_data` is one of [`SWAP_LIKE_FNS`], exchanges the
+    /// capabilities and `latest` snapshots of its first two argument places
+    /// (dereferenced, since they're taken by `&mut` reference) and returns
+    /// `true`, so the caller can skip the generic lifetime-based abstraction
+    /// entirely: a swap doesn't introduce any new borrow, it just relabels
+    /// which place holds which value.
+    ///
+    /// Returns `false` (without modifying anything) if `function_data` isn't
+    /// a recognized swap-like function, or its arguments aren't two `&mut`
+    /// places, so the caller falls back to the default handling.
+    fn try_apply_swap_like_model(
+        &mut self,
+        function_data: &FunctionData<'tcx>,
+        args: &[&Operand<'tcx>],
+    ) -> Result<bool, PcgError> {
+        let def_path = self.ctxt.tcx().def_path_str(function_data.def_id());
+        if !SWAP_LIKE_FNS.contains(&def_path.as_str()) {
+            return Ok(false);
+        }
+        let (Some(a), Some(b)) = (
+            args.first().and_then(|arg| arg.place()),
+            args.get(1).and_then(|arg| arg.place()),
+        ) else {
+            return Ok(false);
+        };
+        let a: utils::Place<'tcx> = a.into();
+        let b: utils::Place<'tcx> = b.into();
+        if !a.is_ref(self.ctxt) || !b.is_ref(self.ctxt) {
+            return Ok(false);
+        }
+        let a = a.project_deref(self.ctxt);
+        let b = b.project_deref(self.ctxt);
+
+        let latest_a = self.pcg.borrow.get_latest(a, self.ctxt);
+        let latest_b = self.pcg.borrow.get_latest(b, self.ctxt);
+        self.record_and_apply_action(
+            BorrowPcgAction::set_latest(a, latest_b, "swap-like call: exchange latest snapshots")
+                .into(),
+        )?;
+        self.record_and_apply_action(
+            BorrowPcgAction::set_latest(b, latest_a, "swap-like call: exchange latest snapshots")
+                .into(),
+        )?;
+
+        let cap_a = self.pcg.capabilities.get(a);
+        let cap_b = self.pcg.capabilities.get(b);
+        match cap_b {
+            Some(cap) => {
+                self.pcg.capabilities.insert(a, cap);
+            }
+            None => {
+                self.pcg.capabilities.remove(a);
+            }
+        }
+        match cap_a {
+            Some(cap) => {
+                self.pcg.capabilities.insert(b, cap);
+            }
+            None => {
+                self.pcg.capabilities.remove(b);
+            }
+        }
+        Ok(true)
+    }
+
+    /// If `function_data` is [`REPLACE_LIKE_FNS`], treats its first argument
+    /// place (dereferenced) like an ordinary assignment target: `mem::replace`
+    /// overwrites it with `src` just as surely as an `Assign` statement would,
+    /// which the generic lifetime-based abstraction below doesn't otherwise
+    /// capture (it only ever adds borrow-flow edges, it never gives a place a
+    /// fresh capability or `latest` snapshot the way
+    /// [`Self::assign_post_main`] does).
+    ///
+    /// This only corrects the written-to place; it deliberately leaves
+    /// precisely connecting the call's return value to `dest`'s old contents
+    /// to the generic abstraction below; modeling that properly would mean
+    /// teaching it that this particular return value aliases an argument's
+    /// *old* value rather than being borrowed from its *current* one, which
+    /// isn't something we can check without also reworking how the generic
+    /// path assigns `destination`'s capability and `latest` snapshot -- not
+    /// safe to do without a compiler run to verify against.
+    fn try_apply_replace_model(
+        &mut self,
+        function_data: &FunctionData<'tcx>,
+        args: &[&Operand<'tcx>],
+        location: Location,
+    ) -> Result<(), PcgError> {
+        let def_path = self.ctxt.tcx().def_path_str(function_data.def_id());
+        if !REPLACE_LIKE_FNS.contains(&def_path.as_str()) {
+            return Ok(());
+        }
+        let Some(dest) = args.first().and_then(|arg| arg.place()) else {
+            return Ok(());
+        };
+        let dest: utils::Place<'tcx> = dest.into();
+        if !dest.is_ref(self.ctxt) {
+            return Ok(());
+        }
+        let dest = dest.project_deref(self.ctxt);
+        self.record_and_apply_action(
+            BorrowPcgAction::set_latest(
+                dest,
+                SnapshotLocation::After(location),
+                "mem::replace: dest is overwritten with src",
+            )
+            .into(),
+        )?;
+        self.pcg.capabilities.insert(dest, CapabilityKind::Exclusive);
+        Ok(())
+    }
 }
 
 fn get_disjoint_lifetime_sets<'tcx, T: RegionProjectionBaseLike<'tcx>>(
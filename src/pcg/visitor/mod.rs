@@ -1,7 +1,7 @@
-use crate::action::{BorrowPcgAction, PcgAction};
+use crate::action::{BorrowPcgAction, OwnedPcgAction, PcgAction};
 use crate::borrow_pcg::action::MakePlaceOldReason;
 use crate::borrow_pcg::borrow_pcg_edge::{BorrowPcgEdge, BorrowPcgEdgeLike, LocalNode};
-use crate::borrow_pcg::borrow_pcg_expansion::{BorrowPcgExpansion, PlaceExpansion};
+use crate::borrow_pcg::borrow_pcg_expansion::BorrowPcgExpansion;
 use crate::borrow_pcg::edge::kind::BorrowPcgEdgeKind;
 use crate::borrow_pcg::edge::outlives::{BorrowFlowEdge, BorrowFlowEdgeKind};
 use crate::borrow_pcg::region_projection::{PcgRegion, RegionProjection, RegionProjectionLabel};
@@ -19,8 +19,12 @@ use crate::utils::visitor::FallableVisitor;
 use crate::utils::{self, CompilerCtxt, HasPlace, Place, SnapshotLocation};
 
 use super::{
-    AnalysisObject, EvalStmtPhase, PCGNode, PCGNodeLike, PCGUnsupportedError, Pcg, PcgError,
+    diagnostics::PcgDiagnostics, function_call_cache::FunctionCallAbstractionCache,
+    AnalysisObject, EvalStmtPhase, FunctionSummaryRegistry, PCGNode, PCGNodeLike,
+    PCGUnsupportedError, Pcg, PcgError,
 };
+use std::cell::RefCell;
+use std::rc::Rc;
 
 mod assign;
 mod function_call;
@@ -38,6 +42,9 @@ pub(crate) struct PcgVisitor<'pcg, 'mir, 'tcx> {
     tw: &'pcg TripleWalker<'mir, 'tcx>,
     location: Location,
     debug_data: Option<PcgDebugData>,
+    function_summaries: Option<Rc<FunctionSummaryRegistry>>,
+    function_call_cache: Rc<FunctionCallAbstractionCache<'tcx>>,
+    diagnostics: Rc<RefCell<PcgDiagnostics>>,
 }
 
 impl<'pcg, 'mir, 'tcx> PcgVisitor<'pcg, 'mir, 'tcx> {
@@ -75,6 +82,7 @@ impl<'pcg, 'mir, 'tcx> PcgVisitor<'pcg, 'mir, 'tcx> {
         Ok(())
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub(crate) fn visit(
         pcg: &'pcg mut Pcg<'tcx>,
         ctxt: CompilerCtxt<'mir, 'tcx>,
@@ -83,12 +91,26 @@ impl<'pcg, 'mir, 'tcx> PcgVisitor<'pcg, 'mir, 'tcx> {
         analysis_object: AnalysisObject<'_, 'tcx>,
         location: Location,
         debug_data: Option<PcgDebugData>,
+        function_summaries: Option<Rc<FunctionSummaryRegistry>>,
+        function_call_cache: Rc<FunctionCallAbstractionCache<'tcx>>,
+        diagnostics: Rc<RefCell<PcgDiagnostics>>,
     ) -> Result<PcgActions<'tcx>, PcgError> {
-        let visitor = Self::new(pcg, ctxt, tw, phase, location, debug_data);
+        let visitor = Self::new(
+            pcg,
+            ctxt,
+            tw,
+            phase,
+            location,
+            debug_data,
+            function_summaries,
+            function_call_cache,
+            diagnostics,
+        );
         let actions = visitor.apply(analysis_object)?;
         Ok(actions)
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub(crate) fn new(
         pcg: &'pcg mut Pcg<'tcx>,
         ctxt: CompilerCtxt<'mir, 'tcx>,
@@ -96,6 +118,9 @@ impl<'pcg, 'mir, 'tcx> PcgVisitor<'pcg, 'mir, 'tcx> {
         phase: EvalStmtPhase,
         location: Location,
         debug_data: Option<PcgDebugData>,
+        function_summaries: Option<Rc<FunctionSummaryRegistry>>,
+        function_call_cache: Rc<FunctionCallAbstractionCache<'tcx>>,
+        diagnostics: Rc<RefCell<PcgDiagnostics>>,
     ) -> Self {
         Self {
             pcg,
@@ -105,6 +130,9 @@ impl<'pcg, 'mir, 'tcx> PcgVisitor<'pcg, 'mir, 'tcx> {
             tw,
             location,
             debug_data,
+            function_summaries,
+            function_call_cache,
+            diagnostics,
         }
     }
 }
@@ -193,6 +221,29 @@ impl<'tcx> FallableVisitor<'tcx> for PcgVisitor<'_, '_, 'tcx> {
         if matches!(rvalue, Rvalue::Ref(_, mir::BorrowKind::Fake(_), _)) {
             return Ok(());
         }
+        if *crate::utils::TRACK_RAW_POINTERS
+            && let Rvalue::RawPtr(_, place) = rvalue
+        {
+            let place: Place<'tcx> = (*place).into();
+            self.pcg.capabilities.mark_escaped(place);
+            self.diagnostics.borrow_mut().record(
+                super::diagnostics::DiagnosticCategory::RawPointerEscape,
+                self.location,
+                self.ctxt,
+                format!(
+                    "address of {} taken; no longer tracking reads/writes through it",
+                    place.to_short_string(self.ctxt)
+                ),
+            );
+        }
+        if self.phase == EvalStmtPhase::PreMain
+            && let Rvalue::Len(place) | Rvalue::Discriminant(place) = rvalue
+        {
+            let place: Place<'tcx> = (*place).into();
+            self.record_and_apply_action(
+                OwnedPcgAction::new(RepackOp::RequireRead(place), None).into(),
+            )?;
+        }
         self.super_rvalue_fallable(rvalue, location)?;
         Ok(())
     }
@@ -260,6 +311,19 @@ impl<'tcx> PcgVisitor<'_, '_, 'tcx> {
         Ok(())
     }
 
+    /// For every node `edge` was the last thing blocking, restores its
+    /// capability: [`CapabilityKind::Read`] if the node is behind a shared
+    /// reference, [`CapabilityKind::Exclusive`] otherwise. This already
+    /// covers shared-borrow expiry as well as mutable-borrow expiry -- the
+    /// branch is purely on whether the *unblocked place* is behind a shared
+    /// ref, not on whether `edge` itself came from a `&` or `&mut` borrow,
+    /// so the last shared borrow of a place expiring emits a
+    /// [`PcgAction::restore_capability`] here the same way a mutable borrow
+    /// expiring does. Because [`Self::remove_edge_and_perform_associated_state_updates`]
+    /// calls this once per removed edge and [`Self::pack_old_and_dead_borrow_leaves`]
+    /// removes edges leaf-first, a reborrow chain restores capability one
+    /// link at a time as each link expires, rather than needing special
+    /// multi-hop handling.
     fn update_unblocked_node_capabilities_and_remove_placeholder_projections(
         &mut self,
         edge: &impl BorrowPcgEdgeLike<'tcx>,
@@ -455,84 +519,10 @@ impl<'tcx> PcgVisitor<'_, '_, 'tcx> {
 
     #[tracing::instrument(skip(self, action))]
     fn record_and_apply_action(&mut self, action: PcgAction<'tcx>) -> Result<bool, PcgError> {
-        let result =
-            match &action {
-                PcgAction::Borrow(action) => self.pcg.borrow.apply_action(
-                    action.clone(),
-                    &mut self.pcg.capabilities,
-                    self.ctxt,
-                )?,
-                PcgAction::Owned(owned_action) => match owned_action.kind {
-                    RepackOp::RegainLoanedCapability(place, capability_kind) => self
-                        .pcg
-                        .capabilities
-                        .insert((*place).into(), capability_kind),
-                    RepackOp::Expand(expand) => {
-                        let target_places = expand.target_places(self.ctxt);
-                        let capability_projections =
-                            self.pcg.owned.locals_mut()[expand.local()].get_allocated_mut();
-                        capability_projections.insert_expansion(
-                            expand.from,
-                            PlaceExpansion::from_places(target_places.clone(), self.ctxt),
-                        );
-                        let source_cap = if expand.capability.is_read() {
-                            expand.capability
-                        } else {
-                            self.pcg.capabilities.get(expand.from).unwrap()
-                        };
-                        tracing::debug!("source_cap for {:?}: {:?}", owned_action, source_cap);
-                        for target_place in target_places {
-                            self.pcg.capabilities.insert(target_place, source_cap);
-                        }
-                        // if source_cap > *capability {
-                        //     self.pcg.capabilities.insert((*to).into(), *capability);
-                        // }
-                        if expand.capability.is_read() {
-                            self.pcg
-                                .capabilities
-                                .insert(expand.from, CapabilityKind::Read);
-                        } else {
-                            self.pcg.capabilities.remove(expand.from);
-                        }
-                        true
-                    }
-                    RepackOp::DerefShallowInit(from, to) => {
-                        let target_places = from.expand_one_level(to, self.ctxt)?.expansion();
-                        let capability_projections =
-                            self.pcg.owned.locals_mut()[from.local].get_allocated_mut();
-                        capability_projections.insert_expansion(
-                            from,
-                            PlaceExpansion::from_places(target_places.clone(), self.ctxt),
-                        );
-                        for target_place in target_places {
-                            self.pcg
-                                .capabilities
-                                .insert(target_place, CapabilityKind::Read);
-                        }
-                        true
-                    }
-                    RepackOp::Collapse(collapse) => {
-                        let capability_projections =
-                            self.pcg.owned.locals_mut()[collapse.local()].get_allocated_mut();
-                        let expansion_places = collapse.expansion_places(self.ctxt);
-                        let retained_cap = expansion_places.iter().fold(
-                            CapabilityKind::Exclusive,
-                            |acc, place| match self.pcg.capabilities.remove(*place) {
-                                Some(cap) => acc.minimum(cap).unwrap_or(CapabilityKind::Write),
-                                None => acc,
-                            },
-                        );
-                        self.pcg
-                            .capabilities
-                            .insert(collapse.to, retained_cap);
-                        capability_projections.expansions.remove(&collapse.to);
-                        true
-                    }
-                    _ => unreachable!(),
-                },
-            };
+        let result = self.pcg.apply_action(&action, self.ctxt)?;
         self.pcg.borrow.graph.render_debug_graph(
             self.ctxt,
+            Some(self.location.block),
             &format!("after {}", action.debug_line(self.ctxt)),
         );
         generate_dot_graph(
@@ -1,10 +1,10 @@
 use super::PcgVisitor;
 
-use crate::action::BorrowPcgAction;
+use crate::action::{BorrowPcgAction, OwnedPcgAction};
 use crate::borrow_pcg::action::MakePlaceOldReason;
 use crate::borrow_pcg::borrow_pcg_edge::BorrowPcgEdgeLike;
 use crate::borrow_pcg::edge::kind::BorrowPcgEdgeKind;
-use crate::free_pcs::CapabilityKind;
+use crate::free_pcs::{CapabilityKind, RepackOp};
 use crate::pcg_validity_assert;
 use crate::rustc_interface::middle::mir::{Statement, StatementKind};
 
@@ -14,6 +14,51 @@ use crate::utils::{self};
 use super::{EvalStmtPhase, PcgError};
 
 impl<'tcx> PcgVisitor<'_, '_, 'tcx> {
+    /// `target` is about to be overwritten with a value that has nothing to
+    /// do with whatever it held before (an `Assign`'s target, or a
+    /// `Deinit`'d place), so any references to it should be made old, and
+    /// any edges blocked by one of its region projections should be
+    /// removed, since they describe borrows of a value that no longer
+    /// exists after this statement.
+    fn make_overwritten_place_old_and_remove_blocked_edges(
+        &mut self,
+        target: utils::Place<'tcx>,
+        context: &'static str,
+    ) -> Result<(), PcgError> {
+        if target.is_ref(self.ctxt) && self.pcg.borrow.graph().contains(target, self.ctxt) {
+            // The permission to the target may have been Read originally.
+            // Now, because it's been made old, the non-old place should be a leaf,
+            // and its permission should be Exclusive.
+            if self.pcg.capabilities.get(target) == Some(CapabilityKind::Read) {
+                self.record_and_apply_action(
+                    BorrowPcgAction::restore_capability(
+                        target,
+                        CapabilityKind::Exclusive,
+                        "restore capability to exclusive before overwrite",
+                    )
+                    .into(),
+                )?;
+            }
+        }
+        for rp in target.region_projections(self.ctxt).into_iter() {
+            let blocked_edges = self
+                .pcg
+                .borrow
+                .graph()
+                .edges_blocked_by(rp.into(), self.ctxt)
+                .map(|edge| edge.to_owned_edge())
+                .collect::<Vec<_>>();
+            for edge in blocked_edges {
+                let should_remove =
+                    !matches!(edge.kind(), BorrowPcgEdgeKind::BorrowPcgExpansion(_));
+                if should_remove {
+                    self.remove_edge_and_perform_associated_state_updates(edge, context)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
     pub(crate) fn perform_statement_actions(
         &mut self,
         statement: &Statement<'tcx>,
@@ -31,27 +76,7 @@ impl<'tcx> PcgVisitor<'_, '_, 'tcx> {
                     }
                     StatementKind::Assign(box (target, _)) => {
                         let target: utils::Place<'tcx> = (*target).into();
-                        // Any references to target should be made old because it
-                        // will be overwritten in the assignment.
-                        if target.is_ref(self.ctxt)
-                            && self.pcg.borrow.graph().contains(target, self.ctxt)
-                        {
-                            // The permission to the target may have been Read originally.
-                            // Now, because it's been made old, the non-old place should be a leaf,
-                            // and its permission should be Exclusive.
-                            if self.pcg.capabilities.get(target)
-                                == Some(CapabilityKind::Read)
-                            {
-                                self.record_and_apply_action(
-                                    BorrowPcgAction::restore_capability(
-                                        target,
-                                        CapabilityKind::Exclusive,
-                                        "Assign: restore capability to exclusive",
-                                    )
-                                    .into(),
-                                )?;
-                            }
-                        }
+                        self.make_overwritten_place_old_and_remove_blocked_edges(target, "Assign")?;
 
                         if !target.is_owned(self.ctxt) {
                             if let Some(target_cap) = self.pcg.capabilities.get(target) {
@@ -83,24 +108,17 @@ impl<'tcx> PcgVisitor<'_, '_, 'tcx> {
                                 );
                             }
                         }
-                        for rp in target.region_projections(self.ctxt).into_iter() {
-                            let blocked_edges = self
-                                .pcg
-                                .borrow
-                                .graph()
-                                .edges_blocked_by(rp.into(), self.ctxt)
-                                .map(|edge| edge.to_owned_edge())
-                                .collect::<Vec<_>>();
-                            for edge in blocked_edges {
-                                let should_remove = !matches!(
-                                    edge.kind(),
-                                    BorrowPcgEdgeKind::BorrowPcgExpansion(_)
-                                );
-                                if should_remove {
-                                    self.remove_edge_and_perform_associated_state_updates(edge, "Assign")?;
-                                }
-                            }
-                        }
+                    }
+                    // `Deinit` marks `place` as uninitialized, the same
+                    // outcome an `Assign` to it would have (its capability
+                    // is taken down to `Write` by the statement's triple,
+                    // see `pcg::triple`); any borrows through `place` are
+                    // invalidated the same way, so it gets the same
+                    // make-old-and-unblock treatment as an overwriting
+                    // `Assign`'s target.
+                    StatementKind::Deinit(box place) => {
+                        let place: utils::Place<'tcx> = (*place).into();
+                        self.make_overwritten_place_old_and_remove_blocked_edges(place, "Deinit")?;
                     }
                     _ => {}
                 }
@@ -118,8 +136,26 @@ impl<'tcx> PcgVisitor<'_, '_, 'tcx> {
         statement: &Statement<'tcx>,
     ) -> Result<(), PcgError> {
         assert!(self.phase == EvalStmtPhase::PostMain);
-        if let StatementKind::Assign(box (target, rvalue)) = &statement.kind {
-            self.assign_post_main((*target).into(), rvalue)?;
+        match &statement.kind {
+            StatementKind::Assign(box (target, rvalue)) => {
+                self.assign_post_main((*target).into(), rvalue)?;
+            }
+            // By this point `ensure_triple` has already applied the
+            // `PlaceCondition::AllocateOrDeallocate`/`Unalloc` post-condition
+            // of the statement's triple (see `pcg::triple`), so the local's
+            // `CapabilityLocal` has already transitioned; these actions just
+            // surface that transition to consumers.
+            StatementKind::StorageLive(local) => {
+                self.record_and_apply_action(
+                    OwnedPcgAction::new(RepackOp::Allocate(*local), None).into(),
+                )?;
+            }
+            StatementKind::StorageDead(local) => {
+                self.record_and_apply_action(
+                    OwnedPcgAction::new(RepackOp::Deallocate(*local), None).into(),
+                )?;
+            }
+            _ => {}
         }
         Ok(())
     }
@@ -25,6 +25,24 @@ impl<'tcx> PcgVisitor<'_, '_, 'tcx> {
                 }
                 self.obtain(place, ObtainType::Capability(capability))?;
             }
+            PlaceCondition::DropWrite(place) => {
+                if place.contains_unsafe_deref(self.ctxt) {
+                    return Err(PcgError::unsupported(PCGUnsupportedError::DerefUnsafePtr));
+                }
+                // `place` may currently be expanded into live child places (each
+                // holding its own capability, none at `place` itself), so an
+                // exact-key lookup in `capabilities` can't tell "moved out" from
+                // "expanded". Check the whole subtree rooted at `place` for a live
+                // leaf instead; `obtain` collapses it back and computes the
+                // capability for `place` itself.
+                let has_live_leaf = !self
+                    .pcg
+                    .leaf_places_where(|leaf| place.is_prefix(leaf), self.ctxt)
+                    .is_empty();
+                if has_live_leaf {
+                    self.obtain(place, ObtainType::Capability(CapabilityKind::Write))?;
+                }
+            }
             PlaceCondition::RemoveCapability(place) => {
                 self.pcg.capabilities.remove(place);
             }
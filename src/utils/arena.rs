@@ -1,4 +1,12 @@
 use std::rc::Rc;
 
-
+/// An `Rc` allocated in `A` (typically a bump arena supplied to
+/// [`crate::run_pcg_with_options`]). [`crate::pcg::domain::PcgDomainData`]
+/// uses this for each of a statement's four [`crate::pcg::EvalStmtPhase`]
+/// snapshots, so they share structure via `Rc::make_mut`-style
+/// copy-on-write rather than each phase holding a fully independent clone:
+/// advancing to the next phase is just cloning the `Rc` (cheap) until that
+/// phase's state is actually mutated, at which point
+/// `ArenaRef::make_mut` clones the underlying value only if it's still
+/// shared with another phase.
 pub type ArenaRef<T, A> = Rc<T, A>;
@@ -3,10 +3,13 @@ use std::{
     collections::{BTreeMap, BTreeSet},
     fs::File,
     io::Write,
+    path::{Path, PathBuf},
 };
 
 use bumpalo::Bump;
 use derive_more::From;
+use rayon::prelude::*;
+use serde_derive::Serialize;
 use tracing::info;
 
 use crate::{
@@ -33,7 +36,7 @@ use crate::{
         session::Session,
         span::SpanSnippetError,
     },
-    utils::MAX_BASIC_BLOCKS,
+    utils::{MAX_BASIC_BLOCKS, PARALLEL},
     PcgOutput,
 };
 
@@ -45,7 +48,7 @@ use crate::visualization::bc_facts_graph::{
     region_inference_outlives, subset_anywhere, subset_at_location, RegionPrettyPrinter,
 };
 
-use super::{env_feature_enabled, CompilerCtxt, Place};
+use super::{env_feature_enabled, incremental, CompilerCtxt, Place};
 
 pub struct PcgCallbacks;
 
@@ -100,7 +103,14 @@ thread_local! {
 }
 
 pub(crate) fn mir_borrowck<'tcx>(tcx: TyCtxt<'tcx>, def_id: LocalDefId) -> MirBorrowck<'tcx> {
-    let consumer_opts = borrowck::ConsumerOptions::PoloniusInputFacts;
+    // Only request the (expensive, forked-toolchain-only) Polonius input
+    // facts when actually running with the Polonius backend; otherwise stick
+    // to the NLL `RegionInferenceContext`, which stock rustc can produce.
+    let consumer_opts = if env_feature_enabled("PCG_POLONIUS").unwrap_or(false) {
+        borrowck::ConsumerOptions::PoloniusInputFacts
+    } else {
+        borrowck::ConsumerOptions::RegionInferenceContext
+    };
     tracing::debug!(
         "Start mir_borrowck for {}",
         tcx.def_path_str(def_id.to_def_id())
@@ -175,6 +185,42 @@ fn is_primary_crate() -> bool {
     std::env::var("CARGO_PRIMARY_PACKAGE").is_ok()
 }
 
+/// The outcome of running PCG on a single function, as recorded in
+/// `function_reports.json` (written alongside `functions.json` when
+/// visualization output is enabled). This is what `pcg-corpus` aggregates
+/// across a crate corpus to track analysis coverage: a function going from
+/// `Success` to `Unsupported`/`Error` between runs is a regression, and the
+/// `duration_ms` lets slow functions be spotted without re-running under a
+/// profiler.
+#[derive(Serialize)]
+struct FunctionReport {
+    item_name: String,
+    outcome: FunctionOutcome,
+    duration_ms: u128,
+}
+
+#[derive(Serialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+enum FunctionOutcome {
+    Success,
+    Unsupported { message: String },
+    Error { message: String },
+}
+
+impl From<Option<&pcg::PcgError>> for FunctionOutcome {
+    fn from(err: Option<&pcg::PcgError>) -> Self {
+        match err.map(|e| &e.kind) {
+            None => FunctionOutcome::Success,
+            Some(pcg::PCGErrorKind::Unsupported(e)) => FunctionOutcome::Unsupported {
+                message: format!("{e:?}"),
+            },
+            Some(pcg::PCGErrorKind::Internal(e)) => FunctionOutcome::Error {
+                message: format!("{e:?}"),
+            },
+        }
+    }
+}
+
 /// # Safety
 ///
 /// Functions bodies stored in `BODIES` must come from the same `tcx`.
@@ -195,6 +241,7 @@ pub(crate) unsafe fn run_pcg_on_all_fns(tcx: TyCtxt<'_>, polonius: bool) {
     }
 
     let mut item_names = vec![];
+    let mut function_reports = vec![];
 
     let user_specified_vis_dir = std::env::var("PCG_VISUALIZATION_DATA_DIR");
     let vis_dir: Option<&str> = if env_feature_enabled("PCG_VISUALIZATION").unwrap_or(false) {
@@ -221,45 +268,58 @@ pub(crate) unsafe fn run_pcg_on_all_fns(tcx: TyCtxt<'_>, polonius: bool) {
         }
     }
 
-    for def_id in hir_body_owners(tcx) {
-        let kind = tcx.def_kind(def_id);
-        if !matches!(kind, DefKind::Fn | DefKind::AssocFn) {
-            continue;
-        }
-        let item_name = tcx.def_path_str(def_id.to_def_id()).to_string();
-        if let Ok(function) = std::env::var("PCG_CHECK_FUNCTION")
-            && function != item_name
-        {
-            tracing::debug!(
-                "Skipping function: {item_name} because PCG_CHECK_FUNCTION is set to {function}"
-            );
-            continue;
-        }
-        if let Ok(function) = std::env::var("PCG_SKIP_FUNCTION")
-            && function == item_name
-        {
-            tracing::info!(
-                "Skipping function: {item_name} because PCG_SKIP_FUNCTION is set to {function}"
+    let bodies_to_check = collect_bodies_to_check(tcx);
+
+    if *PARALLEL {
+        function_reports = bodies_to_check
+            .par_iter()
+            .map(|(def_id, item_name, body)| {
+                info!(
+                    "{}Running PCG on function: {} with {} basic blocks",
+                    cargo_crate_name().map_or("".to_string(), |name| format!("{name}: ")),
+                    item_name,
+                    body.body.basic_blocks.len()
+                );
+                let start = std::time::Instant::now();
+                let err = run_pcg_on_fn(*def_id, body, tcx, polonius, vis_dir, None);
+                FunctionReport {
+                    item_name: item_name.clone(),
+                    outcome: err.as_ref().into(),
+                    duration_ms: start.elapsed().as_millis(),
+                }
+            })
+            .collect();
+        item_names.extend(function_reports.iter().map(|r| r.item_name.clone()));
+    } else {
+        for (def_id, item_name, body) in &bodies_to_check {
+            info!(
+                "{}Running PCG on function: {} with {} basic blocks",
+                cargo_crate_name().map_or("".to_string(), |name| format!("{name}: ")),
+                item_name,
+                body.body.basic_blocks.len()
             );
-            continue;
-        }
-        let body = take_stored_body(tcx, def_id);
-
-        if !should_check_body(&body.body) {
-            continue;
+            tracing::info!("Path: {:?}", body.body.span);
+            tracing::debug!("Number of basic blocks: {}", body.body.basic_blocks.len());
+            tracing::debug!("Number of locals: {}", body.body.local_decls.len());
+            let start = std::time::Instant::now();
+            let err = run_pcg_on_fn(*def_id, body, tcx, polonius, vis_dir, None);
+            function_reports.push(FunctionReport {
+                item_name: item_name.clone(),
+                outcome: err.as_ref().into(),
+                duration_ms: start.elapsed().as_millis(),
+            });
+            item_names.push(item_name.clone());
         }
+    }
 
-        info!(
-            "{}Running PCG on function: {} with {} basic blocks",
-            cargo_crate_name().map_or("".to_string(), |name| format!("{name}: ")),
-            item_name,
-            body.body.basic_blocks.len()
+    if let Some(dir_path) = &vis_dir {
+        let reports_file_path = format!("{dir_path}/function_reports.json");
+        let reports_json = crate::output::versioned(
+            serde_json::to_value(&function_reports)
+                .expect("Failed to serialize function reports to JSON"),
         );
-        tracing::info!("Path: {:?}", body.body.span);
-        tracing::debug!("Number of basic blocks: {}", body.body.basic_blocks.len());
-        tracing::debug!("Number of locals: {}", body.body.local_decls.len());
-        run_pcg_on_fn(def_id, &body, tcx, polonius, vis_dir, None);
-        item_names.push(item_name);
+        std::fs::write(&reports_file_path, reports_json.to_string())
+            .expect("Failed to write function reports to JSON file");
     }
 
     if let Some(dir_path) = &vis_dir {
@@ -278,6 +338,70 @@ pub(crate) unsafe fn run_pcg_on_all_fns(tcx: TyCtxt<'_>, polonius: bool) {
     }
 }
 
+/// Gathers every local function body that should be checked, applying the
+/// `PCG_CHECK_FUNCTION`/`PCG_SKIP_FUNCTION` filters. This has to run on the
+/// current thread, since `take_stored_body` reads from the thread-local
+/// `BODIES` map populated by `mir_borrowck`; the resulting bodies can then
+/// be analyzed from any thread (e.g. by [`run_pcg_all`]'s rayon pool).
+fn collect_bodies_to_check(
+    tcx: TyCtxt<'_>,
+) -> Vec<(LocalDefId, String, BodyWithBorrowckFacts<'_>)> {
+    hir_body_owners(tcx)
+        .filter_map(|def_id| {
+            let kind = tcx.def_kind(def_id);
+            if !matches!(kind, DefKind::Fn | DefKind::AssocFn) {
+                return None;
+            }
+            let item_name = tcx.def_path_str(def_id.to_def_id()).to_string();
+            if let Ok(function) = std::env::var("PCG_CHECK_FUNCTION")
+                && function != item_name
+            {
+                tracing::debug!(
+                    "Skipping function: {item_name} because PCG_CHECK_FUNCTION is set to {function}"
+                );
+                return None;
+            }
+            if let Ok(function) = std::env::var("PCG_SKIP_FUNCTION")
+                && function == item_name
+            {
+                tracing::info!(
+                    "Skipping function: {item_name} because PCG_SKIP_FUNCTION is set to {function}"
+                );
+                return None;
+            }
+            let body = take_stored_body(tcx, def_id);
+            if !should_check_body(&body.body) {
+                return None;
+            }
+            Some((def_id, item_name, body))
+        })
+        .collect()
+}
+
+/// Analyzes every local `DefId` in `tcx`, returning the first error
+/// encountered for each (if any), keyed by `DefId`. Like the
+/// `PCG_PARALLEL` path of [`run_pcg_on_all_fns`], it analyzes the bodies on
+/// rayon's pool, but it is a separate entry point (`run_pcg_on_all_fns` does
+/// not call it) for embedders that want the results rather than just the
+/// as-a-side-effect visualization output.
+///
+/// # Safety
+///
+/// Functions bodies stored in `BODIES` must come from the same `tcx`.
+pub unsafe fn run_pcg_all(
+    tcx: TyCtxt<'_>,
+    polonius: bool,
+    vis_dir: Option<&str>,
+) -> FxHashMap<LocalDefId, Option<pcg::PcgError>> {
+    collect_bodies_to_check(tcx)
+        .par_iter()
+        .map(|(def_id, _, body)| {
+            let err = run_pcg_on_fn(*def_id, body, tcx, polonius, vis_dir, None);
+            (*def_id, err)
+        })
+        .collect()
+}
+
 type PcgCallback<'tcx> =
     dyn for<'mir, 'arena> Fn(PcgAnalysis<'mir, 'tcx, &'arena bumpalo::Bump>) + 'static;
 
@@ -288,7 +412,7 @@ pub(crate) fn run_pcg_on_fn<'tcx>(
     polonius: bool,
     vis_dir: Option<&str>,
     callback: Option<&PcgCallback<'tcx>>,
-) {
+) -> Option<pcg::PcgError> {
     let region_debug_name_overrides = if let Ok(lines) = source_lines(tcx, &body.body) {
         lines
             .iter()
@@ -298,10 +422,15 @@ pub(crate) fn run_pcg_on_fn<'tcx>(
     } else {
         BTreeMap::new()
     };
-    let mut bc = if polonius {
-        BorrowChecker::Polonius(PoloniusBorrowChecker::new(tcx, body))
-    } else {
-        BorrowChecker::Impl(BorrowCheckerImpl::new(tcx, body))
+    // `polonius` may be requested without a Polonius dump actually being
+    // available (e.g. `PCG_POLONIUS=1` on a toolchain/body that wasn't
+    // compiled with `-Zpolonius`): fall back to the NLL-region-based
+    // liveness in `BorrowCheckerImpl` rather than panicking, since it's a
+    // strictly less precise but always-available liveness source for the
+    // same queries.
+    let mut bc = match polonius.then(|| PoloniusBorrowChecker::new(tcx, body)).flatten() {
+        Some(polonius_bc) => BorrowChecker::Polonius(polonius_bc),
+        None => BorrowChecker::Impl(BorrowCheckerImpl::new(tcx, body)),
     };
     #[cfg(feature = "visualization")]
     {
@@ -312,6 +441,23 @@ pub(crate) fn run_pcg_on_fn<'tcx>(
     }
     let item_name = tcx.def_path_str(def_id.to_def_id()).to_string();
     let item_dir = vis_dir.map(|dir| format!("{dir}/{item_name}"));
+
+    let incremental_cache_path: Option<PathBuf> = std::env::var("PCG_INCREMENTAL_CACHE_DIR")
+        .ok()
+        .map(|dir| PathBuf::from(dir).join(format!("{item_name}.json")));
+    let body_hash = incremental::body_hash(&body.body);
+    if let Some(cache_path) = &incremental_cache_path
+        && let Some(cached) = incremental::load(cache_path, body_hash)
+    {
+        tracing::info!("Skipping PCG for {item_name}: body unchanged since last run");
+        if env_feature_enabled("PCG_EMIT_ANNOTATIONS").unwrap_or(false) {
+            for line in &cached.debug_lines {
+                eprintln!("// PCG: {line}");
+            }
+        }
+        return cached.error;
+    }
+
     let arena = Bump::new();
     let mut output = run_pcg(&body.body, tcx, &bc, &arena, item_dir.as_deref());
     let ctxt = CompilerCtxt::new(&body.body, tcx, &bc);
@@ -321,10 +467,15 @@ pub(crate) fn run_pcg_on_fn<'tcx>(
         emit_borrowcheck_graphs(dir_path, ctxt);
     }
 
-    emit_and_check_annotations(item_name, &mut output);
+    let incremental_cache = incremental_cache_path
+        .as_deref()
+        .map(|path| (path, body_hash));
+    emit_and_check_annotations(item_name, &mut output, incremental_cache);
+    let first_error = output.first_error();
     if let Some(callback) = callback {
         callback(output);
     }
+    first_error
 }
 
 struct LifetimeRenderAnnotation {
@@ -390,6 +541,20 @@ impl<'tcx> BorrowCheckerInterface<'tcx> for BorrowChecker<'_, 'tcx> {
         }
     }
 
+    fn loans_killed_at(&self, location: Location) -> BTreeSet<RegionVid> {
+        match self {
+            BorrowChecker::Polonius(bc) => bc.loans_killed_at(location),
+            BorrowChecker::Impl(bc) => bc.loans_killed_at(location),
+        }
+    }
+
+    fn liveness_precision(&self) -> crate::borrow_checker::LivenessPrecision {
+        match self {
+            BorrowChecker::Polonius(bc) => bc.liveness_precision(),
+            BorrowChecker::Impl(bc) => bc.liveness_precision(),
+        }
+    }
+
     fn outlives(&self, sup: PcgRegion, sub: PcgRegion) -> bool {
         match self {
             BorrowChecker::Polonius(bc) => bc.outlives(sup, sub),
@@ -439,7 +604,7 @@ impl<'tcx> BorrowCheckerInterface<'tcx> for BorrowChecker<'_, 'tcx> {
         }
     }
 
-    fn input_facts(&self) -> &borrowck::PoloniusInput {
+    fn input_facts(&self) -> Option<&borrowck::PoloniusInput> {
         match self {
             BorrowChecker::Polonius(bc) => bc.input_facts(),
             BorrowChecker::Impl(bc) => bc.input_facts(),
@@ -454,16 +619,34 @@ impl<'tcx> BorrowCheckerInterface<'tcx> for BorrowChecker<'_, 'tcx> {
     }
 }
 
-fn emit_and_check_annotations(item_name: String, output: &mut PcgOutput<'_, '_, &bumpalo::Bump>) {
+/// Implements the `test-files` corpus's expected-output testing: a test
+/// file can embed `// PCG: <line>` comments (asserting `<line>` appears
+/// somewhere in the analysis's debug output for that function) and
+/// `// ~PCG: <line>` comments (asserting it doesn't), giving that file a
+/// real assertion on the analysis's output instead of merely "doesn't
+/// crash". `<line>` is one of the lines `PcgBasicBlock::debug_lines`
+/// produces for a basic block -- the same per-action/per-capability
+/// strings rendered in the dot-graph visualization -- so an annotation
+/// can be copied straight out of a `PCG_EMIT_ANNOTATIONS=true` run (or
+/// the visualization) once the expected behavior is confirmed correct.
+/// Checking is opt-in via `PCG_CHECK_ANNOTATIONS`, which
+/// `tests/common::run_pcg_on_file` (used by `tests/test_files.rs`) sets
+/// for every corpus run.
+fn emit_and_check_annotations(
+    item_name: String,
+    output: &mut PcgOutput<'_, '_, &bumpalo::Bump>,
+    incremental_cache: Option<(&Path, u64)>,
+) {
     let emit_pcg_annotations = env_feature_enabled("PCG_EMIT_ANNOTATIONS").unwrap_or(false);
     let check_pcg_annotations = env_feature_enabled("PCG_CHECK_ANNOTATIONS").unwrap_or(false);
 
     let ctxt = output.ctxt();
 
-    if emit_pcg_annotations || check_pcg_annotations {
+    if emit_pcg_annotations || check_pcg_annotations || incremental_cache.is_some() {
         let mut debug_lines = Vec::new();
 
-        if let Some(err) = output.first_error() {
+        let first_error = output.first_error();
+        if let Some(err) = &first_error {
             debug_lines.push(format!("{err:?}"));
         }
         for block in ctxt.body().basic_blocks.indices() {
@@ -473,6 +656,12 @@ fn emit_and_check_annotations(item_name: String, output: &mut PcgOutput<'_, '_,
                 }
             }
         }
+        if let Some((cache_path, body_hash)) = incremental_cache {
+            if let Some(dir) = cache_path.parent() {
+                let _ = std::fs::create_dir_all(dir);
+            }
+            incremental::store(cache_path, body_hash, &debug_lines, first_error.as_ref());
+        }
         if emit_pcg_annotations {
             for line in debug_lines.iter() {
                 eprintln!("// PCG: {line}");
@@ -480,7 +669,7 @@ fn emit_and_check_annotations(item_name: String, output: &mut PcgOutput<'_, '_,
         }
         if check_pcg_annotations {
             if let Ok(source) = source_lines(ctxt.tcx(), ctxt.body()) {
-                let debug_lines_set: FxHashSet<_> = debug_lines.into_iter().collect();
+                let debug_lines_set: FxHashSet<_> = debug_lines.iter().cloned().collect();
                 let expected_annotations = source
                     .iter()
                     .flat_map(|l| l.split("// PCG: ").nth(1))
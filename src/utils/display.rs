@@ -122,7 +122,22 @@ impl<'tcx> Place<'tcx> {
             else {
                 return PlaceDisplay::Temporary(*self);
             };
-            Cow::Owned(local_name)
+
+            // Other locals in this body can have the same source name as
+            // `self.local`, e.g. shadowed `let` bindings in disjoint scopes.
+            // Rendering both as just `local_name` would make two distinct
+            // places indistinguishable in visualization output, so
+            // disambiguate by appending the local's index whenever that
+            // happens.
+            let shares_name_with_other_local = repacker.mir.var_debug_info.iter().any(|info| {
+                matches!(info.value, VarDebugInfoContents::Place(place)
+                    if place.local != self.local) && info.name.to_string() == local_name
+            });
+            if shares_name_with_other_local {
+                Cow::Owned(format!("{local_name}#{}", self.local.as_usize()))
+            } else {
+                Cow::Owned(local_name)
+            }
         };
 
         #[derive(Copy, Clone)]
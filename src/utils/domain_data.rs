@@ -32,6 +32,13 @@ pub enum DomainDataIndex {
     Initial,
 }
 
+/// The state at each of a statement's four [`EvalStmtPhase`]s
+/// (`pre_operands`, `post_operands`, `pre_main`, `post_main`). When `T` is
+/// an [`ArenaRef`] (as it is for [`crate::pcg::domain::PcgDomainData`]),
+/// advancing from one phase to the next (see
+/// [`crate::pcg::PcgEngine`]'s transfer function) just clones the `Rc`
+/// rather than the underlying state, so the four snapshots share structure
+/// via copy-on-write until a phase's state is actually mutated.
 #[derive(Debug, Clone, PartialEq, Eq, Default)]
 pub struct DomainDataStates<T>(pub(crate) EvalStmtData<T>);
 
@@ -5,12 +5,37 @@ use crate::utils::validity::HasValidityCheck;
 use crate::utils::CompilerCtxt;
 use serde_json::json;
 
+/// The state at each of a statement's four built-in [`EvalStmtPhase`]s,
+/// plus an optional [`Self::extra`] "bridge" slot.
+///
+/// Making the phase set itself a const-generic or enum-map parameter (so an
+/// embedder could add or drop phases) isn't done here: `EvalStmtPhase` and
+/// the four named fields below are matched exhaustively not just in this
+/// file (`get`/`Index`/`IndexMut`/`iter`/`iter_mut`) but throughout
+/// `crate::pcg::PcgEngine`'s transfer function, where each phase has
+/// concrete, hand-written dataflow semantics (e.g. "apply operand
+/// pre-conditions", "apply the statement's effect") tied to that specific
+/// transition. Genericizing the phase *set* would mean genericizing those
+/// semantics too -- i.e. a pluggable per-phase transfer hook -- which is a
+/// redesign of the engine's transfer function, not just of this storage
+/// type, and isn't something that can be done safely without a compiler and
+/// test suite to check the fixpoint still converges to the same answer.
+///
+/// What's added instead is a single optional `extra` slot that an embedder
+/// like Prusti can populate with its own "bridge" state *after* the engine
+/// has computed the four built-in phases (e.g. from
+/// [`crate::free_pcs::PcgLocation`], which already sits outside the
+/// engine), without PCG itself needing to know what it means or compute
+/// anything for it. This covers the "stash one extra per-statement value"
+/// half of the request; it doesn't cover "drop phases we don't need", which
+/// would need the deeper redesign described above.
 #[derive(Clone, PartialEq, Eq, Debug)]
 pub struct EvalStmtData<T> {
     pub(crate) pre_operands: T,
     pub(crate) post_operands: T,
     pub(crate) pre_main: T,
     pub(crate) post_main: T,
+    extra: Option<T>,
 }
 
 impl<T> EvalStmtData<T> {
@@ -20,8 +45,20 @@ impl<T> EvalStmtData<T> {
             post_operands,
             pre_main,
             post_main,
+            extra: None,
         }
     }
+
+    /// Attaches (or replaces) the `extra` bridge slot; see the type-level
+    /// doc comment.
+    pub fn with_extra(mut self, extra: T) -> Self {
+        self.extra = Some(extra);
+        self
+    }
+
+    pub fn extra(&self) -> Option<&T> {
+        self.extra.as_ref()
+    }
 }
 
 impl<'tcx, BC: Copy, T: ToJsonWithCompilerCtxt<'tcx, BC>> ToJsonWithCompilerCtxt<'tcx, BC> for EvalStmtData<T> {
@@ -31,6 +68,7 @@ impl<'tcx, BC: Copy, T: ToJsonWithCompilerCtxt<'tcx, BC>> ToJsonWithCompilerCtxt
             "post_operands": self.post_operands.to_json(ctxt),
             "pre_main": self.pre_main.to_json(ctxt),
             "post_main": self.post_main.to_json(ctxt),
+            "bridge": self.extra.as_ref().map(|e| e.to_json(ctxt)),
         })
     }
 }
@@ -42,6 +80,7 @@ impl<T: Default> Default for EvalStmtData<T> {
             post_operands: T::default(),
             pre_main: T::default(),
             post_main: T::default(),
+            extra: None,
         }
     }
 }
@@ -51,7 +90,11 @@ impl<'tcx, T: HasValidityCheck<'tcx>> HasValidityCheck<'tcx> for EvalStmtData<T>
         self.pre_operands.check_validity(ctxt)?;
         self.post_operands.check_validity(ctxt)?;
         self.pre_main.check_validity(ctxt)?;
-        self.post_main.check_validity(ctxt)
+        self.post_main.check_validity(ctxt)?;
+        if let Some(extra) = &self.extra {
+            extra.check_validity(ctxt)?;
+        }
+        Ok(())
     }
 }
 impl<T> EvalStmtData<T> {
@@ -61,6 +104,7 @@ impl<T> EvalStmtData<T> {
             post_operands: f(self.post_operands),
             pre_main: f(self.pre_main),
             post_main: f(self.post_main),
+            extra: self.extra.map(f),
         }
     }
 
@@ -0,0 +1,90 @@
+// © 2023, ETH Zurich
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! On-disk cache keyed on a hash of a function's MIR body, so an IDE-style
+//! caller that re-runs the PCG repeatedly (e.g. on every keystroke) can skip
+//! re-analyzing a function whose body hasn't changed since the last run.
+//!
+//! This only caches the debug-line summary produced for
+//! `PCG_EMIT_ANNOTATIONS`/`PCG_CHECK_ANNOTATIONS` (see
+//! [`crate::utils::callbacks::emit_and_check_annotations`]), not the
+//! `PcgAnalysis` itself: the latter borrows from the current `TyCtxt` and
+//! `Body`, neither of which is still alive (or even at the same address) on
+//! a later compiler invocation, so it can't be round-tripped through disk.
+
+use std::hash::{Hash, Hasher};
+use std::path::Path;
+
+use serde_derive::{Deserialize, Serialize};
+
+use crate::{
+    pcg::PcgError,
+    rustc_interface::{data_structures::fx::FxHasher, middle::mir::Body},
+};
+
+#[derive(Serialize, Deserialize)]
+struct CachedSummary {
+    body_hash: u64,
+    debug_lines: Vec<String>,
+    /// The error the cached run ended with, if any. Without this, a cache
+    /// hit would have no way to tell a function that previously failed
+    /// from one that previously succeeded -- see [`load`]. `#[serde(default)]`
+    /// so cache files written before this field existed still load (as a
+    /// cache hit with no error, same as if the field were absent now).
+    #[serde(default)]
+    error: Option<PcgError>,
+}
+
+/// What a cache hit returns: the run's debug lines plus whatever error it
+/// recorded, so a caller can't mistake "this function's body hasn't
+/// changed" for "this function analyzes successfully".
+pub(crate) struct CachedResult {
+    pub(crate) debug_lines: Vec<String>,
+    pub(crate) error: Option<PcgError>,
+}
+
+/// A hash of `body`'s structure. This is a proxy for "has this function
+/// changed" rather than a semantic fingerprint (e.g. it's sensitive to
+/// renamed locals that don't affect behavior), but it's cheap to compute and
+/// false positives only cost a redundant re-analysis, never a stale result.
+pub(crate) fn body_hash(body: &Body<'_>) -> u64 {
+    let mut hasher = FxHasher::default();
+    format!("{body:#?}").hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Returns the cached debug lines and error for `cache_path` if present and
+/// computed from a body with hash `body_hash`.
+pub(crate) fn load(cache_path: &Path, body_hash: u64) -> Option<CachedResult> {
+    let contents = std::fs::read_to_string(cache_path).ok()?;
+    let cached: CachedSummary = serde_json::from_str(&contents).ok()?;
+    if cached.body_hash == body_hash {
+        Some(CachedResult {
+            debug_lines: cached.debug_lines,
+            error: cached.error,
+        })
+    } else {
+        None
+    }
+}
+
+/// Persists `debug_lines` and `error` to `cache_path`, keyed on `body_hash`,
+/// overwriting whatever was cached for this function previously.
+pub(crate) fn store(
+    cache_path: &Path,
+    body_hash: u64,
+    debug_lines: &[String],
+    error: Option<&PcgError>,
+) {
+    let cached = CachedSummary {
+        body_hash,
+        debug_lines: debug_lines.to_vec(),
+        error: error.cloned(),
+    };
+    if let Ok(json) = serde_json::to_string(&cached) {
+        let _ = std::fs::write(cache_path, json);
+    }
+}
@@ -0,0 +1,68 @@
+// © 2023, ETH Zurich
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use crate::rustc_interface::{
+    hir::def_id::DefId,
+    middle::{
+        mir::{Rvalue, StatementKind, TerminatorKind, RETURN_PLACE},
+        ty::TyCtxt,
+    },
+};
+
+/// Checks whether `def_id`'s body is a "trivial getter": a single basic
+/// block that returns a reference to (or copy of) a projection of its
+/// first argument, e.g. `fn get(&self) -> &T { &self.field }` or
+/// `fn get(&self) -> T { self.field }`.
+///
+/// This is the detection half of what would be needed to give `run_pcg` an
+/// "inline trivial callees" preprocessing pass (see
+/// [`crate::PcgOptionsBuilder::inline_trivial_getters`]) -- actually
+/// splicing such a callee's body into its caller is not implemented here.
+/// Doing so soundly would mean building an owned, rewritten
+/// [`crate::rustc_interface::middle::mir::Body`] (renumbering the callee's
+/// locals and basic blocks past the caller's, substituting the callee's
+/// generic parameters with those visible at the call site, and guarding
+/// against inlining a function into itself through recursion), none of
+/// which this function attempts. It exists so callers that only need to
+/// know *whether* a callee is this simple -- e.g. to model its call as a
+/// place projection rather than an opaque
+/// [`crate::borrow_pcg::edge::abstraction::function::FunctionCallAbstraction`]
+/// -- can do so without requiring the MIR-splicing machinery above, though
+/// no such caller exists yet in this crate.
+pub(crate) fn is_trivial_getter(tcx: TyCtxt<'_>, def_id: DefId) -> bool {
+    if !tcx.is_mir_available(def_id) {
+        return false;
+    }
+    let body = tcx.optimized_mir(def_id);
+    let [block] = body.basic_blocks.as_slice() else {
+        return false;
+    };
+    if !matches!(block.terminator().kind, TerminatorKind::Return) {
+        return false;
+    }
+    let mut assigned_return_place = false;
+    for statement in &block.statements {
+        let StatementKind::Assign(box (place, rvalue)) = &statement.kind else {
+            return false;
+        };
+        if *place != RETURN_PLACE.into() {
+            return false;
+        }
+        let source_place = match rvalue {
+            Rvalue::Ref(_, _, source_place) => source_place,
+            Rvalue::Use(operand) => match operand.place() {
+                Some(source_place) => source_place,
+                None => return false,
+            },
+            _ => return false,
+        };
+        if source_place.local != RETURN_PLACE.plus(1) {
+            return false;
+        }
+        assigned_return_place = true;
+    }
+    assigned_return_place
+}
@@ -9,11 +9,13 @@ pub mod callbacks;
 pub mod debug_info;
 pub mod display;
 pub mod eval_stmt_data;
+mod incremental;
 pub(crate) mod incoming_states;
 pub mod loop_usage;
 pub mod json;
 mod mutable;
 pub mod place;
+pub mod place_interner;
 pub mod place_snapshot;
 pub(crate) mod redirect;
 mod root_place;
@@ -24,6 +26,7 @@ pub use place::*;
 pub use place_snapshot::*;
 pub use repacker::*;
 pub(crate) mod domain_data;
+pub(crate) mod mir_inline;
 pub(crate) mod repacker;
 pub(crate) mod data_structures;
 
@@ -44,11 +47,134 @@ lazy_static! {
         env_feature_enabled("PCG_COUPLING_DEBUG_IMGCAT").unwrap_or(false);
     pub static ref BORROWS_DEBUG_IMGCAT: bool =
         env_feature_enabled("PCG_BORROWS_DEBUG_IMGCAT").unwrap_or(false);
+    /// Narrows which renders `PCG_BORROWS_DEBUG_IMGCAT` actually fires for,
+    /// so debugging a single join doesn't flood the terminal with one
+    /// inline image per block in the function. See
+    /// [`ImgcatDebugFilter`]'s doc comment for the environment variables
+    /// and what's and isn't filterable.
+    pub static ref IMGCAT_DEBUG_FILTER: ImgcatDebugFilter = ImgcatDebugFilter {
+        block: std::env::var("PCG_IMGCAT_DEBUG_BLOCK")
+            .ok()
+            .map(|s| s.parse().unwrap_or_else(|_| panic!(
+                "PCG_IMGCAT_DEBUG_BLOCK must be a basic block index, got '{s}'"
+            ))),
+        statement_range: std::env::var("PCG_IMGCAT_DEBUG_STMT_RANGE").ok().map(|s| {
+            let (lo, hi) = s.split_once('-').unwrap_or_else(|| panic!(
+                "PCG_IMGCAT_DEBUG_STMT_RANGE must be 'lo-hi', got '{s}'"
+            ));
+            (lo.parse().unwrap(), hi.parse().unwrap())
+        }),
+        place: std::env::var("PCG_IMGCAT_DEBUG_PLACE").ok().filter(|s| !s.is_empty()),
+    };
     pub static ref VALIDITY_CHECKS_WARN_ONLY: bool =
         env_feature_enabled("PCG_VALIDITY_CHECKS_WARN_ONLY").unwrap_or(false);
     pub static ref POLONIUS: bool = env_feature_enabled("PCG_POLONIUS").unwrap_or(false);
     pub static ref DUMP_MIR_DATAFLOW: bool =
         env_feature_enabled("PCG_DUMP_MIR_DATAFLOW").unwrap_or(false);
+    /// When enabled, taking the address of a place via `&raw (const|mut)`
+    /// marks that place as escaped in [`crate::pcg::place_capabilities::PlaceCapabilities`],
+    /// rather than being treated like an ordinary place use.
+    pub static ref TRACK_RAW_POINTERS: bool =
+        env_feature_enabled("PCG_TRACK_RAW_POINTERS").unwrap_or(false);
+    /// When enabled, [`crate::utils::callbacks::run_pcg_on_all_fns`] analyzes
+    /// the crate's functions across a rayon thread pool instead of one at a
+    /// time.
+    pub static ref PARALLEL: bool = env_feature_enabled("PCG_PARALLEL").unwrap_or(false);
+    /// When enabled (and a visualization output path is configured), also
+    /// assemble a self-contained `report.html` per function; see
+    /// [`crate::visualization::html`].
+    pub static ref HTML_REPORT: bool = env_feature_enabled("PCG_HTML_REPORT").unwrap_or(false);
+    /// When enabled, PCG's liveness facts are cross-validated against
+    /// [`crate::borrow_checker::BorrowCheckerInterface`] after each
+    /// statement; see [`crate::pcg::cross_validation`].
+    pub static ref CROSS_VALIDATION: bool =
+        env_feature_enabled("PCG_CROSS_VALIDATION").unwrap_or(false);
+    /// When enabled, terminator successors that are only reachable by
+    /// unwinding (MIR cleanup blocks) are omitted from
+    /// [`crate::free_pcs::PcgTerminator::succs`]; see
+    /// [`crate::PcgOptions::ignore_unwind_paths`].
+    pub static ref IGNORE_UNWIND_PATHS: bool =
+        env_feature_enabled("PCG_IGNORE_UNWIND_PATHS").unwrap_or(false);
+    /// When enabled, promoted MIR bodies (e.g. the body backing a promoted
+    /// temporary like the one behind `&[1, 2, 3]`) are pulled in from `tcx`
+    /// on demand and surfaced in visualization output; see
+    /// [`crate::PcgOptions::promoted_bodies`].
+    pub static ref PROMOTED_BODIES: bool =
+        env_feature_enabled("PCG_PROMOTED_BODIES").unwrap_or(false);
+    /// When enabled, a place whose own type is `UnsafeCell<T>` keeps
+    /// [`crate::free_pcs::CapabilityKind::ShallowExclusive`] (rather than
+    /// being weakened to [`crate::free_pcs::CapabilityKind::Read`]) when a
+    /// shared borrow of it is created, reflecting that writes through the
+    /// cell are legal even while shared-borrowed. Off by default: callers
+    /// that don't special-case `UnsafeCell` reads/writes downstream would
+    /// otherwise see a capability that doesn't match their assumptions
+    /// about what `Read` means.
+    pub static ref UNSAFE_CELL_WRITE_CAPABILITY: bool =
+        env_feature_enabled("PCG_UNSAFE_CELL_WRITE_CAPABILITY").unwrap_or(false);
+    /// When enabled, a call to a detected trivial getter (see
+    /// [`crate::utils::mir_inline::is_trivial_getter`]) is logged via
+    /// `tracing::debug!` as a candidate for the coarse
+    /// `FunctionCallAbstraction` it is about to be modeled with; see
+    /// [`crate::PcgOptions::inline_trivial_getters`].
+    pub static ref INLINE_TRIVIAL_GETTERS: bool =
+        env_feature_enabled("PCG_INLINE_TRIVIAL_GETTERS").unwrap_or(false);
+}
+
+/// Narrows which [`crate::borrow_pcg::graph::borrows_imgcat_debug`]-gated
+/// inline terminal renders actually fire. Configured via
+/// `PCG_IMGCAT_DEBUG_BLOCK` (a bare block index, e.g. `3`),
+/// `PCG_IMGCAT_DEBUG_STMT_RANGE` (`lo-hi`, inclusive), and
+/// `PCG_IMGCAT_DEBUG_PLACE` (a substring of the render's description), or
+/// programmatically via [`ImgcatDebugFilter::new`]; see [`IMGCAT_DEBUG_FILTER`].
+///
+/// Most call sites only have a `BasicBlock` (not a full `Location`) and/or
+/// only a free-text description (not a structured `Place`) available at
+/// the point they'd render, so `statement_index`/`place` filtering is
+/// applied wherever that context exists, and simply not checked where it
+/// doesn't (an unknown dimension is treated as a match, not a mismatch).
+#[derive(Clone, Debug, Default)]
+pub struct ImgcatDebugFilter {
+    block: Option<usize>,
+    statement_range: Option<(usize, usize)>,
+    place: Option<String>,
+}
+
+impl ImgcatDebugFilter {
+    pub fn new(
+        block: Option<usize>,
+        statement_range: Option<(usize, usize)>,
+        place: Option<String>,
+    ) -> Self {
+        Self {
+            block,
+            statement_range,
+            place,
+        }
+    }
+
+    pub(crate) fn allows(
+        &self,
+        block: Option<usize>,
+        statement_index: Option<usize>,
+        comment: &str,
+    ) -> bool {
+        if let (Some(wanted), Some(actual)) = (self.block, block)
+            && wanted != actual
+        {
+            return false;
+        }
+        if let (Some((lo, hi)), Some(actual)) = (self.statement_range, statement_index)
+            && !(lo..=hi).contains(&actual)
+        {
+            return false;
+        }
+        if let Some(place) = &self.place
+            && !comment.contains(place.as_str())
+        {
+            return false;
+        }
+        true
+    }
 }
 
 fn env_feature_enabled(feature: &'static str) -> Option<bool> {
@@ -67,3 +193,89 @@ fn env_feature_enabled(feature: &'static str) -> Option<bool> {
         Err(_) => None,
     }
 }
+
+/// Which category of internal consistency check [`crate::pcg_category_validity_assert!`]
+/// is guarding, so [`ValidityConfig`] can enable/disable them independently.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ValidityCheckCategory {
+    /// Checks that a borrow/coupling graph has no cycles.
+    GraphAcyclicity,
+    /// Checks that a place's recorded capability is consistent with what's
+    /// required of it (e.g. at least as strong as a required capability).
+    CapabilityConsistency,
+    /// Checks that the "latest" map (tracking the most recent version of a
+    /// place snapshotted for a borrow) is coherent. No crate-internal check
+    /// is currently categorized this way; the variant exists so embedders
+    /// can opt individual functions into such checks as they're added.
+    LatestMapCoherence,
+}
+
+/// Per-category configuration for [`crate::pcg_category_validity_assert!`],
+/// carried in [`CompilerCtxt`] so embedders can enable PCG's (potentially
+/// expensive) internal consistency checks only for selected functions,
+/// rather than process-wide via `PCG_VALIDITY_CHECKS`. Unlike the
+/// process-global checks gated by [`crate::pcg_validity_assert!`] (which
+/// remain available for checks not yet migrated to a category), this is
+/// plain data carried by value, so it composes with [`CompilerCtxt`] being
+/// `Copy` and cloned pervasively throughout the crate.
+#[derive(Debug, Clone, Copy)]
+pub struct ValidityConfig {
+    graph_acyclicity: bool,
+    capability_consistency: bool,
+    latest_map_coherence: bool,
+    warn_only: bool,
+}
+
+impl ValidityConfig {
+    /// Enables or disables every category at once, matching the
+    /// all-or-nothing behavior of the process-global `PCG_VALIDITY_CHECKS`.
+    pub fn all(enabled: bool) -> Self {
+        Self {
+            graph_acyclicity: enabled,
+            capability_consistency: enabled,
+            latest_map_coherence: enabled,
+            warn_only: false,
+        }
+    }
+
+    /// Whether a failing check should be logged via `tracing::error!` rather
+    /// than panicking. [`Self::default`] takes it from
+    /// `PCG_VALIDITY_CHECKS_WARN_ONLY`; [`Self::all`] starts with it off.
+    pub fn warn_only(mut self, enabled: bool) -> Self {
+        self.warn_only = enabled;
+        self
+    }
+
+    pub fn graph_acyclicity(mut self, enabled: bool) -> Self {
+        self.graph_acyclicity = enabled;
+        self
+    }
+
+    pub fn capability_consistency(mut self, enabled: bool) -> Self {
+        self.capability_consistency = enabled;
+        self
+    }
+
+    pub fn latest_map_coherence(mut self, enabled: bool) -> Self {
+        self.latest_map_coherence = enabled;
+        self
+    }
+
+    pub fn is_warn_only(&self) -> bool {
+        self.warn_only
+    }
+
+    pub fn is_enabled(&self, category: ValidityCheckCategory) -> bool {
+        match category {
+            ValidityCheckCategory::GraphAcyclicity => self.graph_acyclicity,
+            ValidityCheckCategory::CapabilityConsistency => self.capability_consistency,
+            ValidityCheckCategory::LatestMapCoherence => self.latest_map_coherence,
+        }
+    }
+}
+
+impl Default for ValidityConfig {
+    fn default() -> Self {
+        Self::all(*VALIDITY_CHECKS).warn_only(*VALIDITY_CHECKS_WARN_ONLY)
+    }
+}
@@ -117,7 +117,7 @@ impl<'tcx> TryFrom<MaybeRemotePlace<'tcx>> for MaybeOldPlace<'tcx> {
     fn try_from(remote_place: MaybeRemotePlace<'tcx>) -> Result<Self, Self::Error> {
         match remote_place {
             MaybeRemotePlace::Local(p) => Ok(p),
-            MaybeRemotePlace::Remote(_) => Err(()),
+            MaybeRemotePlace::Remote(_) | MaybeRemotePlace::Static(_) => Err(()),
         }
     }
 }
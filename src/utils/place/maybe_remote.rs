@@ -11,6 +11,7 @@ use crate::utils::display::DisplayWithCompilerCtxt;
 use crate::utils::json::ToJsonWithCompilerCtxt;
 use crate::utils::place::maybe_old::MaybeOldPlace;
 use crate::utils::place::remote::RemotePlace;
+use crate::utils::static_place::StaticPlace;
 use crate::utils::{CompilerCtxt, HasPlace, Place, PlaceSnapshot};
 
 #[derive(From, PartialEq, Eq, Copy, Clone, Debug, Hash, PartialOrd, Ord)]
@@ -20,6 +21,10 @@ pub enum MaybeRemotePlace<'tcx> {
 
     /// A place that cannot be named, e.g. the source of a reference-type input argument
     Remote(RemotePlace),
+
+    /// A `static` or `#[thread_local]` static item, e.g. the `FOO` in `&FOO`
+    /// or `&mut THREAD_LOCAL`. See [`StaticPlace`].
+    Static(StaticPlace),
 }
 
 impl<'tcx> MaybeRemotePlace<'tcx> {
@@ -27,6 +32,7 @@ impl<'tcx> MaybeRemotePlace<'tcx> {
         match self {
             MaybeRemotePlace::Local(p) => p.is_mutable(ctxt),
             MaybeRemotePlace::Remote(_) => false,
+            MaybeRemotePlace::Static(_) => true,
         }
     }
 }
@@ -36,6 +42,7 @@ impl<'tcx> PCGNodeLike<'tcx> for MaybeRemotePlace<'tcx> {
         match self {
             MaybeRemotePlace::Local(p) => p.to_pcg_node(repacker),
             MaybeRemotePlace::Remote(rp) => rp.to_pcg_node(repacker),
+            MaybeRemotePlace::Static(sp) => sp.to_pcg_node(repacker),
         }
     }
 }
@@ -45,6 +52,7 @@ impl<'tcx> RegionProjectionBaseLike<'tcx> for MaybeRemotePlace<'tcx> {
         match self {
             MaybeRemotePlace::Local(p) => p.to_maybe_remote_region_projection_base(),
             MaybeRemotePlace::Remote(rp) => (*rp).into(),
+            MaybeRemotePlace::Static(sp) => (*sp).into(),
         }
     }
 
@@ -52,7 +60,10 @@ impl<'tcx> RegionProjectionBaseLike<'tcx> for MaybeRemotePlace<'tcx> {
         &self,
         repacker: CompilerCtxt<'_, 'tcx, C>,
     ) -> IndexVec<RegionIdx, PcgRegion> {
-        self.related_local_place().regions(repacker)
+        match self {
+            MaybeRemotePlace::Static(sp) => sp.regions(repacker),
+            _ => self.related_local_place().regions(repacker),
+        }
     }
 }
 
@@ -61,6 +72,7 @@ impl<'tcx, BC: Copy> DisplayWithCompilerCtxt<'tcx, BC> for MaybeRemotePlace<'tcx
         match self {
             MaybeRemotePlace::Local(p) => p.to_short_string(repacker),
             MaybeRemotePlace::Remote(rp) => format!("{rp}"),
+            MaybeRemotePlace::Static(sp) => format!("{sp}"),
         }
     }
 }
@@ -70,6 +82,7 @@ impl<'tcx, BC: Copy> ToJsonWithCompilerCtxt<'tcx, BC> for MaybeRemotePlace<'tcx>
         match self {
             MaybeRemotePlace::Local(p) => p.to_json(repacker),
             MaybeRemotePlace::Remote(rp) => format!("{rp}").into(),
+            MaybeRemotePlace::Static(sp) => format!("{sp}").into(),
         }
     }
 }
@@ -79,6 +92,7 @@ impl<'tcx> HasPcgElems<MaybeOldPlace<'tcx>> for MaybeRemotePlace<'tcx> {
         match self {
             MaybeRemotePlace::Local(p) => vec![p],
             MaybeRemotePlace::Remote(_) => vec![],
+            MaybeRemotePlace::Static(_) => vec![],
         }
     }
 }
@@ -88,6 +102,7 @@ impl std::fmt::Display for MaybeRemotePlace<'_> {
         match self {
             MaybeRemotePlace::Local(p) => write!(f, "{p}"),
             MaybeRemotePlace::Remote(l) => write!(f, "Remote({l:?})"),
+            MaybeRemotePlace::Static(sp) => write!(f, "{sp}"),
         }
     }
 }
@@ -105,10 +120,17 @@ impl<'tcx> MaybeRemotePlace<'tcx> {
         matches!(self, MaybeRemotePlace::Remote(_))
     }
 
+    /// The `Place` of the local most closely associated with this node, used
+    /// to compute e.g. its type or regions. Panics for [`MaybeRemotePlace::Static`],
+    /// which isn't associated with any local; callers that might see a
+    /// `Static` place must check for it first (see e.g. [`Self::regions`]).
     pub(crate) fn related_local_place(&self) -> Place<'tcx> {
         match self {
             MaybeRemotePlace::Local(p) => p.place(),
             MaybeRemotePlace::Remote(rp) => rp.local.into(),
+            MaybeRemotePlace::Static(_) => {
+                unreachable!("static places aren't associated with a local")
+            }
         }
     }
 
@@ -116,7 +138,10 @@ impl<'tcx> MaybeRemotePlace<'tcx> {
         &self,
         repacker: CompilerCtxt<'_, 'tcx, C>,
     ) -> IndexVec<RegionIdx, PcgRegion> {
-        self.related_local_place().regions(repacker)
+        match self {
+            MaybeRemotePlace::Static(sp) => sp.regions(repacker),
+            _ => self.related_local_place().regions(repacker),
+        }
     }
 
     pub(crate) fn as_current_place(&self) -> Option<Place<'tcx>> {
@@ -130,14 +155,14 @@ impl<'tcx> MaybeRemotePlace<'tcx> {
     pub(crate) fn as_local_place_mut(&mut self) -> Option<&mut MaybeOldPlace<'tcx>> {
         match self {
             MaybeRemotePlace::Local(p) => Some(p),
-            MaybeRemotePlace::Remote(_) => None,
+            MaybeRemotePlace::Remote(_) | MaybeRemotePlace::Static(_) => None,
         }
     }
 
     pub fn as_local_place(&self) -> Option<MaybeOldPlace<'tcx>> {
         match self {
             MaybeRemotePlace::Local(p) => Some(*p),
-            MaybeRemotePlace::Remote(_) => None,
+            MaybeRemotePlace::Remote(_) | MaybeRemotePlace::Static(_) => None,
         }
     }
 
@@ -145,6 +170,7 @@ impl<'tcx> MaybeRemotePlace<'tcx> {
         match self {
             MaybeRemotePlace::Local(p) => p.to_json(repacker),
             MaybeRemotePlace::Remote(_) => todo!(),
+            MaybeRemotePlace::Static(sp) => format!("{sp}").into(),
         }
     }
 }
@@ -50,6 +50,7 @@ pub mod corrected;
 pub mod maybe_old;
 pub mod maybe_remote;
 pub mod remote;
+pub mod static_place;
 
 #[derive(Clone, Copy, Deref, DerefMut)]
 pub struct Place<'tcx>(
@@ -484,7 +485,7 @@ impl<'tcx> Place<'tcx> {
     /// +   `partial_cmp(x.f, x.f) == Some(Equal)`
     /// +   `partial_cmp(x.f.g, x.f) == Some(Suffix)`
     /// +   `partial_cmp(x.f, x.f.g) == Some(Prefix)`
-    /// +   `partial_cmp(x as None, x as Some.0) == Some(Both)`
+    /// +   `partial_cmp(x as None, x as Some.0) == None`
     ///
     /// The ultimate question this answers is: are the two places mutually
     /// exclusive (i.e. can we have both or not)?
@@ -522,9 +523,14 @@ impl<'tcx> Place<'tcx> {
                         ..
                     },
                 ) if r == l && lfe == rfe => None,
-                (Downcast(_, _), Downcast(_, _)) | (OpaqueCast(_), OpaqueCast(_)) => {
-                    Some(PlaceOrdering::Both)
-                }
+                // `elem_eq` already treats same-variant `Downcast`s as equal,
+                // so by construction this arm only sees different variants
+                // of the same enum. Those are mutually exclusive at runtime
+                // (only one is ever live), so unlike ambiguous indices
+                // they're not "related": neither can be reached from the
+                // other by expand/collapse.
+                (Downcast(_, _), Downcast(_, _)) => None,
+                (OpaqueCast(_), OpaqueCast(_)) => Some(PlaceOrdering::Both),
                 (left, right) if is_index(left) && is_index(right) => Some(PlaceOrdering::Both),
                 diff => unreachable!("Unexpected diff: {diff:?}"),
             }
@@ -0,0 +1,78 @@
+use crate::borrow_pcg::region_projection::{
+    MaybeRemoteRegionProjectionBase, PcgRegion, RegionIdx, RegionProjectionBaseLike,
+};
+use crate::pcg::{PCGNode, PCGNodeLike};
+use crate::rustc_interface::hir::def_id::DefId;
+use crate::rustc_interface::index::IndexVec;
+use crate::utils::display::DisplayWithCompilerCtxt;
+use crate::utils::json::ToJsonWithCompilerCtxt;
+use crate::utils::validity::HasValidityCheck;
+use crate::utils::CompilerCtxt;
+
+/// The root node for a borrow of a `static` or `#[thread_local]` static item,
+/// e.g. the `FOO` in `&FOO` or `&mut THREAD_LOCAL`. Such items have no
+/// `mir::Local`, so unlike [`super::remote::RemotePlace`] (which is keyed on
+/// one) they're keyed directly on the item's `DefId`.
+///
+/// All of a static's regions are (by construction: `static`s can't mention
+/// non-`'static` lifetimes) `'static`, so there's nothing to gain from
+/// tracking region projections through it; [`Self::regions`] therefore always
+/// returns an empty set, and this node only ever appears as the source of a
+/// flat borrow edge (see `StaticBorrow`), not as a `RegionProjection` base.
+#[derive(PartialEq, Eq, Copy, Clone, Debug, Hash, PartialOrd, Ord)]
+pub struct StaticPlace {
+    def_id: DefId,
+}
+
+impl StaticPlace {
+    pub(crate) fn new(def_id: DefId) -> Self {
+        Self { def_id }
+    }
+
+    pub fn def_id(self) -> DefId {
+        self.def_id
+    }
+}
+
+impl<'tcx> PCGNodeLike<'tcx> for StaticPlace {
+    fn to_pcg_node<C: Copy>(self, _repacker: CompilerCtxt<'_, 'tcx, C>) -> PCGNode<'tcx> {
+        self.into()
+    }
+}
+
+impl<'tcx> RegionProjectionBaseLike<'tcx> for StaticPlace {
+    fn to_maybe_remote_region_projection_base(&self) -> MaybeRemoteRegionProjectionBase<'tcx> {
+        (*self).into()
+    }
+
+    fn regions<C: Copy>(
+        &self,
+        _repacker: CompilerCtxt<'_, 'tcx, C>,
+    ) -> IndexVec<RegionIdx, PcgRegion> {
+        IndexVec::new()
+    }
+}
+
+impl<'tcx, BC: Copy> DisplayWithCompilerCtxt<'tcx, BC> for StaticPlace {
+    fn to_short_string(&self, _repacker: CompilerCtxt<'_, 'tcx, BC>) -> String {
+        format!("{self}")
+    }
+}
+
+impl<'tcx, BC: Copy> ToJsonWithCompilerCtxt<'tcx, BC> for StaticPlace {
+    fn to_json(&self, _repacker: CompilerCtxt<'_, 'tcx, BC>) -> serde_json::Value {
+        format!("{self}").into()
+    }
+}
+
+impl<'tcx> HasValidityCheck<'tcx> for StaticPlace {
+    fn check_validity(&self, _ctxt: CompilerCtxt<'_, 'tcx>) -> Result<(), String> {
+        Ok(())
+    }
+}
+
+impl std::fmt::Display for StaticPlace {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Static({:?})", self.def_id)
+    }
+}
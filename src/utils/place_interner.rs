@@ -0,0 +1,79 @@
+// © 2023, ETH Zurich
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! A standalone interner for [`Place`], producing dense [`PlaceId`] indices.
+//!
+//! The motivation is that `Place` is hashed constantly by
+//! [`crate::pcg::place_capabilities::PlaceCapabilities`] and the borrow
+//! graph, and a dense integer key would be cheaper to hash and to use as an
+//! `IndexVec` key than the `Place` itself. However, [`CompilerCtxt`] is
+//! `#[derive(Copy, Clone)]` and is passed by value at essentially every call
+//! site in this crate; giving it an interner field would mean either
+//! breaking that `Copy` impl (because an interner needs interior
+//! mutability to grow) or threading a new constructor argument through
+//! every one of those call sites. Neither is a change that can be made
+//! safely without a compiler on hand to check the fallout, so this module
+//! only provides the interner itself. Wiring it into `CompilerCtxt` and
+//! migrating `PlaceCapabilities`/the borrow graph's maps to be keyed by
+//! `PlaceId` is left for a follow-up change.
+use crate::rustc_interface::{
+    data_structures::fx::FxHashMap,
+    index::{Idx, IndexVec},
+};
+
+use super::Place;
+
+/// The index of a [`Place`] within a [`PlaceInterner`].
+#[derive(PartialEq, Eq, Clone, Debug, Hash, Copy, Ord, PartialOrd)]
+pub struct PlaceId(usize);
+
+impl Idx for PlaceId {
+    fn new(idx: usize) -> Self {
+        PlaceId(idx)
+    }
+
+    fn index(self) -> usize {
+        self.0
+    }
+}
+
+/// Interns [`Place`]s, handing out a dense [`PlaceId`] for each distinct
+/// place seen. Interning the same place twice returns the same id.
+#[derive(Default)]
+pub struct PlaceInterner<'tcx> {
+    places: IndexVec<PlaceId, Place<'tcx>>,
+    ids: FxHashMap<Place<'tcx>, PlaceId>,
+}
+
+impl<'tcx> PlaceInterner<'tcx> {
+    pub fn new() -> Self {
+        Self {
+            places: IndexVec::new(),
+            ids: FxHashMap::default(),
+        }
+    }
+
+    /// Returns the id for `place`, interning it if it hasn't been seen
+    /// before.
+    pub fn intern(&mut self, place: Place<'tcx>) -> PlaceId {
+        if let Some(id) = self.ids.get(&place) {
+            return *id;
+        }
+        let id = self.places.push(place);
+        self.ids.insert(place, id);
+        id
+    }
+
+    /// Returns the id previously assigned to `place`, if any.
+    pub fn get(&self, place: Place<'tcx>) -> Option<PlaceId> {
+        self.ids.get(&place).copied()
+    }
+
+    /// Returns the place that `id` was interned from.
+    pub fn lookup(&self, id: PlaceId) -> Place<'tcx> {
+        self.places[id]
+    }
+}
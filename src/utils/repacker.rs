@@ -13,8 +13,8 @@ use crate::{
         index::Idx,
         middle::{
             mir::{
-                BasicBlock, Body, HasLocalDecls, Local, Mutability, Place as MirPlace, PlaceElem,
-                ProjectionElem, VarDebugInfoContents,
+                BasicBlock, Body, Const, HasLocalDecls, Local, Mutability, Place as MirPlace,
+                PlaceElem, ProjectionElem, VarDebugInfoContents,
             },
             ty::{TyCtxt, TyKind},
         },
@@ -29,8 +29,20 @@ use crate::{
     pcg::{PCGUnsupportedError, PcgError},
 };
 
+use super::ValidityConfig;
+
 use super::Place;
 
+/// The largest array length for which [`Place::expand_one_level`] will
+/// enumerate individual `ConstantIndex` element places when expanding a
+/// dynamic `Index` projection into an array of known, const-generic-or-literal
+/// length. Longer (or unknown-length, i.e. slice) arrays fall back to
+/// summarizing the un-selected elements as a single place, as we already do
+/// for `Subslice`. This is a fixed constant rather than a field threaded
+/// through [`CompilerCtxt`], since `CompilerCtxt` is `Copy` and cloned
+/// pervasively throughout the crate.
+const MAX_ARRAY_INDEX_EXPANSION: u64 = 32;
+
 #[derive(Debug, Clone, Copy)]
 pub enum ProjectionKind {
     DerefRef(Mutability),
@@ -46,8 +58,12 @@ pub struct ShallowExpansion<'tcx> {
     pub(crate) target_place: Place<'tcx>,
 
     /// Other places that could have resulted from this expansion. Note: this
-    /// vector is always incomplete when projecting with `Index` or `Subslice`
-    /// and also when projecting a slice type with `ConstantIndex`!
+    /// vector is a conservative summary (a single place covering the whole
+    /// base) rather than a precise sibling set when projecting with
+    /// `Subslice`, or with `Index` into a slice or an array whose length
+    /// isn't a small compile-time constant (see [`MAX_ARRAY_INDEX_EXPANSION`]);
+    /// it is always incomplete when projecting a slice type with
+    /// `ConstantIndex`!
     pub(crate) other_places: Vec<Place<'tcx>>,
     pub(crate) kind: ProjectionKind,
 }
@@ -141,6 +157,9 @@ pub struct CompilerCtxt<'a, 'tcx, T = &'a dyn BorrowCheckerInterface<'tcx>> {
     pub(super) mir: &'a Body<'tcx>,
     pub(super) tcx: TyCtxt<'tcx>,
     pub(crate) bc: T,
+    pub(crate) validity_config: ValidityConfig,
+    pub(crate) promoted_bodies: bool,
+    pub(crate) inline_trivial_getters: bool,
 }
 
 impl<'a, 'tcx, T: BorrowCheckerInterface<'tcx> + ?Sized> CompilerCtxt<'a, 'tcx, &'a T> {
@@ -149,13 +168,80 @@ impl<'a, 'tcx, T: BorrowCheckerInterface<'tcx> + ?Sized> CompilerCtxt<'a, 'tcx,
             mir: self.mir,
             tcx: self.tcx,
             bc: self.bc.as_dyn(),
+            validity_config: self.validity_config,
+            promoted_bodies: self.promoted_bodies,
+            inline_trivial_getters: self.inline_trivial_getters,
         }
     }
 }
 
 impl<'a, 'tcx, T> CompilerCtxt<'a, 'tcx, T> {
     pub fn new(mir: &'a Body<'tcx>, tcx: TyCtxt<'tcx>, bc: T) -> Self {
-        Self { mir, tcx, bc }
+        Self {
+            mir,
+            tcx,
+            bc,
+            validity_config: ValidityConfig::default(),
+            promoted_bodies: false,
+            inline_trivial_getters: false,
+        }
+    }
+
+    /// Overrides the [`ValidityConfig`] that [`crate::pcg_category_validity_assert!`]
+    /// checks performed with this context will consult, e.g. to enable
+    /// expensive checks for a single function under analysis without
+    /// setting `PCG_VALIDITY_CHECKS` process-wide.
+    pub fn with_validity_config(mut self, validity_config: ValidityConfig) -> Self {
+        self.validity_config = validity_config;
+        self
+    }
+
+    pub fn validity_config(self) -> ValidityConfig {
+        self.validity_config
+    }
+
+    /// Sets whether this context should pull in promoted MIR bodies from
+    /// `tcx` on demand; see [`crate::PcgOptions::promoted_bodies`].
+    pub fn with_promoted_bodies(mut self, enabled: bool) -> Self {
+        self.promoted_bodies = enabled;
+        self
+    }
+
+    pub fn promoted_bodies(self) -> bool {
+        self.promoted_bodies
+    }
+
+    /// Sets whether calls to a detected "trivial getter" callee (see
+    /// [`crate::utils::mir_inline::is_trivial_getter`]) should be logged as
+    /// a candidate for a coarse [`FunctionCallAbstraction`](crate::borrow_pcg::edge::abstraction::function::FunctionCallAbstraction)
+    /// that a real inlining pass could have avoided; see
+    /// [`crate::PcgOptions::inline_trivial_getters`]. Does not change any
+    /// analysis behavior on its own -- see that option's doc comment for
+    /// why.
+    pub fn with_inline_trivial_getters(mut self, enabled: bool) -> Self {
+        self.inline_trivial_getters = enabled;
+        self
+    }
+
+    pub fn inline_trivial_getters(self) -> bool {
+        self.inline_trivial_getters
+    }
+
+    /// If [`Self::promoted_bodies`] is enabled and `const_` is a reference to
+    /// a promoted constant (e.g. the promoted temporary backing a value like
+    /// `&[1, 2, 3]`), returns the MIR body computed for it, pulled in from
+    /// `tcx` on demand. Returns `None` for ordinary (non-promoted) constants,
+    /// or when promoted-body support is disabled.
+    pub fn promoted_body(self, const_: Const<'tcx>) -> Option<&'tcx Body<'tcx>> {
+        if !self.promoted_bodies {
+            return None;
+        }
+        let Const::Unevaluated(uv, _) = const_ else {
+            return None;
+        };
+        let promoted = uv.promoted?;
+        // Promoted constants are always for a body in the current crate.
+        Some(&self.tcx.promoted_mir(uv.def.expect_local())[promoted])
     }
 
     pub fn body(self) -> &'a Body<'tcx> {
@@ -357,10 +443,71 @@ impl<'tcx> Place<'tcx> {
                 };
                 (Vec::new(), kind)
             }
-            ProjectionElem::Index(..)
-            | ProjectionElem::Subslice { .. }
-            | ProjectionElem::Downcast(..)
-            | ProjectionElem::OpaqueCast(..) => (Vec::new(), ProjectionKind::Other),
+            ProjectionElem::Index(..) => {
+                // The offset isn't known statically, so there's no single
+                // sibling place to exclude. If the base is an array of a
+                // known, small-enough length (including one fixed by a
+                // const generic, once monomorphized/instantiated), we can
+                // still enumerate every element as a potential sibling;
+                // otherwise fall back to summarizing the whole base place,
+                // as for `Subslice` below.
+                let elem_ty = self.ty(repacker);
+                let array_len = match elem_ty.ty.kind() {
+                    TyKind::Array(_, len) => len.try_to_target_usize(repacker.tcx),
+                    _ => None,
+                };
+                let other_places = match array_len {
+                    Some(len) if len <= MAX_ARRAY_INDEX_EXPANSION => (0..len)
+                        .map(|offset| {
+                            repacker
+                                .tcx
+                                .mk_place_elem(
+                                    self.to_rust_place(repacker),
+                                    ProjectionElem::ConstantIndex {
+                                        offset,
+                                        min_length: len,
+                                        from_end: false,
+                                    },
+                                )
+                                .into()
+                        })
+                        .collect(),
+                    _ => vec![repacker
+                        .tcx
+                        .mk_place_elem(
+                            self.to_rust_place(repacker),
+                            ProjectionElem::Subslice {
+                                from: 0,
+                                to: 0,
+                                from_end: true,
+                            },
+                        )
+                        .into()],
+                };
+                (other_places, ProjectionKind::Other)
+            }
+            ProjectionElem::Subslice { .. } => {
+                // We don't have a precise sibling set here: the complement
+                // of a dynamic sub-range isn't expressible as a single
+                // place either. Rather than dropping the non-selected part
+                // of the slice entirely (which would silently stop tracking
+                // its capability), summarize it as the whole base place.
+                let other_places = vec![repacker
+                    .tcx
+                    .mk_place_elem(
+                        self.to_rust_place(repacker),
+                        ProjectionElem::Subslice {
+                            from: 0,
+                            to: 0,
+                            from_end: true,
+                        },
+                    )
+                    .into()];
+                (other_places, ProjectionKind::Other)
+            }
+            ProjectionElem::Downcast(..) | ProjectionElem::OpaqueCast(..) => {
+                (Vec::new(), ProjectionKind::Other)
+            }
             _ => todo!(),
         };
         for p in other_places.iter() {
@@ -376,6 +523,11 @@ impl<'tcx> Place<'tcx> {
     /// each of the struct's fields `{x.f.g.f, x.f.g.g, x.f.g.h}`. If
     /// `without_field` is not `None`, then omits that field from the final
     /// vector.
+    ///
+    /// This is also used for unions, whose "fields" are represented the same
+    /// way at the type level even though they overlap in memory; callers
+    /// that assign capabilities to the returned places must account for that
+    /// overlap themselves (see [`Self::is_union_field`]).
     pub fn expand_field(
         self,
         without_field: Option<usize>,
@@ -488,6 +640,30 @@ impl<'tcx> Place<'tcx> {
         }
     }
 
+    /// True if `self` projects a field of a union. Such fields overlap in
+    /// memory with their siblings, so (unlike struct/tuple fields) they
+    /// can't be assumed to carry independent capabilities from each other.
+    pub(crate) fn is_union_field(self, repacker: CompilerCtxt<'_, 'tcx>) -> bool {
+        match self.last_projection() {
+            Some((parent, ProjectionElem::Field(..))) => {
+                matches!(parent.ty(repacker).ty.kind(), TyKind::Adt(def, _) if def.is_union())
+            }
+            _ => false,
+        }
+    }
+
+    /// True if `self`'s own type is `UnsafeCell<T>` (not merely containing
+    /// one nested somewhere inside, e.g. in a field). Used to grant
+    /// [`CapabilityKind::ShallowExclusive`]-style write capability to
+    /// `UnsafeCell` interiors under a shared borrow; see
+    /// [`crate::utils::UNSAFE_CELL_WRITE_CAPABILITY`].
+    pub(crate) fn is_unsafe_cell(self, repacker: CompilerCtxt<'_, 'tcx>) -> bool {
+        matches!(
+            self.ty(repacker).ty.kind(),
+            TyKind::Adt(def, _) if repacker.tcx().is_diagnostic_item(crate::rustc_interface::span::sym::UnsafeCell, def.did())
+        )
+    }
+
     pub(crate) fn projects_shared_ref(self, repacker: CompilerCtxt<'_, 'tcx>) -> bool {
         self.projects_ty(
             |typ| {
@@ -1,4 +1,4 @@
-use crate::pcg::PcgError;
+use crate::pcg::{PCGUnsupportedError, PcgError};
 use crate::rustc_interface::middle::mir::{
     self,
     visit::{self},
@@ -122,7 +122,7 @@ pub(crate) trait FallableVisitor<'tcx> {
             mir::StatementKind::Nop => {
                 // No places to visit
             }
-            _ => todo!(),
+            _ => return Err(PCGUnsupportedError::UnsupportedStatement.into()),
         }
         Ok(())
     }
@@ -362,7 +362,9 @@ pub(crate) trait FallableVisitor<'tcx> {
                 }
                 Ok(())
             }
-            mir::TerminatorKind::TailCall { .. } => todo!(),
+            mir::TerminatorKind::TailCall { .. } => {
+                Err(PCGUnsupportedError::TailCall.into())
+            }
         }
     }
 }
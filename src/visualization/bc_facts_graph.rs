@@ -81,6 +81,19 @@ pub fn subset_anywhere<'a, 'tcx: 'a, 'bc>(
     graph
 }
 
+/// Maps [`RegionVid`]s to display names, e.g. source-level lifetime names
+/// like `'a` instead of `'?12`. `BorrowCheckerImpl`/`PoloniusBorrowChecker`
+/// each expose one via a `pub pretty_printer` field (and consult it from
+/// `override_region_debug_string`, which feeds `RegionVid`'s
+/// `DisplayWithCompilerCtxt` impl and hence the visualization JSON).
+/// Apart from the names `run_pcg_on_fn` (in `utils::callbacks`) reads from
+/// `PCG_LIFETIME_DISPLAY: ` source comments, populating it is left to the
+/// embedder: this crate doesn't have a
+/// reliable, version-stable way to recover a region's originating HIR
+/// lifetime from `RegionInferenceContext` alone (its relevant fields are
+/// internal to `rustc_borrowck`), whereas an embedder driving its own
+/// `rustc_driver` callbacks can read that association off the HIR/AST
+/// directly before region inference erases it.
 #[derive(Clone)]
 pub struct RegionPrettyPrinter<'bc, 'tcx> {
     sccs: RefCell<Option<petgraph::Graph<Vec<RegionVid>, ()>>>,
@@ -89,7 +102,7 @@ pub struct RegionPrettyPrinter<'bc, 'tcx> {
 }
 
 impl<'bc, 'tcx> RegionPrettyPrinter<'bc, 'tcx> {
-    pub(crate) fn new(region_infer_ctxt: &'bc RegionInferenceContext<'tcx>) -> Self {
+    pub fn new(region_infer_ctxt: &'bc RegionInferenceContext<'tcx>) -> Self {
         RegionPrettyPrinter {
             region_to_string: BTreeMap::new(),
             sccs: RefCell::new(None),
@@ -97,7 +110,10 @@ impl<'bc, 'tcx> RegionPrettyPrinter<'bc, 'tcx> {
         }
     }
 
-    pub(crate) fn insert(&mut self, region: RegionVid, string: String) {
+    /// Registers `string` as the display name for `region`. Panics if
+    /// `region` already has a name, since names are expected to be
+    /// assigned once, up front, before any [`Self::lookup`] is served.
+    pub fn insert(&mut self, region: RegionVid, string: String) {
         assert!(self.region_to_string.insert(region, string).is_none());
         self.sccs.borrow_mut().take();
     }
@@ -88,9 +88,46 @@ impl Display for DotGraph {
         write!(f, "digraph {} {{", self.name)?;
         writeln!(f, "layout=dot")?;
         writeln!(f, "node [shape=rect]")?;
+
+        // Group clustered nodes (see `PlaceCluster`) by base local, each in
+        // its own `subgraph cluster_...`, with old places for that local
+        // nested in a further sub-cluster. Nodes with no natural base local
+        // (e.g. statics, region projections) are rendered flat, as before.
+        let locals: BTreeSet<usize> = self
+            .nodes
+            .iter()
+            .filter_map(|n| n.cluster.map(|c| c.local))
+            .collect();
+        for local in &locals {
+            writeln!(f, "subgraph cluster_local_{local} {{")?;
+            writeln!(f, "label=\"_{local}\"; style=dashed; color=gray;")?;
+            for node in &self.nodes {
+                if matches!(node.cluster, Some(c) if c.local == *local && !c.old) {
+                    writeln!(f, "{node}")?;
+                }
+            }
+            if self
+                .nodes
+                .iter()
+                .any(|n| matches!(n.cluster, Some(c) if c.local == *local && c.old))
+            {
+                writeln!(f, "subgraph cluster_local_{local}_old {{")?;
+                writeln!(f, "label=\"old\"; style=dotted; color=gray;")?;
+                for node in &self.nodes {
+                    if matches!(node.cluster, Some(c) if c.local == *local && c.old) {
+                        writeln!(f, "{node}")?;
+                    }
+                }
+                writeln!(f, "}}")?;
+            }
+            writeln!(f, "}}")?;
+        }
         for node in &self.nodes {
-            writeln!(f, "{node}")?;
+            if node.cluster.is_none() {
+                writeln!(f, "{node}")?;
+            }
         }
+
         for edge in &self.edges {
             writeln!(f, "{edge}")?;
         }
@@ -114,6 +151,17 @@ impl Display for DotLabel {
 
 impl DotAttr for DotLabel {}
 
+/// Identifies the `subgraph cluster_...` a [`DotNode`] should be grouped
+/// under in [`DotGraph`]'s output: all nodes for the same base local are
+/// grouped together, with old (pre-snapshot) places for that local nested
+/// in a visually distinct sub-cluster, so that borrow graphs for functions
+/// with many locals don't render as a single undifferentiated tangle.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct PlaceCluster {
+    pub local: usize,
+    pub old: bool,
+}
+
 pub struct DotNode {
     pub id: NodeId,
     pub label: DotLabel,
@@ -123,6 +171,7 @@ pub struct DotNode {
     pub style: Option<DotStringAttr>,
     pub penwidth: Option<DotFloatAttr>,
     pub tooltip: Option<DotStringAttr>,
+    pub cluster: Option<PlaceCluster>,
 }
 
 impl DotNode {
@@ -136,6 +185,7 @@ impl DotNode {
             style: None,
             penwidth: None,
             tooltip: None,
+            cluster: None,
         }
     }
 }
@@ -251,6 +301,10 @@ impl EdgeOptions {
         self
     }
 
+    pub(crate) fn label(&self) -> &str {
+        &self.label
+    }
+
     pub fn with_color(mut self, color: String) -> Self {
         self.color = Some(color);
         self
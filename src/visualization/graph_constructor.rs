@@ -23,10 +23,12 @@ use crate::borrow_pcg::edge::abstraction::AbstractionType;
 use crate::utils::place::maybe_old::MaybeOldPlace;
 use crate::utils::place::maybe_remote::MaybeRemotePlace;
 use crate::utils::place::remote::RemotePlace;
+use crate::utils::place::static_place::StaticPlace;
 use std::collections::{BTreeSet, HashSet};
 
 pub(super) struct GraphConstructor<'mir, 'tcx> {
     remote_nodes: IdLookup<RemotePlace>,
+    static_nodes: IdLookup<StaticPlace>,
     place_nodes: IdLookup<(Place<'tcx>, Option<SnapshotLocation>)>,
     region_projection_nodes: IdLookup<RegionProjection<'tcx>>,
     nodes: Vec<GraphNode>,
@@ -39,6 +41,7 @@ impl<'a, 'tcx> GraphConstructor<'a, 'tcx> {
     fn new(ctxt: CompilerCtxt<'a, 'tcx>, location: Option<mir::Location>) -> Self {
         Self {
             remote_nodes: IdLookup::new('a'),
+            static_nodes: IdLookup::new('s'),
             place_nodes: IdLookup::new('p'),
             region_projection_nodes: IdLookup::new('r'),
             nodes: vec![],
@@ -71,12 +74,16 @@ impl<'a, 'tcx> GraphConstructor<'a, 'tcx> {
         }
         let id = self.region_projection_nodes.node_id(&projection);
         let base_ty = match projection.place() {
+            MaybeRemoteRegionProjectionBase::Place(MaybeRemotePlace::Static(sp)) => {
+                format!("{:?}", sp.def_id())
+            }
             MaybeRemoteRegionProjectionBase::Place(p) => {
                 format!("{:?}", p.related_local_place().ty(self.ctxt).ty)
             }
-            MaybeRemoteRegionProjectionBase::Const(c) => {
-                format!("{:?}", c.ty())
-            }
+            MaybeRemoteRegionProjectionBase::Const(c) => match self.ctxt.promoted_body(c) {
+                Some(body) => format!("{:?} (promoted: {:?})", c.ty(), body.return_ty()),
+                None => format!("{:?}", c.ty()),
+            },
         };
         let loans = if let Some(output) = self.ctxt.bc.polonius_output()
             && let Some(region_vid) = projection.region(self.ctxt).vid()
@@ -164,6 +171,9 @@ impl<'a, 'tcx> GraphConstructor<'a, 'tcx> {
             LoopAbstractionInput::Place(MaybeRemotePlace::Remote(place)) => {
                 self.insert_remote_node(place)
             }
+            LoopAbstractionInput::Place(MaybeRemotePlace::Static(place)) => {
+                self.insert_static_node(place)
+            }
         }
     }
 
@@ -206,20 +216,26 @@ impl<'a, 'tcx> GraphConstructor<'a, 'tcx> {
 
         let mut first = true;
 
-        // for i in 0..input_nodes.len() - 1{
-        //     self.edges.insert(GraphEdge::HyperedgeSameEndpoint {
-        //         source: input_nodes[i],
-        //         target: input_nodes[i + 1],
-        //         label: hyperedge_id.clone(),
-        //     });
-        // }
-        // for i in 0..output_nodes.len() - 1 {
-        //     self.edges.insert(GraphEdge::HyperedgeSameEndpoint {
-        //         source: output_nodes[i],
-        //         target: output_nodes[i + 1],
-        //         label: hyperedge_id.clone(),
-        //     });
-        // }
+        // Connect the inputs to each other, and the outputs to each other,
+        // with dashed same-endpoint edges sharing `hyperedge_id`, so a
+        // multi-input/multi-output abstraction (a true hyperedge, per
+        // `coupling::HyperEdge`) renders as a visually grouped bundle rather
+        // than `input_nodes.len() * output_nodes.len()` edges that look
+        // unrelated to each other.
+        for window in input_nodes.windows(2) {
+            self.edges.insert(GraphEdge::HyperedgeSameEndpoint {
+                source: window[0],
+                target: window[1],
+                label: hyperedge_id.clone(),
+            });
+        }
+        for window in output_nodes.windows(2) {
+            self.edges.insert(GraphEdge::HyperedgeSameEndpoint {
+                source: window[0],
+                target: window[1],
+                label: hyperedge_id.clone(),
+            });
+        }
 
         for input in &input_nodes {
             for output in &output_nodes {
@@ -250,6 +266,29 @@ impl<'a, 'tcx> GraphConstructor<'a, 'tcx> {
                 location: None,
                 capability: None,
                 ty: format!("{:?}", remote_place.ty(self.ctxt)),
+                cluster_local: Some(remote_place.assigned_local().as_usize()),
+            },
+        };
+        self.insert_node(node);
+        id
+    }
+
+    pub(super) fn insert_static_node(&mut self, static_place: StaticPlace) -> NodeId {
+        if let Some(id) = self.static_nodes.existing_id(&static_place) {
+            return id;
+        }
+        let id = self.static_nodes.node_id(&static_place);
+        let node = GraphNode {
+            id,
+            node_type: NodeType::PlaceNode {
+                owned: false,
+                label: format!("{static_place}"),
+                location: None,
+                capability: None,
+                ty: format!("{:?}", static_place.def_id()),
+                // Static items aren't projections from a MIR local, so they
+                // have no natural cluster to join.
+                cluster_local: None,
             },
         };
         self.insert_node(node);
@@ -275,6 +314,7 @@ impl<'a, 'tcx> GraphConstructor<'a, 'tcx> {
             capability,
             location,
             ty: format!("{:?}", place_ty.ty),
+            cluster_local: Some(place.local.as_usize()),
         };
         let node = GraphNode { id, node_type };
         self.insert_node(node);
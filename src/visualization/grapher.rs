@@ -32,6 +32,7 @@ pub(super) trait Grapher<'state, 'mir: 'state, 'tcx: 'mir> {
         match place {
             MaybeRemotePlace::Local(place) => self.insert_maybe_old_place(place),
             MaybeRemotePlace::Remote(local) => constructor.insert_remote_node(local),
+            MaybeRemotePlace::Static(sp) => constructor.insert_static_node(sp),
         }
     }
     fn insert_pcg_node(&mut self, node: PCGNode<'tcx>) -> NodeId {
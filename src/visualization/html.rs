@@ -0,0 +1,138 @@
+// © 2023, ETH Zurich
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Assembles a function's visualization directory (the per-block
+//! `block_N_iterations.json` index and the DOT files it points at, written
+//! by [`crate::pcg::dot_graphs`]) into a single self-contained
+//! `report.html`, so the PCG state for a function can be browsed with a
+//! statement slider and a phase toggle without installing a DOT viewer or
+//! running the separate `pcg-server`.
+//!
+//! This embeds the DOT source as text; it doesn't lay out or render the
+//! graphs itself; that would need a graph-layout backend, which is tracked
+//! as a separate piece of work.
+
+use std::{fs, path::Path};
+
+use serde_derive::{Deserialize, Serialize};
+
+#[derive(Deserialize)]
+struct DotGraphsForIteration {
+    at_phase: Vec<(String, String)>,
+}
+
+#[derive(Deserialize)]
+struct DotGraphsForStmt {
+    iterations: Vec<DotGraphsForIteration>,
+}
+
+#[derive(Serialize)]
+struct ReportEntry {
+    block: usize,
+    statement_index: usize,
+    iteration: usize,
+    phase: String,
+    dot: String,
+}
+
+/// Reads `{dir_path}/block_{0..num_blocks}_iterations.json` (written
+/// alongside the DOT files during analysis) and writes
+/// `{dir_path}/report.html`.
+pub(crate) fn write_html_report(dir_path: &str, num_blocks: usize) -> std::io::Result<()> {
+    let mut entries = Vec::new();
+    for block in 0..num_blocks {
+        let json_path = format!("{dir_path}/block_{block}_iterations.json");
+        let Ok(contents) = fs::read_to_string(&json_path) else {
+            continue;
+        };
+        let Ok(stmts) = serde_json::from_str::<Vec<DotGraphsForStmt>>(&contents) else {
+            continue;
+        };
+        for (statement_index, stmt) in stmts.into_iter().enumerate() {
+            for (iteration, it) in stmt.iterations.into_iter().enumerate() {
+                for (phase, filename) in it.at_phase {
+                    let dot = fs::read_to_string(Path::new(dir_path).join(&filename))
+                        .unwrap_or_default();
+                    entries.push(ReportEntry {
+                        block,
+                        statement_index,
+                        iteration,
+                        phase,
+                        dot,
+                    });
+                }
+            }
+        }
+    }
+    entries.sort_by_key(|e| (e.block, e.statement_index, e.iteration));
+    let data_json = serde_json::to_string(&entries).unwrap_or_else(|_| "[]".to_string());
+    fs::write(format!("{dir_path}/report.html"), render_html(&data_json))
+}
+
+fn render_html(data_json: &str) -> String {
+    format!(
+        r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+<meta charset="utf-8">
+<title>PCG report</title>
+<style>
+  body {{ font-family: sans-serif; margin: 1em; }}
+  #label {{ font-weight: bold; margin-bottom: 0.5em; }}
+  pre {{ background: #f5f5f5; padding: 1em; overflow: auto; white-space: pre-wrap; }}
+  select {{ margin-left: 1em; }}
+</style>
+</head>
+<body>
+<div id="label"></div>
+<div>
+  <input id="slider" type="range" min="0" value="0" style="width: 60%">
+  <select id="phase-filter"></select>
+</div>
+<pre id="dot"></pre>
+<script>
+const DATA = {data_json};
+const slider = document.getElementById("slider");
+const label = document.getElementById("label");
+const dot = document.getElementById("dot");
+const phaseFilter = document.getElementById("phase-filter");
+
+function visibleEntries() {{
+  const phase = phaseFilter.value;
+  return phase === "__all__" ? DATA : DATA.filter(e => e.phase === phase);
+}}
+
+function render() {{
+  const entries = visibleEntries();
+  slider.max = Math.max(0, entries.length - 1);
+  const entry = entries[Math.min(slider.value, entries.length - 1)];
+  if (!entry) {{
+    label.textContent = "(no statements for this phase)";
+    dot.textContent = "";
+    return;
+  }}
+  label.textContent =
+    `bb${{entry.block}}[${{entry.statement_index}}] iteration ${{entry.iteration}} ${{entry.phase}}`;
+  dot.textContent = entry.dot;
+}}
+
+const phases = ["__all__", ...new Set(DATA.map(e => e.phase))];
+for (const phase of phases) {{
+  const option = document.createElement("option");
+  option.value = phase;
+  option.textContent = phase === "__all__" ? "all phases" : phase;
+  phaseFilter.appendChild(option);
+}}
+
+slider.addEventListener("input", render);
+phaseFilter.addEventListener("change", () => {{ slider.value = 0; render(); }});
+render();
+</script>
+</body>
+</html>
+"#
+    )
+}
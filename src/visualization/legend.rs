@@ -6,19 +6,76 @@ use crate::{
 use super::dot_graph::{DotEdge, DotLabel, DotNode, DotStringAttr, EdgeDirection, EdgeOptions};
 use std::io::{self, Write};
 
-pub fn generate_edge_legend() -> io::Result<String> {
+/// Which edge kind a row of [`generate_edge_legend`]'s output documents.
+/// Lets a [`VisualizationStyle`] drop rows for edge kinds a downstream tool
+/// never surfaces (e.g. a simplified viewer that doesn't show coupled
+/// edges) instead of always rendering the full fixed set.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EdgeLegendKind {
+    Projection,
+    Reborrow,
+    DerefExpansion,
+    Abstract,
+    RegionProjection,
+    Coupled,
+}
+
+impl EdgeLegendKind {
+    pub const ALL: [EdgeLegendKind; 6] = [
+        EdgeLegendKind::Projection,
+        EdgeLegendKind::Reborrow,
+        EdgeLegendKind::DerefExpansion,
+        EdgeLegendKind::Abstract,
+        EdgeLegendKind::RegionProjection,
+        EdgeLegendKind::Coupled,
+    ];
+}
+
+/// Customization for [`generate_edge_legend`]/[`generate_node_legend`]'s
+/// output, configurable via
+/// [`PcgOptions::builder`](crate::PcgOptions::builder) so downstream tools
+/// can re-brand or simplify the legend (e.g. to match a host UI's palette,
+/// or hide edge kinds it doesn't render) without forking this crate.
+///
+/// This only covers the standalone legend graphs. The per-statement PCG
+/// graphs themselves (see [`super::generate_pcg_dot_graph`]) have their
+/// node/edge colors and shapes baked in at construction time, deep inside
+/// the dataflow engine with no access to [`PcgOptions`](crate::PcgOptions);
+/// re-threading that is a much larger change and is out of scope here.
+#[derive(Clone, Debug)]
+pub struct VisualizationStyle {
+    pub owned_node_color: String,
+    pub borrowed_node_color: String,
+    pub region_projection_node_color: String,
+    pub node_shape: String,
+    pub edge_kinds: Vec<EdgeLegendKind>,
+}
+
+impl Default for VisualizationStyle {
+    fn default() -> Self {
+        Self {
+            owned_node_color: "black".to_string(),
+            borrowed_node_color: "darkgreen".to_string(),
+            region_projection_node_color: "blue".to_string(),
+            node_shape: "rect".to_string(),
+            edge_kinds: EdgeLegendKind::ALL.to_vec(),
+        }
+    }
+}
+
+pub fn generate_edge_legend(style: &VisualizationStyle) -> io::Result<String> {
     let mut buf = vec![];
-    write_edge_legend(&mut buf)?;
+    write_edge_legend(&mut buf, style)?;
     Ok(String::from_utf8(buf).unwrap())
 }
 
-pub fn generate_node_legend() -> io::Result<String> {
+pub fn generate_node_legend(style: &VisualizationStyle) -> io::Result<String> {
     let mut buf = vec![];
-    write_node_legend(&mut buf)?;
+    write_node_legend(&mut buf, style)?;
     Ok(String::from_utf8(buf).unwrap())
 }
 
-fn write_edge_legend<T: Write>(out: &mut T) -> io::Result<()> {
+fn write_edge_legend<T: Write>(out: &mut T, style: &VisualizationStyle) -> io::Result<()> {
     writeln!(out, "digraph edge_legend {{")?;
     writeln!(out, "  node [shape=rect];")?;
     writeln!(out, "  rankdir=TB;")?;
@@ -27,72 +84,79 @@ fn write_edge_legend<T: Write>(out: &mut T) -> io::Result<()> {
     writeln!(out, "  nodesep=0.5;")?;
     writeln!(out, "  ranksep=2.0;")?;
 
-    // Create all clusters first
-    // Projection Edge
-    write_edge(
-        out,
-        "proj_a",
-        "proj_b",
-        "Projection Edge",
-        EdgeOptions::undirected(),
-    )?;
+    let shows = |kind: EdgeLegendKind| style.edge_kinds.contains(&kind);
 
-    // Reborrow Edge
-    write_edge(
-        out,
-        "reborrow_a",
-        "reborrow_b",
-        "Reborrow Edge",
-        EdgeOptions::directed(EdgeDirection::Forward)
-            .with_color("orange".to_string())
-            .with_label("region".to_string())
-            .with_tooltip("conditions".to_string()),
-    )?;
+    if shows(EdgeLegendKind::Projection) {
+        write_edge(
+            out,
+            "proj_a",
+            "proj_b",
+            "Projection Edge",
+            EdgeOptions::undirected(),
+        )?;
+    }
 
-    // Deref Expansion Edge
-    write_edge(
-        out,
-        "deref_a",
-        "deref_b",
-        "Deref Expansion Edge",
-        EdgeOptions::undirected()
-            .with_color("green".to_string())
-            .with_tooltip("conditions".to_string()),
-    )?;
+    if shows(EdgeLegendKind::Reborrow) {
+        write_edge(
+            out,
+            "reborrow_a",
+            "reborrow_b",
+            "Reborrow Edge",
+            EdgeOptions::directed(EdgeDirection::Forward)
+                .with_color("orange".to_string())
+                .with_label("region".to_string())
+                .with_tooltip("conditions".to_string()),
+        )?;
+    }
 
-    // Abstract Edge
-    write_edge(
-        out,
-        "abstract_a",
-        "abstract_b",
-        "Abstract Edge",
-        EdgeOptions::directed(EdgeDirection::Forward),
-    )?;
+    if shows(EdgeLegendKind::DerefExpansion) {
+        write_edge(
+            out,
+            "deref_a",
+            "deref_b",
+            "Deref Expansion Edge",
+            EdgeOptions::undirected()
+                .with_color("green".to_string())
+                .with_tooltip("conditions".to_string()),
+        )?;
+    }
 
-    // Region Projection Member Edge
-    write_edge(
-        out,
-        "region_a",
-        "region_b",
-        "Region Projection Edge",
-        EdgeOptions::directed(EdgeDirection::Forward).with_color("purple".to_string()),
-    )?;
+    if shows(EdgeLegendKind::Abstract) {
+        write_edge(
+            out,
+            "abstract_a",
+            "abstract_b",
+            "Abstract Edge",
+            EdgeOptions::directed(EdgeDirection::Forward),
+        )?;
+    }
 
-    // Coupled Edge
-    write_edge(
-        out,
-        "coupled_a",
-        "coupled_b",
-        "Coupled Edge",
-        EdgeOptions::undirected()
-            .with_color("red".to_string())
-            .with_style("dashed".to_string()),
-    )?;
+    if shows(EdgeLegendKind::RegionProjection) {
+        write_edge(
+            out,
+            "region_a",
+            "region_b",
+            "Region Projection Edge",
+            EdgeOptions::directed(EdgeDirection::Forward).with_color("purple".to_string()),
+        )?;
+    }
+
+    if shows(EdgeLegendKind::Coupled) {
+        write_edge(
+            out,
+            "coupled_a",
+            "coupled_b",
+            "Coupled Edge",
+            EdgeOptions::undirected()
+                .with_color("red".to_string())
+                .with_style("dashed".to_string()),
+        )?;
+    }
 
     writeln!(out, "}}")
 }
 
-fn write_node_legend<T: Write>(out: &mut T) -> io::Result<()> {
+fn write_node_legend<T: Write>(out: &mut T, style: &VisualizationStyle) -> io::Result<()> {
     writeln!(out, "digraph node_legend {{")?;
     writeln!(out, "  node [shape=rect];")?;
     writeln!(out, "  rankdir=TB;")?;
@@ -108,6 +172,7 @@ fn write_node_legend<T: Write>(out: &mut T) -> io::Result<()> {
             capability: Some(CapabilityKind::Write),
             location: None,
             ty: "&'a mut i32".to_string(),
+            cluster_local: None,
         },
     };
 
@@ -128,13 +193,31 @@ fn write_node_legend<T: Write>(out: &mut T) -> io::Result<()> {
             location: None,
             capability: None,
             ty: "i32".to_string(),
+            cluster_local: None,
         },
     };
 
-    // Write nodes using to_dot_node()
-    writeln!(out, "  {}", owned_node.to_dot_node())?;
-    writeln!(out, "  {}", region_node.to_dot_node())?;
-    writeln!(out, "  {}", borrowed_node.to_dot_node())?;
+    // Write nodes using to_dot_node(), then apply `style`'s colors/shape on
+    // top (the automatic coloring in `GraphNode::to_dot_node` is derived
+    // from capability/ownership, which the legend's example nodes are only
+    // standing in for).
+    let mut owned_dot = owned_node.to_dot_node();
+    owned_dot.color = DotStringAttr(style.owned_node_color.clone());
+    owned_dot.font_color = DotStringAttr(style.owned_node_color.clone());
+    owned_dot.shape = DotStringAttr(style.node_shape.clone());
+
+    let mut region_dot = region_node.to_dot_node();
+    region_dot.color = DotStringAttr(style.region_projection_node_color.clone());
+    region_dot.font_color = DotStringAttr(style.region_projection_node_color.clone());
+
+    let mut borrowed_dot = borrowed_node.to_dot_node();
+    borrowed_dot.color = DotStringAttr(style.borrowed_node_color.clone());
+    borrowed_dot.font_color = DotStringAttr(style.borrowed_node_color.clone());
+    borrowed_dot.shape = DotStringAttr(style.node_shape.clone());
+
+    writeln!(out, "  {owned_dot}")?;
+    writeln!(out, "  {region_dot}")?;
+    writeln!(out, "  {borrowed_dot}")?;
 
     // Arrange nodes horizontally
     writeln!(
@@ -162,6 +245,7 @@ fn write_edge<T: Write>(
         style: None,
         penwidth: None,
         tooltip: None,
+        cluster: None,
     };
 
     let node_b = DotNode {
@@ -173,6 +257,7 @@ fn write_edge<T: Write>(
         style: None,
         penwidth: None,
         tooltip: None,
+        cluster: None,
     };
 
     let edge = DotEdge {
@@ -0,0 +1,71 @@
+// © 2023, ETH Zurich
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Renders a [`DotGraph`] as a Mermaid `flowchart` instead of DOT, so a
+//! per-statement PCG graph can be pasted directly into a GitHub issue,
+//! comment, or markdown doc (all of which render Mermaid code blocks
+//! natively) without needing `dot`, a browser, or this crate's own
+//! [`crate::visualization::svg`] renderer.
+
+use super::dot_graph::{DotGraph, DotLabel};
+
+fn mermaid_id(id: &str) -> String {
+    // Mermaid node IDs can't contain most punctuation that shows up in our
+    // DOT node ids (e.g. `*`, `[`, `.`), so hash-free sanitize by replacing
+    // every non-alphanumeric character with `_`.
+    id.chars()
+        .map(|c| if c.is_alphanumeric() { c } else { '_' })
+        .collect()
+}
+
+fn plain_text(label: &DotLabel) -> String {
+    let raw = match label {
+        DotLabel::Text(text) => text.clone(),
+        DotLabel::Html(html) => html.clone(),
+    };
+    let mut out = String::new();
+    let mut in_tag = false;
+    for c in raw.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            _ if !in_tag => out.push(c),
+            _ => {}
+        }
+    }
+    out.replace("&nbsp;", " ")
+}
+
+fn escape_label(text: &str) -> String {
+    // Mermaid node labels are quoted strings; escape embedded quotes.
+    text.replace('"', "#quot;")
+}
+
+/// Renders `graph` as a Mermaid `flowchart` definition (without the
+/// surrounding ` ```mermaid ` code fence, so callers can embed it in a
+/// larger document).
+pub(crate) fn render_mermaid(graph: &DotGraph) -> String {
+    let mut out = String::from("flowchart TD\n");
+    for node in &graph.nodes {
+        let id = mermaid_id(&node.id);
+        let label = escape_label(&plain_text(&node.label));
+        out.push_str(&format!("    {id}[\"{label}\"]\n"));
+    }
+    for edge in &graph.edges {
+        let from = mermaid_id(&edge.from);
+        let to = mermaid_id(&edge.to);
+        let label = edge.options.label();
+        if label.is_empty() {
+            out.push_str(&format!("    {from} --> {to}\n"));
+        } else {
+            out.push_str(&format!(
+                "    {from} -->|\"{}\"| {to}\n",
+                escape_label(&label)
+            ));
+        }
+    }
+    out
+}
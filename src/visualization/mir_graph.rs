@@ -27,6 +27,7 @@ struct MirGraph {
 #[derive(Serialize)]
 struct MirStmt {
     stmt: String,
+    span: String,
     loans_invalidated_start: Vec<String>,
     loans_invalidated_mid: Vec<String>,
 }
@@ -37,6 +38,19 @@ struct MirNode {
     block: usize,
     stmts: Vec<MirStmt>,
     terminator: String,
+    /// Loop nesting depth of this block (0 = not in a loop), from
+    /// [`crate::r#loop::LoopAnalysis::loop_depth`]. There's no peeled/
+    /// unrolled structure to show here: this crate analyzes the `Body`
+    /// rustc hands it as-is, and peeling the first k iterations of a loop
+    /// before a fixpoint run would mean handing the dataflow engine an
+    /// owned, rewritten `Body` with duplicated blocks (renumbered locals,
+    /// re-wired back-edges) rather than the borrowed one [`run_pcg`]
+    /// takes today -- the same owned-vs-borrowed-`Body` obstacle noted on
+    /// [`crate::PcgOptionsBuilder::inline_trivial_getters`]. This field is
+    /// the scoped substitute: it doesn't sharpen the invariant state at a
+    /// loop head, but it does let the visualization highlight which
+    /// blocks a sharper, peeled analysis would affect.
+    loop_depth: usize,
 }
 
 #[derive(Serialize)]
@@ -136,7 +150,7 @@ fn format_rvalue<'tcx>(rvalue: &Rvalue<'tcx>, ctxt: CompilerCtxt<'_, 'tcx>) -> S
             format!("&{} {}", kind, format_place(place, ctxt))
         }
         Rvalue::RawPtr(kind, place) => format_raw_ptr(kind, place, ctxt),
-        Rvalue::ThreadLocalRef(_) => todo!(),
+        Rvalue::ThreadLocalRef(def_id) => format!("thread_local_ref({def_id:?})"),
         Rvalue::Len(x) => format!("len({})", format_place(x, ctxt)),
         Rvalue::Cast(_, operand, ty) => format!("{} as {}", format_operand(operand, ctxt), ty),
         Rvalue::BinaryOp(op, box (lhs, rhs)) => {
@@ -240,12 +254,18 @@ fn format_stmt<'tcx>(stmt: &Statement<'tcx>, repacker: CompilerCtxt<'_, 'tcx>) -
 fn mk_mir_graph(ctxt: CompilerCtxt<'_, '_>) -> MirGraph {
     let mut nodes = Vec::new();
     let mut edges = Vec::new();
+    let loop_analysis = crate::r#loop::LoopAnalysis::find_loops(ctxt.body());
 
     for (bb, data) in ctxt.body().basic_blocks.iter_enumerated() {
-        let stmts = data.statements.iter().enumerate().map(|(idx, stmt)| {
-            let stmt = format_stmt(stmt, ctxt);
+        let stmts = data.statements.iter().enumerate().map(|(idx, raw_stmt)| {
+            let span = format!("{:?}", raw_stmt.source_info.span);
+            let stmt = format_stmt(raw_stmt, ctxt);
             let bc = ctxt.bc;
-            let invalidated_at = &bc.input_facts().loan_invalidated_at;
+            let no_invalidations = Default::default();
+            let invalidated_at = bc
+                .input_facts()
+                .map(|facts| &facts.loan_invalidated_at)
+                .unwrap_or(&no_invalidations);
             let location = mir::Location {
                 block: bb,
                 statement_index: idx,
@@ -274,6 +294,7 @@ fn mk_mir_graph(ctxt: CompilerCtxt<'_, '_>) -> MirGraph {
                 .collect::<Vec<_>>();
             MirStmt {
                 stmt,
+                span,
                 loans_invalidated_start,
                 loans_invalidated_mid,
             }
@@ -286,6 +307,7 @@ fn mk_mir_graph(ctxt: CompilerCtxt<'_, '_>) -> MirGraph {
             block: bb.as_usize(),
             stmts: stmts.collect(),
             terminator,
+            loop_depth: loop_analysis.loop_depth(bb),
         });
 
         match &data.terminator().kind {
@@ -9,10 +9,14 @@ pub mod bc_facts_graph;
 pub mod dot_graph;
 pub mod drawer;
 pub mod graph_constructor;
+pub mod graphml;
 mod grapher;
+pub(crate) mod html;
 pub mod legend;
+pub mod mermaid;
 pub mod mir_graph;
 mod node;
+pub mod svg;
 
 use crate::{
     borrow_pcg::{edge::outlives::BorrowFlowEdgeKind, graph::BorrowsGraph},
@@ -33,6 +37,7 @@ use graph_constructor::BorrowsGraphConstructor;
 use self::{
     dot_graph::{
         DotEdge, DotFloatAttr, DotLabel, DotNode, DotStringAttr, EdgeDirection, EdgeOptions,
+        PlaceCluster,
     },
     graph_constructor::PcgGraphConstructor,
 };
@@ -41,6 +46,72 @@ pub fn place_id(place: &Place<'_>) -> String {
     format!("{place:?}")
 }
 
+/// Which format [`run_pcg`](crate::run_pcg)'s visualization output is
+/// written in, controlled by the `PCG_OUTPUT_FORMAT` environment variable
+/// (`dot`, the default, or `svg`).
+#[derive(Copy, Clone, PartialEq, Eq)]
+pub enum OutputFormat {
+    Dot,
+    Svg,
+}
+
+impl OutputFormat {
+    pub fn extension(self) -> &'static str {
+        match self {
+            OutputFormat::Dot => "dot",
+            OutputFormat::Svg => "svg",
+        }
+    }
+}
+
+lazy_static::lazy_static! {
+    pub static ref OUTPUT_FORMAT: OutputFormat = match std::env::var("PCG_OUTPUT_FORMAT") {
+        Ok(val) if val == "svg" => OutputFormat::Svg,
+        Ok(val) if val == "dot" || val.is_empty() => OutputFormat::Dot,
+        Ok(val) => panic!("Environment variable PCG_OUTPUT_FORMAT has unexpected value: '{val}'. Expected one of: dot, svg"),
+        Err(_) => OutputFormat::Dot,
+    };
+}
+
+/// Restricts graph output to the subgraph reachable (in either direction)
+/// from a chosen set of locals/places, so visualizing a large function
+/// doesn't require scrolling through its entire PCG. Construct
+/// programmatically with [`GraphFilter::new`], or rely on [`PLACE_FILTER`]
+/// to pick one up from the environment.
+#[derive(Clone, Debug, Default)]
+pub struct GraphFilter {
+    seeds: HashSet<String>,
+}
+
+impl GraphFilter {
+    pub fn new(seeds: impl IntoIterator<Item = String>) -> Self {
+        Self {
+            seeds: seeds.into_iter().collect(),
+        }
+    }
+
+    /// A node's label "matches" the filter if it contains one of the seed
+    /// strings, since place labels for projections (e.g. `*_1`) don't equal
+    /// the bare local name (`_1`) a caller is likely to pass as a seed.
+    fn matches(&self, label: &str) -> bool {
+        self.seeds.iter().any(|seed| label.contains(seed.as_str()))
+    }
+}
+
+lazy_static::lazy_static! {
+    /// The filter applied to the per-statement DOT/SVG files written by
+    /// [`write_pcg_dot_graph_to_file`] (the other graph renderers in this
+    /// module do not apply it), configured via the
+    /// `PCG_FILTER_PLACES` environment variable (a comma-separated list of
+    /// place strings, e.g. `_1,_2`). `None` (the default) disables filtering.
+    pub static ref PLACE_FILTER: Option<GraphFilter> = match std::env::var("PCG_FILTER_PLACES") {
+        Ok(val) if !val.is_empty() => Some(GraphFilter::new(
+            val.split(',').map(|s| s.trim().to_string())
+        )),
+        _ => None,
+    };
+}
+
 pub struct GraphDrawer<T: io::Write> {
     out: T,
 }
@@ -75,6 +146,7 @@ impl GraphNode {
                 location,
                 label,
                 ty,
+                cluster_local,
             } => {
                 let capability_text = match capability {
                     Some(k) => format!("{k:?}"),
@@ -117,6 +189,10 @@ impl GraphNode {
                     style,
                     penwidth,
                     tooltip: Some(DotStringAttr(ty.clone())),
+                    cluster: cluster_local.map(|local| PlaceCluster {
+                        local,
+                        old: location.is_some(),
+                    }),
                 }
             }
             NodeType::RegionProjectionNode {
@@ -134,6 +210,11 @@ impl GraphNode {
                     style: None,
                     penwidth: None,
                     tooltip: Some(DotStringAttr(format!("{place_ty}\\\n{loans}"))),
+                    // Region projections aren't tied to a single base local
+                    // the way place nodes are (their base can be a remote
+                    // region projection, a promoted constant, ...), so they
+                    // aren't clustered.
+                    cluster: None,
                 }
             }
         }
@@ -148,6 +229,10 @@ enum NodeType {
         capability: Option<CapabilityKind>,
         location: Option<SnapshotLocation>,
         ty: String,
+        /// The base local this place projects from, if it has one (e.g.
+        /// `None` for static items), used to group nodes into DOT clusters;
+        /// see [`dot_graph::PlaceCluster`].
+        cluster_local: Option<usize>,
     },
     RegionProjectionNode {
         label: String,
@@ -159,10 +244,14 @@ enum NodeType {
 impl NodeType {
     #[cfg(test)]
     pub(crate) fn label(&self) -> String {
-        match self {
-            NodeType::PlaceNode { label, .. } => label.clone(),
-            NodeType::RegionProjectionNode { label, .. } => label.clone(),
-        }
+        node_label(self).to_string()
+    }
+}
+
+fn node_label(node_type: &NodeType) -> &str {
+    match node_type {
+        NodeType::PlaceNode { label, .. } => label,
+        NodeType::RegionProjectionNode { label, .. } => label,
     }
 }
 
@@ -346,6 +435,81 @@ impl Graph {
                 label1, label2
             ))
     }
+
+    /// Restricts this graph to the subgraph reachable, in either direction
+    /// over its edges, from any node whose label matches `filter`.
+    fn restrict_to(&self, filter: &GraphFilter) -> Graph {
+        let mut adjacency: std::collections::HashMap<String, Vec<String>> =
+            std::collections::HashMap::new();
+        for edge in &self.edges {
+            let dot_edge = edge.to_dot_edge();
+            adjacency
+                .entry(dot_edge.from.clone())
+                .or_default()
+                .push(dot_edge.to.clone());
+            adjacency
+                .entry(dot_edge.to.clone())
+                .or_default()
+                .push(dot_edge.from.clone());
+        }
+
+        let mut queue: std::collections::VecDeque<String> = self
+            .nodes
+            .iter()
+            .filter(|n| filter.matches(node_label(&n.node_type)))
+            .map(|n| n.id.to_string())
+            .collect();
+        let mut reachable: HashSet<String> = queue.iter().cloned().collect();
+        while let Some(id) = queue.pop_front() {
+            for neighbor in adjacency.get(&id).into_iter().flatten() {
+                if reachable.insert(neighbor.clone()) {
+                    queue.push_back(neighbor.clone());
+                }
+            }
+        }
+
+        let nodes = self
+            .nodes
+            .iter()
+            .filter(|n| reachable.contains(&n.id.to_string()))
+            .cloned()
+            .collect();
+        let edges = self
+            .edges
+            .iter()
+            .filter(|e| {
+                let dot_edge = e.to_dot_edge();
+                reachable.contains(&dot_edge.from) && reachable.contains(&dot_edge.to)
+            })
+            .cloned()
+            .collect();
+        Graph::new(nodes, edges)
+    }
+
+    /// Per-node cluster metadata (base local + old-vs-current) mirroring
+    /// the `subgraph` grouping the DOT writer applies (see
+    /// [`dot_graph::DotGraph`]'s `Display` impl), so a viewer of the JSON
+    /// visualization output can replicate the same grouping without
+    /// re-deriving it from place labels. Nodes with no natural cluster
+    /// (statics, region projections) are simply absent from the map.
+    fn cluster_metadata_json(&self) -> serde_json::Value {
+        let entries = self
+            .nodes
+            .iter()
+            .filter_map(|n| match &n.node_type {
+                NodeType::PlaceNode {
+                    cluster_local: Some(local),
+                    location,
+                    ..
+                } => Some((
+                    n.id.to_string(),
+                    serde_json::json!({ "local": local, "old": location.is_some() }),
+                )),
+                _ => None,
+            })
+            .collect::<serde_json::Map<_, _>>();
+        serde_json::Value::Object(entries)
+    }
 }
 
 pub(crate) fn generate_borrows_dot_graph<'a, 'tcx: 'a, 'bc>(
@@ -373,6 +537,40 @@ pub(crate) fn generate_pcg_dot_graph<'a, 'tcx: 'a>(
     Ok(String::from_utf8(buf).unwrap())
 }
 
+/// Renders the PCG graph at `location` as a Mermaid flowchart (see
+/// [`mermaid::render_mermaid`]), so it can be pasted into a GitHub issue or
+/// markdown doc without any rendering infrastructure.
+pub fn generate_pcg_mermaid_graph<'a, 'tcx: 'a>(
+    pcg: &Pcg<'tcx>,
+    ctxt: CompilerCtxt<'a, 'tcx>,
+    location: Location,
+) -> String {
+    let constructor = PcgGraphConstructor::new(pcg, ctxt, location);
+    let graph = constructor.construct_graph();
+    let dot_graph = dot_graph::DotGraph {
+        name: "CapabilitySummary".to_string(),
+        nodes: graph.nodes.iter().map(|g| g.to_dot_node()).collect(),
+        edges: graph.edges.into_iter().map(|e| e.to_dot_edge()).collect(),
+    };
+    mermaid::render_mermaid(&dot_graph)
+}
+
+/// Renders the PCG graph at `location` as GraphML (see
+/// [`graphml::render_graphml`]), for loading into external graph-analysis
+/// tooling (Gephi, NetworkX, ...).
+pub fn generate_pcg_graphml<'a, 'tcx: 'a>(
+    pcg: &Pcg<'tcx>,
+    ctxt: CompilerCtxt<'a, 'tcx>,
+    location: Location,
+) -> String {
+    let constructor = PcgGraphConstructor::new(pcg, ctxt, location);
+    let graph = constructor.construct_graph();
+    graphml::render_graphml(&graph)
+}
+
+/// Writes the PCG graph at `location` to `file_path`, in [`OUTPUT_FORMAT`].
+/// The caller is expected to have picked `file_path`'s extension to match
+/// (see [`OutputFormat::extension`]).
 pub(crate) fn write_pcg_dot_graph_to_file<'a, 'tcx: 'a>(
     pcg: &Pcg<'tcx>,
     ctxt: CompilerCtxt<'a, 'tcx>,
@@ -381,8 +579,28 @@ pub(crate) fn write_pcg_dot_graph_to_file<'a, 'tcx: 'a>(
 ) -> io::Result<()> {
     let constructor = PcgGraphConstructor::new(pcg, ctxt, location);
     let graph = constructor.construct_graph();
-    let drawer = GraphDrawer::new(File::create(file_path).unwrap_or_else(|e| {
-        panic!("Failed to create file at path: {file_path}: {e}");
-    }));
-    drawer.draw(graph)
+    let graph = match PLACE_FILTER.as_ref() {
+        Some(filter) => graph.restrict_to(filter),
+        None => graph,
+    };
+    std::fs::write(
+        format!("{file_path}.clusters.json"),
+        graph.cluster_metadata_json().to_string(),
+    )?;
+    match *OUTPUT_FORMAT {
+        OutputFormat::Dot => {
+            let drawer = GraphDrawer::new(File::create(file_path).unwrap_or_else(|e| {
+                panic!("Failed to create file at path: {file_path}: {e}");
+            }));
+            drawer.draw(graph)
+        }
+        OutputFormat::Svg => {
+            let dot_graph = dot_graph::DotGraph {
+                name: "CapabilitySummary".to_string(),
+                nodes: graph.nodes.iter().map(|g| g.to_dot_node()).collect(),
+                edges: graph.edges.into_iter().map(|e| e.to_dot_edge()).collect(),
+            };
+            std::fs::write(file_path, svg::render_svg(&dot_graph))
+        }
+    }
 }
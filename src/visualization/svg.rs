@@ -0,0 +1,196 @@
+// © 2023, ETH Zurich
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Renders a [`DotGraph`] directly to SVG, without shelling out to the
+//! `dot` binary.
+//!
+//! The layout here is a simple layered (Sugiyama-style) placement: nodes
+//! are bucketed into layers by longest path from a source, and placed in
+//! reading order within their layer. It isn't a replacement for graphviz's
+//! layout quality (no edge crossing minimization, no curved edges), but it
+//! produces a readable, dependency-free rendering for the common case of
+//! the PCG's small per-statement graphs.
+
+use std::collections::{HashMap, VecDeque};
+
+use super::dot_graph::{DotGraph, DotLabel};
+
+const LAYER_HEIGHT: f64 = 80.0;
+const NODE_HEIGHT: f64 = 30.0;
+const NODE_H_PADDING: f64 = 20.0;
+const NODE_GAP: f64 = 30.0;
+const CHAR_WIDTH: f64 = 7.0;
+
+struct Layout {
+    /// id -> (layer, x, y, width)
+    positions: HashMap<String, (usize, f64, f64, f64)>,
+    width: f64,
+    height: f64,
+}
+
+fn plain_text(label: &DotLabel) -> String {
+    let raw = match label {
+        DotLabel::Text(text) => text.clone(),
+        DotLabel::Html(html) => html.clone(),
+    };
+    // Strip HTML tags (used for rich place-node labels); this is a
+    // best-effort plain-text fallback, not a general HTML renderer.
+    let mut out = String::new();
+    let mut in_tag = false;
+    for c in raw.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            _ if !in_tag => out.push(c),
+            _ => {}
+        }
+    }
+    out.replace("&nbsp;", " ")
+}
+
+fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn compute_layout(graph: &DotGraph) -> Layout {
+    let mut outgoing: HashMap<&str, Vec<&str>> = HashMap::new();
+    let mut indegree: HashMap<&str, usize> = HashMap::new();
+    for node in &graph.nodes {
+        indegree.entry(node.id.as_str()).or_insert(0);
+    }
+    for edge in &graph.edges {
+        outgoing.entry(edge.from.as_str()).or_default().push(&edge.to);
+        *indegree.entry(edge.to.as_str()).or_insert(0) += 1;
+    }
+
+    // Longest-path layering via Kahn's algorithm. The PCG's graphs are not
+    // guaranteed acyclic (e.g. coupled loop abstractions), so a node left
+    // over after the queue drains (on or downstream of a cycle) keeps the
+    // layer its already-processed predecessors gave it, or layer 0 if it
+    // has none.
+    let mut layer: HashMap<&str, usize> = HashMap::new();
+    let mut remaining_indegree = indegree.clone();
+    let mut queue: VecDeque<&str> = remaining_indegree
+        .iter()
+        .filter(|(_, &d)| d == 0)
+        .map(|(id, _)| *id)
+        .collect();
+    for id in &queue {
+        layer.insert(id, 0);
+    }
+    while let Some(id) = queue.pop_front() {
+        let this_layer = layer[id];
+        if let Some(succs) = outgoing.get(id) {
+            for succ in succs {
+                let entry = layer.entry(succ).or_insert(0);
+                *entry = (*entry).max(this_layer + 1);
+                if let Some(d) = remaining_indegree.get_mut(succ) {
+                    *d = d.saturating_sub(1);
+                    if *d == 0 {
+                        queue.push_back(succ);
+                    }
+                }
+            }
+        }
+    }
+    for node in &graph.nodes {
+        layer.entry(node.id.as_str()).or_insert(0);
+    }
+
+    let mut by_layer: HashMap<usize, Vec<&str>> = HashMap::new();
+    for node in &graph.nodes {
+        by_layer
+            .entry(layer[node.id.as_str()])
+            .or_default()
+            .push(&node.id);
+    }
+
+    let labels: HashMap<&str, String> = graph
+        .nodes
+        .iter()
+        .map(|n| (n.id.as_str(), plain_text(&n.label)))
+        .collect();
+
+    let mut positions = HashMap::new();
+    let mut max_width = 0.0_f64;
+    let num_layers = by_layer.keys().copied().max().map_or(0, |m| m + 1);
+    for layer_idx in 0..num_layers {
+        let Some(ids) = by_layer.get(&layer_idx) else {
+            continue;
+        };
+        let mut x = NODE_GAP;
+        for id in ids {
+            let text = &labels[id];
+            let w = (text.len() as f64 * CHAR_WIDTH) + NODE_H_PADDING * 2.0;
+            let y = NODE_GAP + layer_idx as f64 * LAYER_HEIGHT;
+            positions.insert((*id).to_string(), (layer_idx, x, y, w));
+            x += w + NODE_GAP;
+        }
+        max_width = max_width.max(x);
+    }
+
+    Layout {
+        positions,
+        width: max_width.max(NODE_GAP),
+        height: NODE_GAP + num_layers as f64 * LAYER_HEIGHT,
+    }
+}
+
+/// Renders `graph` as a standalone SVG document.
+pub(crate) fn render_svg(graph: &DotGraph) -> String {
+    let layout = compute_layout(graph);
+    let mut svg = String::new();
+    svg.push_str(&format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\" \
+         viewBox=\"0 0 {} {}\" font-family=\"monospace\" font-size=\"12\">\n",
+        layout.width, layout.height, layout.width, layout.height
+    ));
+    svg.push_str(
+        "<defs><marker id=\"arrow\" markerWidth=\"8\" markerHeight=\"8\" refX=\"7\" refY=\"4\" \
+         orient=\"auto\"><path d=\"M0,0 L8,4 L0,8 Z\" fill=\"#555\"/></marker></defs>\n",
+    );
+
+    for edge in &graph.edges {
+        let (Some(&(_, fx, fy, fw)), Some(&(_, tx, ty, tw))) = (
+            layout.positions.get(&edge.from),
+            layout.positions.get(&edge.to),
+        ) else {
+            continue;
+        };
+        let x1 = fx + fw / 2.0;
+        let y1 = fy + NODE_HEIGHT;
+        let x2 = tx + tw / 2.0;
+        let y2 = ty;
+        svg.push_str(&format!(
+            "<line x1=\"{x1}\" y1=\"{y1}\" x2=\"{x2}\" y2=\"{y2}\" stroke=\"#555\" \
+             marker-end=\"url(#arrow)\"/>\n"
+        ));
+    }
+
+    for node in &graph.nodes {
+        let Some(&(_, x, y, w)) = layout.positions.get(&node.id) else {
+            continue;
+        };
+        let text = escape_xml(&plain_text(&node.label));
+        svg.push_str(&format!(
+            "<rect x=\"{x}\" y=\"{y}\" width=\"{w}\" height=\"{NODE_HEIGHT}\" rx=\"3\" \
+             fill=\"white\" stroke=\"{color}\"/>\n",
+            color = node.color.0,
+        ));
+        svg.push_str(&format!(
+            "<text x=\"{}\" y=\"{}\" text-anchor=\"middle\" fill=\"{color}\">{text}</text>\n",
+            x + w / 2.0,
+            y + NODE_HEIGHT / 2.0 + 4.0,
+            color = node.font_color.0,
+        ));
+    }
+
+    svg.push_str("</svg>\n");
+    svg
+}
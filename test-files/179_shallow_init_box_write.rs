@@ -0,0 +1,11 @@
+// Exercises the `ShallowExclusive` capability assigned to a box right after
+// `Rvalue::ShallowInitBox` (see 87_prusti_create_box.rs), followed by a
+// write through the box's deref, which should be accepted since writing to
+// a place always grants it `Exclusive` (see `assign_post_main`).
+fn write_box(v: i32) -> Box<i32> {
+    let mut x = Box::new(v);
+    *x = v + 1;
+    x
+}
+
+fn main() {}
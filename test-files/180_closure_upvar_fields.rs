@@ -0,0 +1,15 @@
+struct S {
+    f: i32,
+    g: i32,
+}
+
+fn client(s: &mut S) {
+    let mut add_f = || s.f += 1;
+    add_f();
+    s.g += 1;
+}
+
+fn main() {
+    let mut s = S { f: 0, g: 0 };
+    client(&mut s);
+}
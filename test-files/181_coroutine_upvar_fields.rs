@@ -0,0 +1,21 @@
+#![feature(coroutines, coroutine_trait)]
+
+use std::ops::Coroutine;
+use std::pin::Pin;
+
+struct S {
+    f: i32,
+}
+
+fn make_coroutine(s: S) -> impl Coroutine<Yield = i32, Return = ()> {
+    #[coroutine]
+    move || {
+        yield s.f;
+    }
+}
+
+fn main() {
+    let s = S { f: 1 };
+    let mut co = make_coroutine(s);
+    let _ = Pin::new(&mut co).resume(());
+}
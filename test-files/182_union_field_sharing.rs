@@ -0,0 +1,18 @@
+union U {
+    a: i32,
+    b: i32,
+}
+
+fn client(u: &mut U) {
+    unsafe {
+        u.a = 1;
+        let x = u.a;
+        u.b = 2;
+        let _ = x;
+    }
+}
+
+fn main() {
+    let mut u = U { a: 0 };
+    client(&mut u);
+}
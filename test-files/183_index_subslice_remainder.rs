@@ -0,0 +1,11 @@
+fn client(s: &mut [i32]) {
+    let x = &mut s[0];
+    *x += 1;
+    let y = &s[1..];
+    let _ = y.len();
+}
+
+fn main() {
+    let mut v = vec![1, 2, 3];
+    client(&mut v);
+}
@@ -0,0 +1,16 @@
+enum E {
+    A(i32),
+    B(i32),
+}
+
+fn client(e: &mut E) {
+    match e {
+        E::A(x) => *x += 1,
+        E::B(y) => *y += 1,
+    }
+}
+
+fn main() {
+    let mut e = E::A(0);
+    client(&mut e);
+}
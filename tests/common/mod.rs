@@ -108,6 +108,11 @@ pub fn is_polonius_test_file(file: &Path) -> bool {
     file.to_str().unwrap().contains("polonius")
 }
 
+/// Runs `pcg_bin` on a single `test-files/*.rs` file, with
+/// `PCG_CHECK_ANNOTATIONS=true` so any `// PCG: <line>` / `// ~PCG: <line>`
+/// comments in the file are checked against the analysis's actual output
+/// (see `emit_and_check_annotations` in `src/utils/callbacks.rs`), not
+/// just that the analysis ran without panicking.
 #[allow(dead_code)]
 pub fn run_pcg_on_file(file: &Path) {
     let workspace_dir = PathBuf::from(std::env::var("CARGO_MANIFEST_DIR").unwrap());
@@ -136,6 +141,115 @@ pub fn run_pcg_on_file(file: &Path) {
     );
 }
 
+/// Like [`run_pcg_on_file`], but with `PCG_PARALLEL=true`, so that `file`'s
+/// functions are analyzed via the `rayon`-based driver in
+/// `run_pcg_on_all_fns` (see `crate::utils::callbacks`) instead of
+/// sequentially. Exists because no other test sets `PCG_PARALLEL`, so the
+/// parallel driver was otherwise never exercised by the test suite.
+#[allow(dead_code)]
+pub fn run_pcg_on_file_parallel(file: &Path) {
+    let workspace_dir = PathBuf::from(std::env::var("CARGO_MANIFEST_DIR").unwrap());
+    let pcs_exe = workspace_dir.join("target/debug/pcg_bin");
+    println!("Running PCG (parallel) on file: {}", file.display());
+
+    let status = Command::new(&pcs_exe)
+        .arg(file)
+        .env("PCG_CHECK_ANNOTATIONS", "true")
+        .env("PCG_PARALLEL", "true")
+        .env(
+            "PCG_POLONIUS",
+            if is_polonius_test_file(file) {
+                "true"
+            } else {
+                "false"
+            },
+        )
+        .status()
+        .unwrap_or_else(|e| panic!("Failed to execute test {}: {}", file.display(), e));
+
+    assert!(
+        status.success(),
+        "Test {} failed with status: {}",
+        file.display(),
+        status
+    );
+}
+
+/// The per-function shape `src/utils/incremental.rs`'s `CachedSummary`
+/// writes when `PCG_INCREMENTAL_CACHE_DIR` is set. Duplicated here (rather
+/// than imported) since it's private to the `pcg` crate and this is a
+/// separate test binary consuming its on-disk contract.
+#[derive(Deserialize)]
+struct IncrementalCacheEntry {
+    debug_lines: Vec<String>,
+}
+
+/// Runs `pcg_bin` on `file` with `PCG_INCREMENTAL_CACHE_DIR` pointed at a
+/// scratch directory under `target/`, then stitches the resulting
+/// per-function `debug_lines` (borrow edges, capabilities and repack
+/// actions at each statement phase; see `PcgBasicBlock::debug_lines` in
+/// `src/free_pcs/results/cursor.rs`) into one
+/// deterministic string, suitable for golden-file snapshot testing (see
+/// `tests/snapshot_test_files.rs`). `body_hash` is intentionally omitted
+/// from the result: it's a proxy for "has the MIR changed", not part of
+/// the analysis's observable behavior, and including it would make every
+/// snapshot spuriously fail on a compiler bump that reformats MIR debug
+/// output without changing what PCG concludes.
+#[allow(dead_code)]
+pub fn run_pcg_and_capture_snapshot(file: &Path) -> String {
+    let workspace_dir = PathBuf::from(std::env::var("CARGO_MANIFEST_DIR").unwrap());
+    let pcs_exe = workspace_dir.join("target/debug/pcg_bin");
+    let stem = file.file_stem().unwrap().to_str().unwrap();
+    let cache_dir = workspace_dir.join("target/snapshot_cache").join(stem);
+    let _ = std::fs::remove_dir_all(&cache_dir);
+    std::fs::create_dir_all(&cache_dir)
+        .unwrap_or_else(|e| panic!("Failed to create {}: {}", cache_dir.display(), e));
+
+    let status = Command::new(&pcs_exe)
+        .arg(file)
+        .env("PCG_INCREMENTAL_CACHE_DIR", &cache_dir)
+        .env(
+            "PCG_POLONIUS",
+            if is_polonius_test_file(file) {
+                "true"
+            } else {
+                "false"
+            },
+        )
+        .status()
+        .unwrap_or_else(|e| panic!("Failed to execute test {}: {}", file.display(), e));
+    assert!(
+        status.success(),
+        "Test {} failed with status: {}",
+        file.display(),
+        status
+    );
+
+    let mut entries: Vec<(String, IncrementalCacheEntry)> = std::fs::read_dir(&cache_dir)
+        .unwrap_or_else(|e| panic!("Failed to read {}: {}", cache_dir.display(), e))
+        .map(|entry| {
+            let path = entry.unwrap().path();
+            let item_name = path.file_stem().unwrap().to_string_lossy().into_owned();
+            let contents = std::fs::read_to_string(&path).unwrap();
+            let entry: IncrementalCacheEntry = serde_json::from_str(&contents)
+                .unwrap_or_else(|e| panic!("Failed to parse {}: {}", path.display(), e));
+            (item_name, entry)
+        })
+        .collect();
+    entries.sort_by(|a, b| a.0.cmp(&b.0));
+
+    let mut summary = String::new();
+    for (item_name, entry) in entries {
+        summary.push_str(&format!("== {item_name} ==\n"));
+        for line in &entry.debug_lines {
+            summary.push_str(line);
+            summary.push('\n');
+        }
+        summary.push('\n');
+    }
+    summary
+}
+
 pub fn crate_download_dirname(name: &str, version: &str) -> String {
     format!("./tmp/{name}-{version}")
 }
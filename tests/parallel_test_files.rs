@@ -0,0 +1,17 @@
+use std::path::PathBuf;
+
+mod common;
+
+/// Runs the same `test-files/` corpus as `check_test_files`, but with
+/// `PCG_PARALLEL=true` so the `rayon`-based driver added for
+/// `run_pcg_on_all_fns` actually gets exercised by the test suite.
+#[test]
+fn check_parallel_test_files() {
+    let workspace_dir = PathBuf::from(std::env::var("CARGO_MANIFEST_DIR").unwrap());
+    let test_dir = workspace_dir.join("test-files");
+    let test_files = common::get_test_files(&test_dir);
+
+    test_files.iter().for_each(|test_file| {
+        common::run_pcg_on_file_parallel(test_file);
+    });
+}
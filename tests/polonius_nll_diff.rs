@@ -0,0 +1,131 @@
+//! Differential testing harness: runs the `test-files` corpus through
+//! both borrow-checker backends (NLL and Polonius) and diffs the
+//! resulting borrow PCGs' capability/coupling facts, flagging any place
+//! where the two backends disagree on what's blocking what.
+//!
+//! Files under `test-files` that are Polonius-specific (see
+//! [`common::is_polonius_test_file`]) aren't NLL-checkable and are
+//! skipped.
+
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+mod common;
+use common::{get_test_files, is_polonius_test_file};
+
+#[test]
+#[ignore]
+fn diff_polonius_vs_nll() {
+    let workspace_dir = PathBuf::from(std::env::var("CARGO_MANIFEST_DIR").unwrap());
+    let pcs_exe = workspace_dir.join("target/debug/pcg_bin");
+    let test_dir = workspace_dir.join("test-files");
+    let test_files = get_test_files(&test_dir);
+
+    let tmp_dir = workspace_dir.join("tmp");
+    std::fs::create_dir_all(&tmp_dir).unwrap();
+
+    let mut divergences = Vec::new();
+    for file in test_files.iter().filter(|f| !is_polonius_test_file(f)) {
+        let nll_facts = run_and_collect_facts(&pcs_exe, &tmp_dir, file, false);
+        let polonius_facts = run_and_collect_facts(&pcs_exe, &tmp_dir, file, true);
+
+        for (location, nll_entry) in &nll_facts {
+            match polonius_facts.get(location) {
+                Some(polonius_entry) if polonius_entry == nll_entry => {}
+                Some(polonius_entry) => divergences.push(format!(
+                    "{}: {location}: NLL says {nll_entry:?}, Polonius says {polonius_entry:?}",
+                    file.display()
+                )),
+                None => divergences.push(format!(
+                    "{}: {location}: only present under NLL",
+                    file.display()
+                )),
+            }
+        }
+        for location in polonius_facts.keys() {
+            if !nll_facts.contains_key(location) {
+                divergences.push(format!(
+                    "{}: {location}: only present under Polonius",
+                    file.display()
+                ));
+            }
+        }
+    }
+
+    assert!(
+        divergences.is_empty(),
+        "Found {} divergence(s) between the NLL and Polonius backends:\n{}",
+        divergences.len(),
+        divergences.join("\n")
+    );
+}
+
+/// Runs `pcg_bin` on `file` with the given backend and collects every
+/// place's `(capability, blocked_by)` fact from the
+/// `block_N_stmt_M_queries.json` files it writes, keyed by
+/// `"block_N_stmt_M: place"` so facts about the same place at the same
+/// program point can be compared across backends. `blocked_by` edge
+/// descriptions are sorted, since they're collected from a hash map and
+/// their original order isn't a backend-meaningful fact.
+fn run_and_collect_facts(
+    pcs_exe: &Path,
+    tmp_dir: &Path,
+    file: &Path,
+    polonius: bool,
+) -> BTreeMap<String, (Option<String>, Vec<String>)> {
+    let backend = if polonius { "polonius" } else { "nll" };
+    let data_dir = tmp_dir.join(format!(
+        "{}-{backend}",
+        file.file_stem().unwrap().to_str().unwrap()
+    ));
+    if data_dir.exists() {
+        std::fs::remove_dir_all(&data_dir).unwrap();
+    }
+    std::fs::create_dir_all(&data_dir).unwrap();
+
+    let status = Command::new(pcs_exe)
+        .arg(file)
+        .env("PCG_VISUALIZATION", "true")
+        .env("PCG_VISUALIZATION_DATA_DIR", &data_dir)
+        .env("PCG_POLONIUS", if polonius { "true" } else { "false" })
+        .status()
+        .unwrap_or_else(|e| panic!("Failed to execute pcg_bin on {}: {}", file.display(), e));
+    assert!(
+        status.success(),
+        "pcg_bin failed on {} (polonius={polonius}) with status: {status}",
+        file.display()
+    );
+
+    let mut facts = BTreeMap::new();
+    for entry in std::fs::read_dir(&data_dir).unwrap() {
+        let path = entry.unwrap().path();
+        let Some(file_name) = path.file_name().and_then(|n| n.to_str()) else {
+            continue;
+        };
+        let Some(stmt_id) = file_name
+            .strip_prefix("block_")
+            .and_then(|s| s.strip_suffix("_queries.json"))
+        else {
+            continue;
+        };
+        let queries: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        for entry in queries["data"].as_array().unwrap() {
+            let place = entry["place"].as_str().unwrap().to_string();
+            let capability = entry["capability"].as_str().map(str::to_string);
+            let mut blocked_by: Vec<String> = entry["blocked_by"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|v| v.as_str().unwrap().to_string())
+                .collect();
+            blocked_by.sort();
+            facts.insert(format!("block_{stmt_id}: {place}"), (capability, blocked_by));
+        }
+    }
+    std::fs::remove_dir_all(&data_dir).unwrap();
+    facts
+}
@@ -0,0 +1,74 @@
+//! Golden-file snapshot testing for `test-files/`: for every test file, runs
+//! PCG and compares a canonical, deterministic summary (per-function
+//! borrow edges, capabilities and repack actions at each statement; see
+//! `common::run_pcg_and_capture_snapshot`) against a checked-in snapshot
+//! under `tests/snapshots/`, catching changes in analysis *results* that
+//! `check_test_files` (which only asserts "didn't crash" plus whatever
+//! `// PCG:` annotations a file happens to have) wouldn't.
+//!
+//! To create or refresh snapshots after an intentional change, run:
+//!
+//! ```sh
+//! UPDATE_SNAPSHOTS=1 cargo test --test snapshot_test_files -- --ignored
+//! ```
+//!
+//! and review the resulting diff under `tests/snapshots/` like any other
+//! source change. The `-- --ignored` is required until `tests/snapshots/`
+//! has real snapshots checked in: `check_snapshot_test_files` is `#[ignore]`d
+//! (see the attribute below) so plain `cargo test` doesn't fail every entry
+//! against an empty snapshot directory, and an ignored test only runs when
+//! explicitly asked for via `--ignored`.
+
+use std::path::PathBuf;
+
+mod common;
+
+#[test]
+#[ignore = "no snapshots are checked in yet (tests/snapshots/ has no .snap files) -- run \
+            `UPDATE_SNAPSHOTS=1 cargo test --test snapshot_test_files -- --ignored` once from an \
+            environment with a working toolchain, commit the resulting tests/snapshots/*.snap, \
+            then remove this attribute"]
+fn check_snapshot_test_files() {
+    let workspace_dir = PathBuf::from(std::env::var("CARGO_MANIFEST_DIR").unwrap());
+    let test_dir = workspace_dir.join("test-files");
+    let snapshot_dir = workspace_dir.join("tests/snapshots");
+    std::fs::create_dir_all(&snapshot_dir)
+        .unwrap_or_else(|e| panic!("Failed to create {}: {}", snapshot_dir.display(), e));
+
+    let update = std::env::var("UPDATE_SNAPSHOTS").is_ok();
+    let mut failures = Vec::new();
+
+    for test_file in common::get_test_files(&test_dir) {
+        let stem = test_file.file_stem().unwrap().to_str().unwrap();
+        let snapshot_path = snapshot_dir.join(format!("{stem}.snap"));
+        let actual = common::run_pcg_and_capture_snapshot(&test_file);
+
+        if update {
+            std::fs::write(&snapshot_path, &actual).unwrap_or_else(|e| {
+                panic!("Failed to write {}: {}", snapshot_path.display(), e)
+            });
+            continue;
+        }
+
+        match std::fs::read_to_string(&snapshot_path) {
+            Ok(expected) if expected == actual => {}
+            Ok(_) => failures.push(format!(
+                "{}: snapshot mismatch against {} -- if this change is expected, \
+                 re-run with UPDATE_SNAPSHOTS=1 and review the diff",
+                test_file.display(),
+                snapshot_path.display()
+            )),
+            Err(_) => failures.push(format!(
+                "{}: no checked-in snapshot at {} -- run with UPDATE_SNAPSHOTS=1 to create it",
+                test_file.display(),
+                snapshot_path.display()
+            )),
+        }
+    }
+
+    if update {
+        eprintln!("Wrote snapshots to {}", snapshot_dir.display());
+        return;
+    }
+    assert!(failures.is_empty(), "{}", failures.join("\n"));
+}